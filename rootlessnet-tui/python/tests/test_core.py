@@ -2,8 +2,10 @@
 Tests for RootlessNet TUI core module.
 """
 
+import json
+
 import pytest
-from rootlessnet_tui.core import Identity, Content, Messaging
+from rootlessnet_tui.core import Identity, Content, Messaging, RUST_BACKEND_AVAILABLE, _rust_core
 
 
 class TestIdentity:
@@ -169,5 +171,25 @@ class TestIntegration:
         assert decrypted == secret
 
 
+@pytest.mark.skipif(not RUST_BACKEND_AVAILABLE, reason="Rust backend not built")
+class TestRustErrors:
+    """Tests for the Rust backend's exception hierarchy."""
+    
+    def test_decryption_failure_raises_decryption_error(self):
+        """Test a tampered message raises DecryptionError, not a bare RuntimeError."""
+        alice = _rust_core.create_identity("Alice")
+        bob = _rust_core.create_identity("Bob")
+        message = json.loads(_rust_core.encrypt_message("secret", alice, bob.public_key))
+        message["ciphertext"] = message["ciphertext"][:-2] + (
+            "01" if message["ciphertext"].endswith("00") else "00"
+        )
+        
+        with pytest.raises(_rust_core.DecryptionError) as excinfo:
+            _rust_core.decrypt_message(json.dumps(message), bob, alice.public_key)
+        
+        assert isinstance(excinfo.value, _rust_core.CryptoError)
+        assert isinstance(excinfo.value, RuntimeError)
+
+
 if __name__ == "__main__":
     pytest.main([__file__, "-v"])
@@ -0,0 +1,32 @@
+//! Reused `Cipher` versus re-keying `encrypt_data` for small messages
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rootlessnet_core::{decrypt_data, encrypt_data, AeadAlgorithm, Cipher};
+
+fn bench_cipher(c: &mut Criterion) {
+    let key = [7u8; 32];
+    let messages: Vec<Vec<u8>> = (0..256).map(|i| format!("Message {}", i).into_bytes()).collect();
+    let cipher = Cipher::new(&key);
+    
+    c.bench_function("encrypt_data_256", |b| {
+        b.iter(|| {
+            messages.iter()
+                .map(|m| encrypt_data(&key, m, &[], AeadAlgorithm::default(), false).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("cipher_seal_256", |b| {
+        b.iter(|| messages.iter().map(|m| cipher.seal(m, &[]).unwrap()).collect::<Vec<_>>())
+    });
+    
+    let sealed: Vec<Vec<u8>> = messages.iter().map(|m| cipher.seal(m, &[]).unwrap()).collect();
+    c.bench_function("decrypt_data_256", |b| {
+        b.iter(|| sealed.iter().map(|m| decrypt_data(&key, m, &[]).unwrap()).collect::<Vec<_>>())
+    });
+    c.bench_function("cipher_open_256", |b| {
+        b.iter(|| sealed.iter().map(|m| cipher.open(m, &[]).unwrap()).collect::<Vec<_>>())
+    });
+}
+
+criterion_group!(benches, bench_cipher);
+criterion_main!(benches);
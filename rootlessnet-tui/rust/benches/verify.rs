@@ -0,0 +1,23 @@
+//! Batch versus sequential content verification
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rootlessnet_core::{verify_batch, Content, Identity};
+
+fn feed(len: usize) -> Vec<Content> {
+    let identity = Identity::new(None);
+    (0..len)
+        .map(|i| Content::new(format!("Post {}", i), &identity).unwrap())
+        .collect()
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let contents = feed(256);
+    
+    c.bench_function("verify_sequential_256", |b| {
+        b.iter(|| contents.iter().map(|c| c.verify().is_ok()).collect::<Vec<_>>())
+    });
+    c.bench_function("verify_batch_256", |b| b.iter(|| verify_batch(&contents)));
+}
+
+criterion_group!(benches, bench_verify);
+criterion_main!(benches);
@@ -2,19 +2,85 @@
 //!
 //! Provides content creation, signing, verification, and CID-based addressing.
 
-use crate::crypto::{hash_blake3, verify_signature, CryptoError};
-use crate::identity::{Identity, PyIdentity};
+use crate::crypto::{hash_blake3, verify_signature, CryptoError, CONTENT_DOMAIN};
+use crate::identity::{did_from_verifying_key, Identity, PyIdentity};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Content types supported by the protocol
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(try_from = "ContentTypeRepr")]
 pub enum ContentType {
     Text,
     Media,
     Document,
     Thread,
+    /// Application-defined type such as a poll or reaction
+    Custom(String),
+}
+
+impl fmt::Display for ContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentType::Text => f.write_str("Text"),
+            ContentType::Media => f.write_str("Media"),
+            ContentType::Document => f.write_str("Document"),
+            ContentType::Thread => f.write_str("Thread"),
+            ContentType::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Built-in names match case-insensitively; any other non-empty name
+/// becomes `Custom` with its case preserved.
+impl FromStr for ContentType {
+    type Err = CryptoError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ContentType::Text),
+            "media" => Ok(ContentType::Media),
+            "document" => Ok(ContentType::Document),
+            "thread" => Ok(ContentType::Thread),
+            "" => Err(CryptoError::InvalidEncoding("Content type name is empty".to_string())),
+            _ => Ok(ContentType::Custom(s.to_string())),
+        }
+    }
+}
+
+/// Serialized form of `ContentType`, checked before it is accepted
+#[derive(Deserialize)]
+enum ContentTypeRepr {
+    Text,
+    Media,
+    Document,
+    Thread,
+    Custom(String),
+}
+
+/// A custom name that parses as a built-in type would shadow it, so it is
+/// rejected rather than read as `Custom`.
+impl TryFrom<ContentTypeRepr> for ContentType {
+    type Error = String;
+    
+    fn try_from(repr: ContentTypeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            ContentTypeRepr::Text => Ok(ContentType::Text),
+            ContentTypeRepr::Media => Ok(ContentType::Media),
+            ContentTypeRepr::Document => Ok(ContentType::Document),
+            ContentTypeRepr::Thread => Ok(ContentType::Thread),
+            ContentTypeRepr::Custom(name) => match name.parse() {
+                Ok(ContentType::Custom(name)) => Ok(ContentType::Custom(name)),
+                Ok(_) => Err(format!("Custom content type {:?} shadows a built-in type", name)),
+                Err(e) => Err(e.to_string()),
+            },
+        }
+    }
 }
 
 impl Default for ContentType {
@@ -40,44 +106,76 @@ pub struct Content {
     pub created_at: u64,
     /// Cryptographic signature
     pub signature: String,
+    /// CID of the content this replies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_cid: Option<String>,
 }
 
 impl Content {
     /// Create new signed content
     pub fn new(body: String, identity: &Identity) -> Result<Self, CryptoError> {
+        Self::create(body, identity, None)
+    }
+
+    /// Create signed content replying to `parent_cid`
+    ///
+    /// The parent reference is covered by both the CID and the signature.
+    pub fn reply_to(body: String, identity: &Identity, parent_cid: String) -> Result<Self, CryptoError> {
+        Self::create(body, identity, Some(parent_cid))
+    }
+
+    fn create(body: String, identity: &Identity, parent_cid: Option<String>) -> Result<Self, CryptoError> {
         let created_at = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
         
-        // Create content hash for CID
-        let content_data = format!("{}:{}:{}", identity.did, body, created_at);
-        let content_hash = hash_blake3(content_data.as_bytes());
-        let cid = format!("bafk{}", bs58::encode(&content_hash[..16]).into_string());
-        
-        // Create signature payload
-        let signature_payload = format!("{}:{}:{}:{}", cid, identity.did, body, created_at);
-        let signature = identity.sign(signature_payload.as_bytes())?;
-        
-        Ok(Content {
+        let cid = derive_cid(&identity.did, &body, created_at, parent_cid.as_deref());
+        let mut content = Content {
             cid,
             author: identity.did.clone(),
             author_public_key: identity.public_key.clone(),
             content_type: ContentType::Text,
             body,
             created_at,
-            signature: hex::encode(signature),
-        })
+            signature: String::new(),
+            parent_cid,
+        };
+        content.signature = hex::encode(identity.sign(content.signature_payload().as_bytes())?);
+        Ok(content)
+    }
+
+    /// CID recomputed from the author, body, timestamp and parent
+    ///
+    /// Differs from `cid` if any of those fields were altered after creation.
+    pub fn derived_cid(&self) -> String {
+        derive_cid(&self.author, &self.body, self.created_at, self.parent_cid.as_deref())
+    }
+
+    /// Bytes covered by the signature
+    ///
+    /// `rootless:content:v1` and a NUL byte, then `cid:author:body:created_at`;
+    /// replies append the parent CID.
+    fn signature_payload(&self) -> String {
+        let mut payload = format!(
+            "{}\0{}:{}:{}:{}",
+            CONTENT_DOMAIN, self.cid, self.author, self.body, self.created_at
+        );
+        if let Some(parent_cid) = &self.parent_cid {
+            payload.push_str(&format!(":{}", parent_cid));
+        }
+        payload
     }
 
     /// Verify content signature
     pub fn verify(&self) -> Result<bool, CryptoError> {
-        // Reconstruct signature payload
-        let signature_payload = format!(
-            "{}:{}:{}:{}",
-            self.cid, self.author, self.body, self.created_at
-        );
-        
+        let (verifying_key, signature_bytes) = self.signing_parts()?;
+        verify_signature(&verifying_key, self.signature_payload().as_bytes(), &signature_bytes)?;
+        Ok(true)
+    }
+
+    /// Decode the author key and signature, checking the DID matches the key
+    fn signing_parts(&self) -> Result<(ed25519_dalek::VerifyingKey, Vec<u8>), CryptoError> {
         // Decode public key and signature
         let public_key_bytes = hex::decode(&self.author_public_key)
             .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
@@ -90,8 +188,11 @@ impl Content {
                 .map_err(|_| CryptoError::InvalidKey("Invalid key length".to_string()))?
         ).map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
         
-        verify_signature(&verifying_key, signature_payload.as_bytes(), &signature_bytes)?;
-        Ok(true)
+        // The author DID must belong to the signing key
+        if did_from_verifying_key(&verifying_key) != self.author {
+            return Err(CryptoError::SignatureVerificationFailed);
+        }
+        Ok((verifying_key, signature_bytes))
     }
 
     /// Export content as JSON
@@ -100,6 +201,62 @@ impl Content {
     }
 }
 
+/// Verify a feed of content, returning one result per item
+///
+/// Well-formed items are checked with a single Ed25519 batch verification;
+/// only if the batch fails is each item re-checked on its own, so one bad
+/// item never hides the result of the others.
+pub fn verify_batch(contents: &[Content]) -> Vec<bool> {
+    let mut results = vec![false; contents.len()];
+    let mut positions = Vec::with_capacity(contents.len());
+    let mut payloads = Vec::with_capacity(contents.len());
+    let mut signatures = Vec::with_capacity(contents.len());
+    let mut verifying_keys = Vec::with_capacity(contents.len());
+    
+    for (position, content) in contents.iter().enumerate() {
+        let Ok((verifying_key, signature_bytes)) = content.signing_parts() else {
+            continue;
+        };
+        let Ok(signature) = ed25519_dalek::Signature::from_slice(&signature_bytes) else {
+            continue;
+        };
+        positions.push(position);
+        payloads.push(content.signature_payload());
+        signatures.push(signature);
+        verifying_keys.push(verifying_key);
+    }
+    
+    let messages: Vec<&[u8]> = payloads.iter().map(|p| p.as_bytes()).collect();
+    if ed25519_dalek::verify_batch(&messages, &signatures, &verifying_keys).is_ok() {
+        for position in positions {
+            results[position] = true;
+        }
+    } else {
+        for position in positions {
+            results[position] = contents[position].verify().is_ok();
+        }
+    }
+    results
+}
+
+/// Collect the direct replies to `parent_cid`, in the given order
+pub fn direct_replies<'a>(parent_cid: &str, contents: &'a [Content]) -> Vec<&'a Content> {
+    contents
+        .iter()
+        .filter(|c| c.parent_cid.as_deref() == Some(parent_cid))
+        .collect()
+}
+
+/// Content Identifier for the given fields: `bafk` + base58 of BLAKE3
+fn derive_cid(author: &str, body: &str, created_at: u64, parent_cid: Option<&str>) -> String {
+    let mut content_data = format!("{}:{}:{}", author, body, created_at);
+    if let Some(parent_cid) = parent_cid {
+        content_data.push_str(&format!(":{}", parent_cid));
+    }
+    let content_hash = hash_blake3(content_data.as_bytes());
+    format!("bafk{}", bs58::encode(&content_hash[..16]).into_string())
+}
+
 /// Python wrapper for Content
 #[pyclass]
 #[derive(Clone)]
@@ -112,7 +269,15 @@ impl PyContent {
     #[new]
     pub fn new(body: String, identity: &PyIdentity) -> PyResult<Self> {
         let content = Content::new(body, identity.inner())
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            .map_err(PyErr::from)?;
+        Ok(PyContent { inner: content })
+    }
+
+    /// Create a signed reply to another content CID
+    #[staticmethod]
+    pub fn reply_to(body: String, identity: &PyIdentity, parent_cid: String) -> PyResult<Self> {
+        let content = Content::reply_to(body, identity.inner(), parent_cid)
+            .map_err(PyErr::from)?;
         Ok(PyContent { inner: content })
     }
 
@@ -140,10 +305,16 @@ impl PyContent {
         self.inner.created_at
     }
 
+    /// Get the CID this content replies to
+    #[getter]
+    pub fn parent_cid(&self) -> Option<String> {
+        self.inner.parent_cid.clone()
+    }
+
     /// Verify the content signature
     pub fn verify(&self) -> PyResult<bool> {
         self.inner.verify()
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+            .map_err(PyErr::from)
     }
 
     /// Export content as JSON
@@ -152,6 +323,20 @@ impl PyContent {
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
     }
 
+    /// Equal when the CIDs derived from both contents match
+    ///
+    /// The CID is recomputed rather than read from `cid`, so a tampered body
+    /// never compares equal to the original.
+    fn __eq__(&self, other: &Self) -> bool {
+        self.inner.derived_cid() == other.inner.derived_cid()
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inner.derived_cid().hash(&mut hasher);
+        hasher.finish()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Content(cid={}, author={}, body={}...)",
@@ -162,6 +347,13 @@ impl PyContent {
     }
 }
 
+impl PyContent {
+    /// Get inner content
+    pub fn inner(&self) -> &Content {
+        &self.inner
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,4 +386,96 @@ mod tests {
         
         assert!(content.verify().is_err());
     }
+
+    #[test]
+    fn test_mismatched_author_did_fails_verification() {
+        let identity = Identity::new(None);
+        let impersonated = Identity::new(None);
+        let mut content = Content::new("Original".to_string(), &identity).unwrap();
+        
+        // Re-sign under the real key but claim someone else's DID
+        content.author = impersonated.did.clone();
+        content.signature = hex::encode(identity.sign(content.signature_payload().as_bytes()).unwrap());
+        
+        assert!(content.verify().is_err());
+    }
+
+    #[test]
+    fn test_reply_verifies_and_is_collected() {
+        let identity = Identity::new(None);
+        let post = Content::new("Post".to_string(), &identity).unwrap();
+        let reply = Content::reply_to("Reply".to_string(), &identity, post.cid.clone()).unwrap();
+        let other = Content::new("Unrelated".to_string(), &identity).unwrap();
+        
+        assert!(reply.verify().unwrap());
+        assert_eq!(reply.parent_cid.as_deref(), Some(post.cid.as_str()));
+        
+        let contents = vec![post.clone(), reply.clone(), other];
+        let replies = direct_replies(&post.cid, &contents);
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0].cid, reply.cid);
+    }
+
+    #[test]
+    fn test_tampered_parent_cid_fails_verification() {
+        let identity = Identity::new(None);
+        let post = Content::new("Post".to_string(), &identity).unwrap();
+        let mut reply = Content::reply_to("Reply".to_string(), &identity, post.cid.clone()).unwrap();
+        
+        reply.parent_cid = Some("bafkforged".to_string());
+        assert!(reply.verify().is_err());
+        
+        reply.parent_cid = None;
+        assert!(reply.verify().is_err());
+    }
+
+    #[test]
+    fn test_verify_batch_reports_each_item() {
+        let alice = Identity::new(None);
+        let bob = Identity::new(None);
+        let valid = Content::new("Valid".to_string(), &alice).unwrap();
+        let reply = Content::reply_to("Reply".to_string(), &bob, valid.cid.clone()).unwrap();
+        
+        let mut tampered = Content::new("Original".to_string(), &bob).unwrap();
+        tampered.body = "Tampered".to_string();
+        let mut garbled = Content::new("Garbled".to_string(), &alice).unwrap();
+        garbled.signature = "not hex".to_string();
+        
+        let feed = vec![valid.clone(), tampered, reply.clone(), garbled];
+        assert_eq!(verify_batch(&feed), vec![true, false, true, false]);
+        assert_eq!(verify_batch(&[valid, reply]), vec![true, true]);
+        assert!(verify_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_custom_content_type_round_trips() {
+        let poll: ContentType = "poll".parse().unwrap();
+        assert_eq!(poll, ContentType::Custom("poll".to_string()));
+        assert_eq!("thread".parse::<ContentType>().unwrap(), ContentType::Thread);
+        assert!("".parse::<ContentType>().is_err());
+        assert_eq!(poll.to_string(), "poll");
+        
+        let json = serde_json::to_string(&poll).unwrap();
+        assert_eq!(serde_json::from_str::<ContentType>(&json).unwrap(), poll);
+        
+        // A custom name can't pass itself off as a built-in type
+        assert!(serde_json::from_str::<ContentType>(r#"{"Custom":"Text"}"#).is_err());
+        assert!(serde_json::from_str::<ContentType>(r#"{"Custom":""}"#).is_err());
+    }
+
+    #[test]
+    fn test_py_content_equality_follows_cid() {
+        let identity = Identity::new(None);
+        let content = PyContent { inner: Content::new("Hello".to_string(), &identity).unwrap() };
+        let same = PyContent { inner: serde_json::from_str(&content.inner.export().unwrap()).unwrap() };
+        
+        assert_eq!(content.inner.derived_cid(), content.inner.cid);
+        assert!(content.__eq__(&same));
+        assert_eq!(content.__hash__(), same.__hash__());
+        
+        let mut tampered = same.clone();
+        tampered.inner.body = "Tampered".to_string();
+        assert!(!content.__eq__(&tampered));
+        assert_ne!(content.__hash__(), tampered.__hash__());
+    }
 }
@@ -1,32 +1,138 @@
 //! Cryptographic primitives for RootlessNet
 //!
-//! Implements Ed25519 signatures, X25519 key exchange, XChaCha20-Poly1305 encryption,
-//! and BLAKE3 hashing.
+//! Implements Ed25519 signatures, X25519 key exchange, XChaCha20-Poly1305 encryption
+//! (optionally AES-256-GCM), and BLAKE3 hashing.
+//!
+//! Everything here except key generation, `encrypt_data` and the stream
+//! functions, which need an OS random source or `std::io`, builds without
+//! `std`.
 
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+use core::fmt;
 use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 use x25519_dalek::{PublicKey, StaticSecret};
 use chacha20poly1305::{
-    aead::{Aead, KeyInit},
+    aead::{Aead, KeyInit, Payload},
     XChaCha20Poly1305, XNonce,
 };
+#[cfg(feature = "aes")]
+use aes_gcm::{Aes256Gcm, Nonce as AesNonce};
+#[cfg(feature = "std")]
 use rand::rngs::OsRng;
-use thiserror::Error;
+use rand_core::{CryptoRng, RngCore};
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 pub enum CryptoError {
-    #[error("Invalid key format: {0}")]
     InvalidKey(String),
-    #[error("Signature verification failed")]
     SignatureVerificationFailed,
-    #[error("Encryption failed: {0}")]
     EncryptionFailed(String),
-    #[error("Decryption failed: {0}")]
     DecryptionFailed(String),
-    #[error("Key derivation failed: {0}")]
     KeyDerivationFailed(String),
+    InvalidEncoding(String),
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::InvalidKey(e) => write!(f, "Invalid key format: {}", e),
+            CryptoError::SignatureVerificationFailed => f.write_str("Signature verification failed"),
+            CryptoError::EncryptionFailed(e) => write!(f, "Encryption failed: {}", e),
+            CryptoError::DecryptionFailed(e) => write!(f, "Decryption failed: {}", e),
+            CryptoError::KeyDerivationFailed(e) => write!(f, "Key derivation failed: {}", e),
+            CryptoError::InvalidEncoding(e) => write!(f, "Invalid encoding: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CryptoError {}
+
+/// Tag bit marking a ciphertext that carries a key commitment
+const KEY_COMMITMENT_FLAG: u8 = 0x80;
+
+/// Domain tag prefixed to every content signature payload
+///
+/// Matches the `tui_protocol` tag, so a content signature can't be replayed
+/// as a message or challenge signature.
+pub const CONTENT_DOMAIN: &str = "rootless:content:v1";
+
+/// BLAKE3 context for AEAD key commitments
+const KEY_COMMITMENT_CONTEXT: &str = "RootlessNet AEAD key commitment v1";
+
+/// HKDF salt for deterministic per-message session nonces
+const SESSION_NONCE_SALT: &[u8] = b"rootlessnet-session-nonce-v1";
+
+/// Plaintext bytes per `encrypt_stream` chunk
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Progress callback for `encrypt_stream` and `decrypt_stream`
+///
+/// Called after each chunk with `(bytes_done, total_bytes)`, counting input
+/// bytes consumed. `total_bytes` is whatever the caller passed in, so it is
+/// `None` for sources of unknown length.
+#[cfg(feature = "std")]
+pub type StreamProgress<'a> = &'a mut dyn FnMut(u64, Option<u64>);
+
+/// HKDF info for per-stream subkeys
+#[cfg(feature = "std")]
+const STREAM_KEY_INFO: &[u8] = b"rootlessnet-stream-key-v1";
+
+/// Length of the random salt at the start of an encrypted stream
+#[cfg(feature = "std")]
+const STREAM_SALT_LEN: usize = 32;
+
+/// Authentication tag length, the same for both AEADs
+const AEAD_TAG_LEN: usize = 16;
+
+/// Size of the key commitment appended in committing mode
+pub const KEY_COMMITMENT_LEN: usize = 32;
+
+/// AEAD cipher used by `encrypt_data`
+///
+/// The algorithm is written as a one-byte tag in front of the ciphertext so
+/// `decrypt_data` can tell which cipher to use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AeadAlgorithm {
+    /// XChaCha20-Poly1305 with a 24-byte random nonce
+    #[default]
+    XChaCha20Poly1305,
+    /// AES-256-GCM with a 12-byte random nonce
+    #[cfg(feature = "aes")]
+    Aes256Gcm,
+}
+
+impl AeadAlgorithm {
+    /// Tag byte written in front of the ciphertext
+    pub fn tag(self) -> u8 {
+        match self {
+            AeadAlgorithm::XChaCha20Poly1305 => 1,
+            #[cfg(feature = "aes")]
+            AeadAlgorithm::Aes256Gcm => 2,
+        }
+    }
+
+    /// Algorithm for a tag byte
+    pub fn from_tag(tag: u8) -> Result<Self, CryptoError> {
+        match tag {
+            1 => Ok(AeadAlgorithm::XChaCha20Poly1305),
+            #[cfg(feature = "aes")]
+            2 => Ok(AeadAlgorithm::Aes256Gcm),
+            _ => Err(CryptoError::DecryptionFailed(format!("Unsupported AEAD algorithm tag {}", tag))),
+        }
+    }
+
+    /// Nonce length in bytes
+    pub fn nonce_len(self) -> usize {
+        match self {
+            AeadAlgorithm::XChaCha20Poly1305 => 24,
+            #[cfg(feature = "aes")]
+            AeadAlgorithm::Aes256Gcm => 12,
+        }
+    }
 }
 
 /// Generate a new Ed25519 keypair and return as hex strings
+#[cfg(feature = "std")]
 pub fn generate_keypair_hex() -> Result<(String, String), CryptoError> {
     let signing_key = SigningKey::generate(&mut OsRng);
     let verifying_key = signing_key.verifying_key();
@@ -38,6 +144,7 @@ pub fn generate_keypair_hex() -> Result<(String, String), CryptoError> {
 }
 
 /// Generate Ed25519 signing key
+#[cfg(feature = "std")]
 pub fn generate_signing_key() -> SigningKey {
     SigningKey::generate(&mut OsRng)
 }
@@ -62,6 +169,7 @@ pub fn verify_signature(
 }
 
 /// Generate X25519 static secret for key exchange
+#[cfg(feature = "std")]
 pub fn generate_x25519_secret() -> StaticSecret {
     StaticSecret::random_from_rng(OsRng)
 }
@@ -71,42 +179,379 @@ pub fn derive_shared_secret(private_key: &StaticSecret, public_key: &PublicKey)
     private_key.diffie_hellman(public_key).to_bytes()
 }
 
-/// Encrypt data using XChaCha20-Poly1305
-pub fn encrypt_data(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
-    let cipher = XChaCha20Poly1305::new_from_slice(key)
-        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
-    
+/// Encrypt data with the given AEAD algorithm
+///
+/// Output is `tag || nonce || ciphertext`. `aad` is authenticated but not
+/// encrypted; pass an empty slice when there is no context to bind.
+///
+/// Neither AEAD commits to its key, so a crafted ciphertext can decrypt
+/// under two different keys. With `committing` set, a BLAKE3 commitment to
+/// the key and nonce is appended and checked before decryption, at a cost
+/// of 32 bytes and one extra hash per call.
+#[cfg(feature = "std")]
+pub fn encrypt_data(
+    key: &[u8; 32],
+    plaintext: &[u8],
+    aad: &[u8],
+    algorithm: AeadAlgorithm,
+    committing: bool,
+) -> Result<Vec<u8>, CryptoError> {
+    encrypt_data_with_rng(&mut OsRng, key, plaintext, aad, algorithm, committing)
+}
+
+/// `encrypt_data` with the nonce drawn from `rng`
+///
+/// Lets tests inject a seeded RNG. Production code must use `encrypt_data`,
+/// which draws from `OsRng`.
+pub fn encrypt_data_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    key: &[u8; 32],
+    plaintext: &[u8],
+    aad: &[u8],
+    algorithm: AeadAlgorithm,
+    committing: bool,
+) -> Result<Vec<u8>, CryptoError> {
     // Generate random nonce
-    let mut nonce_bytes = [0u8; 24];
-    rand::RngCore::fill_bytes(&mut OsRng, &mut nonce_bytes);
-    let nonce = XNonce::from_slice(&nonce_bytes);
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill_bytes(&mut nonce_bytes);
+    encrypt_data_with_nonce(key, &nonce_bytes, plaintext, aad, algorithm, committing)
+}
+
+/// Encrypt with a caller-chosen nonce, in the same format as `encrypt_data`
+///
+/// The caller must never reuse a nonce under the same key.
+pub fn encrypt_data_with_nonce(
+    key: &[u8; 32],
+    nonce_bytes: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+    algorithm: AeadAlgorithm,
+    committing: bool,
+) -> Result<Vec<u8>, CryptoError> {
+    if nonce_bytes.len() != algorithm.nonce_len() {
+        return Err(CryptoError::EncryptionFailed("Invalid nonce length".to_string()));
+    }
+    let payload = Payload { msg: plaintext, aad };
     
-    let ciphertext = cipher
-        .encrypt(nonce, plaintext)
-        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+    let ciphertext = match algorithm {
+        AeadAlgorithm::XChaCha20Poly1305 => XChaCha20Poly1305::new_from_slice(key)
+            .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?
+            .encrypt(XNonce::from_slice(nonce_bytes), payload),
+        #[cfg(feature = "aes")]
+        AeadAlgorithm::Aes256Gcm => Aes256Gcm::new_from_slice(key)
+            .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?
+            .encrypt(AesNonce::from_slice(nonce_bytes), payload),
+    }
+    .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
     
-    // Prepend nonce to ciphertext
-    let mut result = nonce_bytes.to_vec();
+    // Prepend algorithm tag and nonce to ciphertext
+    let mut result = Vec::with_capacity(1 + nonce_bytes.len() + ciphertext.len() + KEY_COMMITMENT_LEN);
+    if committing {
+        result.push(algorithm.tag() | KEY_COMMITMENT_FLAG);
+    } else {
+        result.push(algorithm.tag());
+    }
+    result.extend(nonce_bytes);
     result.extend(ciphertext);
+    if committing {
+        result.extend(key_commitment(key, nonce_bytes).as_bytes());
+    }
     
     Ok(result)
 }
 
-/// Decrypt data using XChaCha20-Poly1305
-pub fn decrypt_data(key: &[u8; 32], ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
-    if ciphertext.len() < 24 {
+/// Decrypt data produced by `encrypt_data`
+///
+/// The algorithm and committing mode are read from the leading tag byte.
+/// That byte is not authenticated, so an attacker can strip a commitment
+/// and this function will accept the result; use `decrypt_data_committing`
+/// wherever key commitment matters. Fails unless `aad` matches the value
+/// used for encryption. An empty plaintext is valid: its ciphertext is just
+/// the tag byte, nonce and 16-byte AEAD tag. Anything shorter is rejected
+/// before decryption.
+pub fn decrypt_data(key: &[u8; 32], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    open_frame(key, ciphertext, aad, false)
+}
+
+/// Decrypt a frame from `encrypt_data` in committing mode
+///
+/// Frames without a key commitment are rejected, so a commitment can't be
+/// downgraded away by clearing the flag in the tag byte.
+pub fn decrypt_data_committing(key: &[u8; 32], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    open_frame(key, ciphertext, aad, true)
+}
+
+fn open_frame(key: &[u8; 32], ciphertext: &[u8], aad: &[u8], require_commitment: bool) -> Result<Vec<u8>, CryptoError> {
+    let frame = split_frame(ciphertext, require_commitment)?;
+    check_commitment(key, &frame)?;
+    let (algorithm, nonce) = (frame.algorithm, frame.nonce);
+    let payload = Payload { msg: frame.encrypted, aad };
+    
+    match algorithm {
+        AeadAlgorithm::XChaCha20Poly1305 => XChaCha20Poly1305::new_from_slice(key)
+            .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?
+            .decrypt(XNonce::from_slice(nonce), payload),
+        #[cfg(feature = "aes")]
+        AeadAlgorithm::Aes256Gcm => Aes256Gcm::new_from_slice(key)
+            .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?
+            .decrypt(AesNonce::from_slice(nonce), payload),
+    }
+    .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))
+}
+
+/// A ciphertext frame split into its parts
+struct Frame<'a> {
+    algorithm: AeadAlgorithm,
+    nonce: &'a [u8],
+    encrypted: &'a [u8],
+    commitment: Option<&'a [u8]>,
+}
+
+/// Parse `tag || nonce || ciphertext [|| commitment]`
+fn split_frame(ciphertext: &[u8], require_commitment: bool) -> Result<Frame<'_>, CryptoError> {
+    let (&tag, rest) = ciphertext.split_first()
+        .ok_or_else(|| CryptoError::DecryptionFailed("Ciphertext too short".to_string()))?;
+    let committing = tag & KEY_COMMITMENT_FLAG != 0;
+    if require_commitment && !committing {
+        return Err(CryptoError::DecryptionFailed("Ciphertext has no key commitment".to_string()));
+    }
+    let algorithm = AeadAlgorithm::from_tag(tag & !KEY_COMMITMENT_FLAG)?;
+    
+    let trailer_len = if committing { KEY_COMMITMENT_LEN } else { 0 };
+    if rest.len() < algorithm.nonce_len() + AEAD_TAG_LEN + trailer_len {
         return Err(CryptoError::DecryptionFailed("Ciphertext too short".to_string()));
     }
+    let (nonce, encrypted) = rest.split_at(algorithm.nonce_len());
+    let (encrypted, commitment) = encrypted.split_at(encrypted.len() - trailer_len);
+    Ok(Frame { algorithm, nonce, encrypted, commitment: committing.then_some(commitment) })
+}
+
+/// Check a frame's key commitment, if it carries one
+fn check_commitment(key: &[u8; 32], frame: &Frame<'_>) -> Result<(), CryptoError> {
+    match frame.commitment {
+        // blake3::Hash compares in constant time
+        Some(commitment) if key_commitment(key, frame.nonce) != blake3::Hash::from_bytes(commitment.try_into().unwrap()) => {
+            Err(CryptoError::DecryptionFailed("Key commitment mismatch".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// XChaCha20-Poly1305 keyed once and reused for many messages
+///
+/// `encrypt_data` and `decrypt_data` re-run key setup on every call; a
+/// session encrypting many small messages under one key should hold a
+/// `Cipher` instead. Frames are interchangeable with the stateless
+/// functions.
+#[derive(Clone)]
+pub struct Cipher {
+    key: [u8; 32],
+    aead: XChaCha20Poly1305,
+}
+
+impl Cipher {
+    /// Set up the cipher for `key`
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self { key: *key, aead: XChaCha20Poly1305::new(key.into()) }
+    }
+    
+    /// Encrypt with a random nonce, like `encrypt_data` without commitment
+    #[cfg(feature = "std")]
+    pub fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.seal_with_rng(&mut OsRng, plaintext, aad)
+    }
+    
+    /// `seal` with the nonce drawn from `rng`
+    pub fn seal_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let mut nonce = [0u8; 24];
+        rng.fill_bytes(&mut nonce);
+        let ciphertext = self.aead.encrypt(XNonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+            .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+        
+        let mut result = Vec::with_capacity(1 + nonce.len() + ciphertext.len());
+        result.push(AeadAlgorithm::XChaCha20Poly1305.tag());
+        result.extend(nonce);
+        result.extend(ciphertext);
+        Ok(result)
+    }
+    
+    /// Decrypt an XChaCha20-Poly1305 frame from `seal` or `encrypt_data`
+    ///
+    /// Like `decrypt_data`, this accepts frames whose commitment was
+    /// stripped; use `open_committing` where commitment matters.
+    pub fn open(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.open_frame(ciphertext, aad, false)
+    }
+    
+    /// Decrypt a committing frame, rejecting frames without a commitment
+    pub fn open_committing(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.open_frame(ciphertext, aad, true)
+    }
+    
+    fn open_frame(&self, ciphertext: &[u8], aad: &[u8], require_commitment: bool) -> Result<Vec<u8>, CryptoError> {
+        let frame = split_frame(ciphertext, require_commitment)?;
+        if frame.algorithm != AeadAlgorithm::XChaCha20Poly1305 {
+            return Err(CryptoError::DecryptionFailed("Cipher only opens XChaCha20-Poly1305".to_string()));
+        }
+        check_commitment(&self.key, &frame)?;
+        
+        self.aead.decrypt(XNonce::from_slice(frame.nonce), Payload { msg: frame.encrypted, aad })
+            .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))
+    }
+}
+
+impl fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cipher(<redacted>)")
+    }
+}
+
+/// Nonce for message number `counter` of a session keyed by `session_key`
+pub fn derive_session_nonce(
+    session_key: &[u8; 32],
+    counter: u64,
+    algorithm: AeadAlgorithm,
+) -> Result<Vec<u8>, CryptoError> {
+    derive_key(session_key, SESSION_NONCE_SALT, &counter.to_be_bytes(), algorithm.nonce_len())
+}
+
+/// Encrypt `reader` into `writer` in `STREAM_CHUNK_SIZE` chunks
+///
+/// The output starts with a random salt that derives a fresh subkey for
+/// the stream, so `key` can be reused across files. Each chunk is then
+/// written as a big-endian `u32` length and an `encrypt_data` frame whose
+/// nonce is derived from the chunk index. The AAD marks the
+/// last chunk, so `decrypt_stream` detects reordered, dropped or truncated
+/// chunks. Returns the number of plaintext bytes read.
+#[cfg(feature = "std")]
+pub fn encrypt_stream<R: std::io::Read, W: std::io::Write>(
+    key: &[u8; 32],
+    reader: &mut R,
+    writer: &mut W,
+    algorithm: AeadAlgorithm,
+    total_bytes: Option<u64>,
+    mut progress: Option<StreamProgress>,
+) -> Result<u64, CryptoError> {
+    let mut salt = [0u8; STREAM_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    writer.write_all(&salt).map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+    let key = &stream_key(key, &salt)?;
     
-    let cipher = XChaCha20Poly1305::new_from_slice(key)
-        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+    let mut done = 0u64;
+    let mut chunk = read_chunk(reader)?;
+    for counter in 0u64.. {
+        // Read ahead so the last chunk is known before it is sealed
+        let next = if chunk.len() == STREAM_CHUNK_SIZE { read_chunk(reader)? } else { Vec::new() };
+        let last = next.is_empty();
+        let nonce = derive_session_nonce(key, counter, algorithm)?;
+        let frame = encrypt_data_with_nonce(key, &nonce, &chunk, &[last as u8], algorithm, false)?;
+        let frame_len = u32::try_from(frame.len())
+            .map_err(|_| CryptoError::EncryptionFailed("Chunk too large".to_string()))?;
+        writer.write_all(&frame_len.to_be_bytes())
+            .and_then(|_| writer.write_all(&frame))
+            .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+        
+        done += chunk.len() as u64;
+        if let Some(progress) = progress.as_mut() {
+            progress(done, total_bytes);
+        }
+        if last {
+            break;
+        }
+        chunk = next;
+    }
+    Ok(done)
+}
+
+/// Decrypt a stream written by `encrypt_stream`
+///
+/// Progress counts ciphertext bytes read, so pass the encrypted size as
+/// `total_bytes` when it is known. Returns the number of plaintext bytes
+/// written.
+#[cfg(feature = "std")]
+pub fn decrypt_stream<R: std::io::Read, W: std::io::Write>(
+    key: &[u8; 32],
+    reader: &mut R,
+    writer: &mut W,
+    algorithm: AeadAlgorithm,
+    total_bytes: Option<u64>,
+    mut progress: Option<StreamProgress>,
+) -> Result<u64, CryptoError> {
+    let truncated = || CryptoError::DecryptionFailed("Stream truncated".to_string());
+    let mut salt = [0u8; STREAM_SALT_LEN];
+    reader.read_exact(&mut salt).map_err(|_| truncated())?;
+    let key = &stream_key(key, &salt)?;
     
-    let nonce = XNonce::from_slice(&ciphertext[..24]);
-    let encrypted = &ciphertext[24..];
+    // Largest frame `encrypt_stream` can write, checked before allocating
+    let max_frame_len = 1 + algorithm.nonce_len() + STREAM_CHUNK_SIZE + AEAD_TAG_LEN + KEY_COMMITMENT_LEN;
+    let mut consumed = STREAM_SALT_LEN as u64;
+    let mut written = 0u64;
+    for counter in 0u64.. {
+        let mut frame_len = [0u8; 4];
+        reader.read_exact(&mut frame_len).map_err(|_| truncated())?;
+        let frame_len = u32::from_be_bytes(frame_len) as usize;
+        if frame_len > max_frame_len {
+            return Err(CryptoError::DecryptionFailed("Chunk too large".to_string()));
+        }
+        let mut frame = vec![0u8; frame_len];
+        reader.read_exact(&mut frame).map_err(|_| truncated())?;
+        
+        let nonce = derive_session_nonce(key, counter, algorithm)?;
+        if frame.get(1..1 + nonce.len()) != Some(nonce.as_slice()) {
+            return Err(CryptoError::DecryptionFailed("Chunk out of order".to_string()));
+        }
+        // Only the last chunk decrypts with the last-chunk AAD
+        let (plaintext, last) = match decrypt_data(key, &frame, &[0]) {
+            Ok(plaintext) => (plaintext, false),
+            Err(_) => (decrypt_data(key, &frame, &[1])?, true),
+        };
+        writer.write_all(&plaintext)
+            .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+        
+        consumed += 4 + frame.len() as u64;
+        written += plaintext.len() as u64;
+        if let Some(progress) = progress.as_mut() {
+            progress(consumed, total_bytes);
+        }
+        if last {
+            break;
+        }
+    }
+    if reader.read(&mut [0u8; 1]).map_err(|e| CryptoError::DecryptionFailed(e.to_string()))? != 0 {
+        return Err(CryptoError::DecryptionFailed("Trailing data after last chunk".to_string()));
+    }
+    Ok(written)
+}
+
+/// Subkey for one encrypted stream
+#[cfg(feature = "std")]
+fn stream_key(key: &[u8; 32], salt: &[u8]) -> Result<[u8; 32], CryptoError> {
+    let okm = derive_key(key, salt, STREAM_KEY_INFO, 32)?;
+    Ok(okm.try_into().unwrap())
+}
+
+/// Read up to `STREAM_CHUNK_SIZE` bytes, short only at end of input
+#[cfg(feature = "std")]
+fn read_chunk<R: std::io::Read>(reader: &mut R) -> Result<Vec<u8>, CryptoError> {
+    use std::io::Read;
     
-    cipher
-        .decrypt(nonce, encrypted)
-        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))
+    let mut chunk = Vec::with_capacity(STREAM_CHUNK_SIZE);
+    reader.take(STREAM_CHUNK_SIZE as u64).read_to_end(&mut chunk)
+        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+    Ok(chunk)
+}
+
+/// Commitment to an AEAD key and nonce
+fn key_commitment(key: &[u8; 32], nonce: &[u8]) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new_derive_key(KEY_COMMITMENT_CONTEXT);
+    hasher.update(key);
+    hasher.update(nonce);
+    hasher.finalize()
 }
 
 /// Hash data using BLAKE3
@@ -114,12 +559,35 @@ pub fn hash_blake3(data: &[u8]) -> [u8; 32] {
     blake3::hash(data).into()
 }
 
-/// Derive key using HKDF-SHA256
+/// Encode bytes as base58 (Bitcoin alphabet, as used for CIDs and DIDs)
+pub fn base58_encode(data: &[u8]) -> String {
+    bs58::encode(data).into_string()
+}
+
+/// Decode a base58 string (Bitcoin alphabet)
+pub fn base58_decode(encoded: &str) -> Result<Vec<u8>, CryptoError> {
+    bs58::decode(encoded)
+        .into_vec()
+        .map_err(|e| CryptoError::InvalidEncoding(e.to_string()))
+}
+
+/// Derive key using HKDF-SHA256 (extract then expand)
 pub fn derive_key(ikm: &[u8], salt: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, CryptoError> {
-    use hkdf::Hkdf;
-    use sha2::Sha256;
-    
-    let hk = Hkdf::<Sha256>::new(Some(salt), ikm);
+    hkdf_expand(&hkdf_extract(salt, ikm), info, length)
+}
+
+/// HKDF-SHA256 extract step: a 32-byte pseudorandom key from input keying material
+pub fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+    let (prk, _) = hkdf::Hkdf::<sha2::Sha256>::extract(Some(salt), ikm);
+    prk.to_vec()
+}
+
+/// HKDF-SHA256 expand step: `length` bytes of output keying material
+///
+/// `prk` must be at least 32 bytes and `length` at most 8160 (255 blocks).
+pub fn hkdf_expand(prk: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, CryptoError> {
+    let hk = hkdf::Hkdf::<sha2::Sha256>::from_prk(prk)
+        .map_err(|e| CryptoError::KeyDerivationFailed(e.to_string()))?;
     let mut okm = vec![0u8; length];
     hk.expand(info, &mut okm)
         .map_err(|e| CryptoError::KeyDerivationFailed(e.to_string()))?;
@@ -127,7 +595,7 @@ pub fn derive_key(ikm: &[u8], salt: &[u8], info: &[u8], length: usize) -> Result
     Ok(okm)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -154,16 +622,262 @@ mod tests {
         let key = [42u8; 32];
         let plaintext = b"Secret message";
         
-        let ciphertext = encrypt_data(&key, plaintext).unwrap();
-        let decrypted = decrypt_data(&key, &ciphertext).unwrap();
+        let ciphertext = encrypt_data(&key, plaintext, &[], AeadAlgorithm::default(), false).unwrap();
+        let decrypted = decrypt_data(&key, &ciphertext, &[]).unwrap();
         
+        assert_eq!(ciphertext[0], AeadAlgorithm::XChaCha20Poly1305.tag());
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn test_decrypt_rejects_unknown_tag() {
+        let key = [42u8; 32];
+        let mut ciphertext = encrypt_data(&key, b"Secret message", &[], AeadAlgorithm::default(), false).unwrap();
+        
+        ciphertext[0] = 0xff;
+        assert!(decrypt_data(&key, &ciphertext, &[]).is_err());
+        assert!(decrypt_data(&key, &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_committing_mode_rejects_downgrade() {
+        let key = [42u8; 32];
+        let plaintext = b"Secret message";
+        let committed = encrypt_data(&key, plaintext, &[], AeadAlgorithm::default(), true).unwrap();
+        assert_eq!(decrypt_data_committing(&key, &committed, &[]).unwrap(), plaintext);
+        assert_eq!(Cipher::new(&key).open_committing(&committed, &[]).unwrap(), plaintext);
+        
+        // Clear the flag and drop the commitment: the AEAD still accepts it
+        let mut stripped = committed[..committed.len() - KEY_COMMITMENT_LEN].to_vec();
+        stripped[0] &= !KEY_COMMITMENT_FLAG;
+        assert_eq!(decrypt_data(&key, &stripped, &[]).unwrap(), plaintext);
+        for result in [
+            decrypt_data_committing(&key, &stripped, &[]),
+            Cipher::new(&key).open_committing(&stripped, &[]),
+        ] {
+            assert!(matches!(result, Err(CryptoError::DecryptionFailed(e)) if e.contains("no key commitment")));
+        }
+        
+        // A commitment to another key is caught before the AEAD runs
+        let mut mismatched = committed.clone();
+        let nonce = &committed[1..1 + AeadAlgorithm::default().nonce_len()];
+        let start = mismatched.len() - KEY_COMMITMENT_LEN;
+        mismatched[start..].copy_from_slice(key_commitment(&[7u8; 32], nonce).as_bytes());
+        assert!(matches!(
+            decrypt_data_committing(&key, &mismatched, &[]),
+            Err(CryptoError::DecryptionFailed(e)) if e.contains("commitment mismatch")
+        ));
+    }
+
+    #[cfg(feature = "aes")]
+    #[test]
+    fn test_aes_gcm_round_trip_and_tag_swap() {
+        let key = [42u8; 32];
+        let plaintext = b"Secret message";
+        
+        let ciphertext = encrypt_data(&key, plaintext, b"aad", AeadAlgorithm::Aes256Gcm, false).unwrap();
+        assert_eq!(ciphertext[0], AeadAlgorithm::Aes256Gcm.tag());
+        assert_eq!(decrypt_data(&key, &ciphertext, b"aad").unwrap(), plaintext);
+        
+        let mut swapped = ciphertext.clone();
+        swapped[0] = AeadAlgorithm::XChaCha20Poly1305.tag();
+        assert!(decrypt_data(&key, &swapped, b"aad").is_err());
+        
+        let mut swapped = encrypt_data(&key, plaintext, b"aad", AeadAlgorithm::XChaCha20Poly1305, false).unwrap();
+        swapped[0] = AeadAlgorithm::Aes256Gcm.tag();
+        assert!(decrypt_data(&key, &swapped, b"aad").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_aad() {
+        let key = [42u8; 32];
+        let plaintext = b"Secret message";
+        
+        let ciphertext = encrypt_data(&key, plaintext, b"msg-1", AeadAlgorithm::default(), false).unwrap();
+        
+        assert_eq!(decrypt_data(&key, &ciphertext, b"msg-1").unwrap(), plaintext);
+        assert!(decrypt_data(&key, &ciphertext, b"msg-2").is_err());
+        assert!(decrypt_data(&key, &ciphertext, &[]).is_err());
+    }
+
+    #[test]
+    fn test_base58_round_trip() {
+        let data = [0u8, 0, 1, 2, 3, 255];
+        let encoded = base58_encode(&data);
+        
+        assert!(encoded.starts_with("11"));
+        assert_eq!(base58_decode(&encoded).unwrap(), data);
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert!(base58_decode("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_base58_rejects_invalid_characters() {
+        // 0, O, I and l are not in the alphabet
+        assert!(matches!(base58_decode("abc0"), Err(CryptoError::InvalidEncoding(_))));
+        assert!(base58_decode("Il").is_err());
+    }
+
     #[test]
     fn test_blake3_hash() {
         let data = b"Hello, World!";
         let hash = hash_blake3(data);
         assert_eq!(hash.len(), 32);
     }
+
+    #[test]
+    fn test_empty_plaintext_and_short_ciphertext() {
+        let key = [42u8; 32];
+        
+        let ciphertext = encrypt_data(&key, b"", &[], AeadAlgorithm::default(), false).unwrap();
+        assert_eq!(ciphertext.len(), 1 + 24 + AEAD_TAG_LEN);
+        assert!(decrypt_data(&key, &ciphertext, &[]).unwrap().is_empty());
+        
+        for len in 0..ciphertext.len() {
+            assert!(matches!(
+                decrypt_data(&key, &ciphertext[..len], &[]),
+                Err(CryptoError::DecryptionFailed(_))
+            ));
+        }
+        
+        let committed = encrypt_data(&key, b"", &[], AeadAlgorithm::default(), true).unwrap();
+        assert!(decrypt_data(&key, &committed, &[]).unwrap().is_empty());
+        assert!(decrypt_data(&key, &committed[..committed.len() - 1], &[]).is_err());
+    }
+
+    #[test]
+    fn test_seeded_rng_gives_reproducible_ciphertext() {
+        use rand::{rngs::StdRng, SeedableRng};
+        
+        let key = [42u8; 32];
+        let encrypt = |seed| {
+            encrypt_data_with_rng(&mut StdRng::seed_from_u64(seed), &key, b"Secret", &[], AeadAlgorithm::default(), false)
+                .unwrap()
+        };
+        
+        assert_eq!(encrypt(1), encrypt(1));
+        assert_ne!(encrypt(1), encrypt(2));
+        assert_eq!(decrypt_data(&key, &encrypt(1), &[]).unwrap(), b"Secret");
+    }
+
+    #[test]
+    fn test_hkdf_rfc5869_case_1() {
+        let ikm = [0x0b; 22];
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let okm = "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865";
+        
+        let prk = hkdf_extract(&salt, &ikm);
+        assert_eq!(hex::encode(&prk), "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5");
+        assert_eq!(hex::encode(hkdf_expand(&prk, &info, 42).unwrap()), okm);
+        assert_eq!(hex::encode(derive_key(&ikm, &salt, &info, 42).unwrap()), okm);
+        
+        assert!(hkdf_expand(&prk[..16], &info, 42).is_err());
+        assert!(hkdf_expand(&prk, &info, 255 * 32 + 1).is_err());
+    }
+
+    #[test]
+    fn test_stream_progress_fires_per_chunk() {
+        let key = [7u8; 32];
+        let data: Vec<u8> = (0..10 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        
+        let mut calls = Vec::new();
+        let mut encrypted = Vec::new();
+        let mut record = |done, total| calls.push((done, total));
+        let read = encrypt_stream(
+            &key, &mut data.as_slice(), &mut encrypted, AeadAlgorithm::default(),
+            Some(data.len() as u64), Some(&mut record),
+        ).unwrap();
+        
+        assert_eq!(read, data.len() as u64);
+        assert_eq!(calls.len(), data.len() / STREAM_CHUNK_SIZE);
+        assert_eq!(calls[0], (STREAM_CHUNK_SIZE as u64, Some(data.len() as u64)));
+        assert_eq!(calls.last().unwrap().0, data.len() as u64);
+        
+        // Unknown total still reports progress
+        let mut decrypt_calls = Vec::new();
+        let mut decrypted = Vec::new();
+        let mut record = |done, total| decrypt_calls.push((done, total));
+        decrypt_stream(
+            &key, &mut encrypted.as_slice(), &mut decrypted, AeadAlgorithm::default(),
+            None, Some(&mut record),
+        ).unwrap();
+        assert_eq!(decrypted, data);
+        assert_eq!(decrypt_calls.len(), calls.len());
+        assert_eq!(decrypt_calls.last().unwrap(), &(encrypted.len() as u64, None));
+    }
+
+    #[test]
+    fn test_decrypt_stream_rejects_truncation() {
+        let key = [7u8; 32];
+        let data = vec![1u8; STREAM_CHUNK_SIZE * 2 + 10];
+        let mut encrypted = Vec::new();
+        encrypt_stream(&key, &mut data.as_slice(), &mut encrypted, AeadAlgorithm::default(), None, None).unwrap();
+        
+        // Drop the last chunk: the stream now ends on a non-final chunk
+        let first_two = STREAM_SALT_LEN + 2 * (4 + 1 + 24 + STREAM_CHUNK_SIZE + 16);
+        let mut sink = Vec::new();
+        assert!(decrypt_stream(&key, &mut &encrypted[..first_two], &mut sink, AeadAlgorithm::default(), None, None).is_err());
+        
+        let mut sink = Vec::new();
+        assert!(decrypt_stream(&key, &mut encrypted.as_slice(), &mut sink, AeadAlgorithm::default(), None, None).is_ok());
+        assert_eq!(sink, data);
+    }
+
+    #[test]
+    fn test_decrypt_stream_rejects_oversized_frame() {
+        let key = [7u8; 32];
+        let mut encrypted = Vec::new();
+        encrypt_stream(&key, &mut &b"data"[..], &mut encrypted, AeadAlgorithm::default(), None, None).unwrap();
+        
+        // A huge declared length fails before any frame bytes are read
+        encrypted.truncate(STREAM_SALT_LEN);
+        encrypted.extend_from_slice(&u32::MAX.to_be_bytes());
+        let mut sink = Vec::new();
+        let err = decrypt_stream(&key, &mut encrypted.as_slice(), &mut sink, AeadAlgorithm::default(), None, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("Chunk too large"));
+    }
+
+    #[test]
+    fn test_cipher_matches_stateless_functions() {
+        let key = [9u8; 32];
+        let cipher = Cipher::new(&key);
+        
+        let sealed = cipher.seal(b"hello", b"aad").unwrap();
+        assert_eq!(decrypt_data(&key, &sealed, b"aad").unwrap(), b"hello");
+        assert_eq!(cipher.open(&sealed, b"aad").unwrap(), b"hello");
+        assert!(cipher.open(&sealed, b"other").is_err());
+        
+        for committing in [false, true] {
+            let encrypted = encrypt_data(&key, b"world", b"", AeadAlgorithm::default(), committing).unwrap();
+            assert_eq!(cipher.open(&encrypted, b"").unwrap(), b"world");
+        }
+        
+        // Same nonce, same frame
+        let nonce = [3u8; 24];
+        let mut rng = FixedRng(nonce);
+        assert_eq!(
+            cipher.seal_with_rng(&mut rng, b"fixed", b"").unwrap(),
+            encrypt_data_with_nonce(&key, &nonce, b"fixed", b"", AeadAlgorithm::default(), false).unwrap()
+        );
+        
+        assert!(Cipher::new(&[1u8; 32]).open(&sealed, b"aad").is_err());
+        assert!(cipher.open(&sealed[..20], b"aad").is_err());
+    }
+    
+    /// RNG that always yields the same 24 bytes
+    struct FixedRng([u8; 24]);
+    
+    impl RngCore for FixedRng {
+        fn next_u32(&mut self) -> u32 { 0 }
+        fn next_u64(&mut self) -> u64 { 0 }
+        fn fill_bytes(&mut self, dest: &mut [u8]) { dest.copy_from_slice(&self.0[..dest.len()]); }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+    
+    impl CryptoRng for FixedRng {}
 }
@@ -0,0 +1,44 @@
+//! Python exception hierarchy for `CryptoError`
+//!
+//! Every variant maps to its own subclass of `CryptoError`, which itself
+//! subclasses `RuntimeError`, so existing `except RuntimeError` handlers
+//! keep working while new code can catch specific failures.
+
+use crate::crypto;
+use pyo3::create_exception;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+
+create_exception!(rootlessnet_core, CryptoError, PyRuntimeError, "Base class for cryptographic failures");
+create_exception!(rootlessnet_core, InvalidKeyError, CryptoError, "A key could not be parsed or has the wrong length");
+create_exception!(rootlessnet_core, SignatureError, CryptoError, "A signature did not verify");
+create_exception!(rootlessnet_core, EncryptionError, CryptoError, "Encryption failed");
+create_exception!(rootlessnet_core, DecryptionError, CryptoError, "Decryption or authentication failed");
+create_exception!(rootlessnet_core, KeyDerivationError, CryptoError, "Key derivation failed");
+create_exception!(rootlessnet_core, EncodingError, CryptoError, "Data was not validly encoded");
+
+impl From<crypto::CryptoError> for PyErr {
+    fn from(err: crypto::CryptoError) -> PyErr {
+        let message = err.to_string();
+        match err {
+            crypto::CryptoError::InvalidKey(_) => InvalidKeyError::new_err(message),
+            crypto::CryptoError::SignatureVerificationFailed => SignatureError::new_err(message),
+            crypto::CryptoError::EncryptionFailed(_) => EncryptionError::new_err(message),
+            crypto::CryptoError::DecryptionFailed(_) => DecryptionError::new_err(message),
+            crypto::CryptoError::KeyDerivationFailed(_) => KeyDerivationError::new_err(message),
+            crypto::CryptoError::InvalidEncoding(_) => EncodingError::new_err(message),
+        }
+    }
+}
+
+/// Add the exception classes to the Python module
+pub fn register(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("CryptoError", py.get_type::<CryptoError>())?;
+    m.add("InvalidKeyError", py.get_type::<InvalidKeyError>())?;
+    m.add("SignatureError", py.get_type::<SignatureError>())?;
+    m.add("EncryptionError", py.get_type::<EncryptionError>())?;
+    m.add("DecryptionError", py.get_type::<DecryptionError>())?;
+    m.add("KeyDerivationError", py.get_type::<KeyDerivationError>())?;
+    m.add("EncodingError", py.get_type::<EncodingError>())?;
+    Ok(())
+}
@@ -2,13 +2,21 @@
 //!
 //! Provides self-sovereign identity creation, management, and DID-based addressing.
 
-use crate::crypto::{generate_signing_key, sign_message, hash_blake3, CryptoError};
+use crate::crypto::{derive_key, generate_signing_key, sign_message, hash_blake3, verify_signature, CryptoError};
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use x25519_dalek::{PublicKey, StaticSecret};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// HKDF salt for child key derivation
+const CHILD_KEY_SALT: &[u8] = b"RootlessNet child key v1";
+
 /// Identity representation
+///
+/// The private key is only serialized by `to_full_json`, unless the
+/// `serialize-secrets` feature is enabled.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Identity {
     /// Decentralized Identifier (DID)
@@ -18,23 +26,79 @@ pub struct Identity {
     /// Public key (hex encoded)
     pub public_key: String,
     /// Private key (hex encoded) - stored securely
+    #[cfg_attr(not(feature = "serialize-secrets"), serde(skip_serializing))]
     private_key: String,
     /// Creation timestamp
     pub created_at: u64,
+    /// Path from the master identity (e.g. `m/0/3`), set on derived children
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+}
+
+/// Serialization view of an identity including its private key
+#[derive(Serialize)]
+struct FullIdentity<'a> {
+    did: &'a str,
+    name: &'a Option<String>,
+    public_key: &'a str,
+    private_key: &'a str,
+    created_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    derivation_path: &'a Option<String>,
+}
+
+impl fmt::Debug for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Identity")
+            .field("did", &self.did)
+            .field("name", &self.name)
+            .field("public_key", &self.public_key)
+            .field("private_key", &"<redacted>")
+            .field("created_at", &self.created_at)
+            .field("derivation_path", &self.derivation_path)
+            .finish()
+    }
 }
 
 impl Identity {
     /// Create a new identity
     pub fn new(name: Option<String>) -> Self {
-        let signing_key = generate_signing_key();
+        Self::from_signing_key(&generate_signing_key(), name)
+    }
+
+    /// Deterministically create an identity from a 32-byte seed
+    ///
+    /// The seed is the Ed25519 secret key, so it must be high-entropy and kept
+    /// secret. The same seed always yields the same keys and DID.
+    pub fn from_seed(seed: [u8; 32], name: Option<String>) -> Self {
+        Self::from_signing_key(&SigningKey::from_bytes(&seed), name)
+    }
+
+    /// Rebuild an identity from a hex key pair such as `generate_keypair` returns
+    ///
+    /// Fails unless `public_key_hex` is the verifying key of `private_key_hex`.
+    pub fn from_keys(private_key_hex: &str, public_key_hex: &str, name: Option<String>) -> Result<Self, CryptoError> {
+        let private_bytes: [u8; 32] = hex::decode(private_key_hex)
+            .map_err(|e| CryptoError::InvalidKey(e.to_string()))?
+            .try_into()
+            .map_err(|_| CryptoError::InvalidKey("Private key must be 32 bytes".to_string()))?;
+        let public_bytes = hex::decode(public_key_hex)
+            .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        
+        let signing_key = SigningKey::from_bytes(&private_bytes);
+        if signing_key.verifying_key().as_bytes().as_slice() != public_bytes.as_slice() {
+            return Err(CryptoError::InvalidKey("Public key does not match private key".to_string()));
+        }
+        Ok(Self::from_signing_key(&signing_key, name))
+    }
+
+    fn from_signing_key(signing_key: &SigningKey, name: Option<String>) -> Self {
         let verifying_key = signing_key.verifying_key();
         
         let public_key_hex = hex::encode(verifying_key.to_bytes());
         let private_key_hex = hex::encode(signing_key.to_bytes());
         
-        // Create DID using the public key hash
-        let key_hash = hash_blake3(verifying_key.to_bytes().as_ref());
-        let did = format!("did:rootless:key:{}", bs58::encode(&key_hash[..16]).into_string());
+        let did = did_from_verifying_key(&verifying_key);
         
         let created_at = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -47,20 +111,61 @@ impl Identity {
             public_key: public_key_hex,
             private_key: private_key_hex,
             created_at,
+            derivation_path: None,
         }
     }
 
+    /// Derive the child identity at `index`
+    ///
+    /// The child key is HKDF-SHA256 over this identity's private key, so it
+    /// is reproducible from the master but cannot be linked to the master's
+    /// public key by anyone else.
+    pub fn derive_child(&self, index: u32) -> Result<Identity, CryptoError> {
+        let private_bytes = hex::decode(&self.private_key)
+            .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        
+        let child_seed: [u8; 32] = derive_key(&private_bytes, CHILD_KEY_SALT, &index.to_be_bytes(), 32)?
+            .try_into()
+            .map_err(|_| CryptoError::KeyDerivationFailed("Invalid child key length".to_string()))?;
+        
+        let mut child = Self::from_signing_key(&SigningKey::from_bytes(&child_seed), self.name.clone());
+        let parent_path = self.derivation_path.as_deref().unwrap_or("m");
+        child.derivation_path = Some(format!("{}/{}", parent_path, index));
+        Ok(child)
+    }
+
+    /// Change the display name, or clear it with None
+    ///
+    /// The name is not part of the DID or of anything signed, so keys and
+    /// existing signatures are unaffected.
+    pub fn rename(&mut self, new_name: Option<String>) {
+        self.name = new_name;
+    }
+
     /// Sign data with this identity
     pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(sign_message(&self.signing_key()?, data))
+    }
+
+    /// X25519 secret matching `PublicIdentity::x25519_public` for this key
+    pub(crate) fn x25519_secret(&self) -> Result<StaticSecret, CryptoError> {
+        Ok(StaticSecret::from(self.signing_key()?.to_scalar_bytes()))
+    }
+
+    fn signing_key(&self) -> Result<SigningKey, CryptoError> {
         let private_bytes = hex::decode(&self.private_key)
             .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
         
-        let signing_key = SigningKey::from_bytes(
+        Ok(SigningKey::from_bytes(
             private_bytes.as_slice().try_into()
                 .map_err(|_| CryptoError::InvalidKey("Invalid key length".to_string()))?
-        );
-        
-        Ok(sign_message(&signing_key, data))
+        ))
+    }
+
+    /// Short display ID: base58 of the first 8 bytes of BLAKE3(public key)
+    pub fn fingerprint(&self) -> Result<String, CryptoError> {
+        let key_hash = hash_blake3(self.verifying_key()?.as_bytes());
+        Ok(bs58::encode(&key_hash[..8]).into_string())
     }
 
     /// Get the verifying key
@@ -74,14 +179,126 @@ impl Identity {
         ).map_err(|e| CryptoError::InvalidKey(e.to_string()))
     }
 
-    /// Export identity as JSON
+    /// Verify-only view of this identity, without the private key
+    pub fn public_identity(&self) -> Result<PublicIdentity, CryptoError> {
+        PublicIdentity::new(&self.public_key)
+    }
+
+    /// Export identity as JSON (public fields only)
+    ///
+    /// `import` can't read this back; it needs `to_full_json`.
+    #[deprecated(note = "use to_public_json, or to_full_json for a backup that import accepts")]
     pub fn export(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(self)
+        self.to_public_json()
     }
 
-    /// Import identity from JSON
+    /// Serialize public fields only (safe to share)
+    pub fn to_public_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "did": self.did,
+            "name": self.name,
+            "public_key": self.public_key,
+            "created_at": self.created_at,
+        }))
+    }
+
+    /// Serialize the full identity including the private key
+    pub fn to_full_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&FullIdentity {
+            did: &self.did,
+            name: &self.name,
+            public_key: &self.public_key,
+            private_key: &self.private_key,
+            created_at: self.created_at,
+            derivation_path: &self.derivation_path,
+        })
+    }
+
+    /// Import identity from `to_full_json` output
+    ///
+    /// Public-only JSON from `to_public_json` or `export` is rejected with an
+    /// error saying so, rather than a bare missing-field error.
     pub fn import(json: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(json)
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if value.get("private_key").is_none() {
+            return Err(serde::de::Error::custom(
+                "JSON holds only public fields; import needs the output of to_full_json"
+            ));
+        }
+        serde_json::from_value(value)
+    }
+}
+
+/// DID for a verifying key: BLAKE3 of the key, truncated and base58-encoded
+pub fn did_from_verifying_key(verifying_key: &VerifyingKey) -> String {
+    let key_hash = hash_blake3(verifying_key.to_bytes().as_ref());
+    format!("did:rootless:key:{}", bs58::encode(&key_hash[..16]).into_string())
+}
+
+/// Verify-only identity: a DID and public key, with no private key
+///
+/// Relays and other nodes that only check signatures should hold these
+/// rather than full identities, so private keys never reach them.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PublicIdentity {
+    did: String,
+    public_key: String,
+    verifying_key: VerifyingKey,
+}
+
+impl PublicIdentity {
+    /// Parse a hex-encoded Ed25519 public key
+    pub fn new(public_key: &str) -> Result<Self, CryptoError> {
+        let public_bytes = hex::decode(public_key)
+            .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        
+        let verifying_key = VerifyingKey::from_bytes(
+            public_bytes.as_slice().try_into()
+                .map_err(|_| CryptoError::InvalidKey("Invalid key length".to_string()))?
+        ).map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        
+        Ok(PublicIdentity {
+            did: did_from_verifying_key(&verifying_key),
+            public_key: hex::encode(verifying_key.to_bytes()),
+            verifying_key,
+        })
+    }
+
+    /// X25519 public key birationally equivalent to the Ed25519 key
+    pub(crate) fn x25519_public(&self) -> PublicKey {
+        PublicKey::from(self.verifying_key.to_montgomery().to_bytes())
+    }
+}
+
+#[pymethods]
+impl PublicIdentity {
+    /// Create a verify-only identity from a hex-encoded public key
+    #[staticmethod]
+    pub fn from_public_key(public_key: &str) -> PyResult<Self> {
+        PublicIdentity::new(public_key)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get the DID
+    #[getter]
+    pub fn did(&self) -> String {
+        self.did.clone()
+    }
+
+    /// Get the public key (hex encoded)
+    #[getter]
+    pub fn public_key(&self) -> String {
+        self.public_key.clone()
+    }
+
+    /// Check a signature made by the matching full identity
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+        verify_signature(&self.verifying_key, data, signature).is_ok()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PublicIdentity(did={})", self.did)
     }
 }
 
@@ -101,6 +318,32 @@ impl PyIdentity {
         })
     }
 
+    /// Deterministically create an identity from a 32-byte seed
+    #[staticmethod]
+    #[pyo3(signature = (seed, name=None))]
+    pub fn from_seed(seed: &[u8], name: Option<String>) -> PyResult<Self> {
+        let seed: [u8; 32] = seed.try_into()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("Seed must be 32 bytes"))?;
+        Ok(PyIdentity {
+            inner: Identity::from_seed(seed, name),
+        })
+    }
+
+    /// Create an identity from a matching hex private/public key pair
+    #[staticmethod]
+    #[pyo3(signature = (private_key_hex, public_key_hex, name=None))]
+    pub fn from_keys(private_key_hex: &str, public_key_hex: &str, name: Option<String>) -> PyResult<Self> {
+        Ok(PyIdentity {
+            inner: Identity::from_keys(private_key_hex, public_key_hex, name).map_err(PyErr::from)?,
+        })
+    }
+
+    /// Get the short fingerprint of the public key
+    pub fn fingerprint(&self) -> PyResult<String> {
+        self.inner.fingerprint()
+            .map_err(PyErr::from)
+    }
+
     /// Get the DID
     #[getter]
     pub fn did(&self) -> String {
@@ -113,6 +356,12 @@ impl PyIdentity {
         self.inner.name.clone()
     }
 
+    /// Change the display name
+    #[setter]
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.inner.rename(name);
+    }
+
     /// Get the public key (hex encoded)
     #[getter]
     pub fn public_key(&self) -> String {
@@ -125,15 +374,55 @@ impl PyIdentity {
         self.inner.created_at
     }
 
+    /// Get the derivation path (None for a master identity)
+    #[getter]
+    pub fn derivation_path(&self) -> Option<String> {
+        self.inner.derivation_path.clone()
+    }
+
+    /// Derive the child identity at `index`
+    pub fn derive_child(&self, index: u32) -> PyResult<Self> {
+        let child = self.inner.derive_child(index)
+            .map_err(PyErr::from)?;
+        Ok(PyIdentity { inner: child })
+    }
+
     /// Sign data with this identity
     pub fn sign(&self, data: &[u8]) -> PyResult<Vec<u8>> {
         self.inner.sign(data)
+            .map_err(PyErr::from)
+    }
+
+    /// Verify-only view of this identity, safe to hand to relays
+    pub fn public_identity(&self) -> PyResult<PublicIdentity> {
+        self.inner.public_identity()
+            .map_err(PyErr::from)
+    }
+
+    /// Export identity as JSON (public fields only)
+    ///
+    /// Deprecated: `import` can't read this back. Use `to_public_json` to
+    /// share an identity and `to_full_json` for a backup.
+    pub fn export(&self, py: Python<'_>) -> PyResult<String> {
+        PyErr::warn(
+            py,
+            py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+            "Identity.export is deprecated; use to_public_json, or to_full_json for a backup that import accepts",
+            1,
+        )?;
+        self.inner.to_public_json()
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
     }
 
-    /// Export identity as JSON
-    pub fn export(&self) -> PyResult<String> {
-        self.inner.export()
+    /// Serialize public fields only (safe to share)
+    pub fn to_public_json(&self) -> PyResult<String> {
+        self.inner.to_public_json()
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    }
+
+    /// Serialize the full identity including the private key
+    pub fn to_full_json(&self) -> PyResult<String> {
+        self.inner.to_full_json()
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
     }
 
@@ -157,6 +446,12 @@ impl PyIdentity {
     }
 }
 
+impl From<Identity> for PyIdentity {
+    fn from(inner: Identity) -> Self {
+        PyIdentity { inner }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,14 +472,134 @@ mod tests {
         assert_eq!(signature.len(), 64); // Ed25519 signature is 64 bytes
     }
 
+    #[test]
+    fn test_from_seed_is_deterministic() {
+        let first = Identity::from_seed([7u8; 32], Some("Alice".to_string()));
+        let second = Identity::from_seed([7u8; 32], None);
+        let other = Identity::from_seed([8u8; 32], None);
+        
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.did, second.did);
+        assert_ne!(first.public_key, other.public_key);
+        assert_eq!(first.sign(b"data").unwrap(), second.sign(b"data").unwrap());
+    }
+
+    #[test]
+    fn test_derive_child() {
+        let master = Identity::new(Some("Alice".to_string()));
+        
+        let first = master.derive_child(1).unwrap();
+        let again = master.derive_child(1).unwrap();
+        let second = master.derive_child(2).unwrap();
+        
+        assert_eq!(first.public_key, again.public_key);
+        assert_ne!(first.public_key, second.public_key);
+        assert_ne!(first.public_key, master.public_key);
+        assert_eq!(first.derivation_path.as_deref(), Some("m/1"));
+        assert_eq!(first.derive_child(0).unwrap().derivation_path.as_deref(), Some("m/1/0"));
+        
+        let imported = Identity::import(&first.to_full_json().unwrap()).unwrap();
+        assert_eq!(imported.derivation_path, first.derivation_path);
+    }
+
+    #[test]
+    fn test_fingerprint_is_deterministic_and_distinct() {
+        let identity = Identity::from_seed([1u8; 32], None);
+        assert_eq!(identity.fingerprint().unwrap(), Identity::from_seed([1u8; 32], None).fingerprint().unwrap());
+        
+        let mut seen = std::collections::HashSet::new();
+        for i in 0..10_000u32 {
+            let mut seed = [0u8; 32];
+            seed[..4].copy_from_slice(&i.to_le_bytes());
+            assert!(seen.insert(Identity::from_seed(seed, None).fingerprint().unwrap()));
+        }
+    }
+
     #[test]
     fn test_identity_export_import() {
         let identity = Identity::new(Some("Bob".to_string()));
-        let json = identity.export().unwrap();
+        let json = identity.to_full_json().unwrap();
         let imported = Identity::import(&json).unwrap();
         
         assert_eq!(identity.did, imported.did);
         assert_eq!(identity.name, imported.name);
         assert_eq!(identity.public_key, imported.public_key);
+        assert_eq!(identity.sign(b"data").unwrap(), imported.sign(b"data").unwrap());
+        
+        let err = Identity::import(&identity.to_public_json().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("to_full_json"));
+    }
+
+    #[test]
+    fn test_default_output_redacts_private_key() {
+        let identity = Identity::new(Some("Carol".to_string()));
+        
+        let debug = format!("{:?}", identity);
+        let public_json = identity.to_public_json().unwrap();
+        #[allow(deprecated)]
+        let export = identity.export().unwrap();
+        let serialized = serde_json::to_string(&identity).unwrap();
+        
+        for output in [&debug, &public_json, &export] {
+            assert!(!output.contains(&identity.private_key));
+            assert!(!output.contains(&identity.private_key[..16]));
+        }
+        // `serialize-secrets` opts the default serde output into the key
+        assert_eq!(serialized.contains(&identity.private_key), cfg!(feature = "serialize-secrets"));
+        assert!(identity.to_full_json().unwrap().contains(&identity.private_key));
+    }
+
+    #[test]
+    fn test_public_identity_verifies_full_identity_signatures() {
+        let identity = Identity::new(Some("Dana".to_string()));
+        let signature = identity.sign(b"data").unwrap();
+        
+        // PublicIdentity exposes no signing method and holds no private key
+        let public = PublicIdentity::new(&identity.public_key).unwrap();
+        assert_eq!(public.did(), identity.did);
+        assert!(public.verify(b"data", &signature));
+        assert!(!public.verify(b"other", &signature));
+        assert!(!public.verify(b"data", &signature[..32]));
+        assert!(!format!("{:?}", public).contains(&identity.private_key));
+        
+        assert_eq!(identity.public_identity().unwrap().public_key(), identity.public_key);
+        assert!(PublicIdentity::new("zz").is_err());
+        assert!(PublicIdentity::new("abcd").is_err());
+    }
+
+    #[test]
+    fn test_rename_keeps_keys_and_signatures() {
+        let mut identity = Identity::new(Some("Erin".to_string()));
+        let (did, public_key) = (identity.did.clone(), identity.public_key.clone());
+        let content = crate::content::Content::new("post".to_string(), &identity).unwrap();
+        
+        identity.rename(Some("Erin B".to_string()));
+        let exported = Identity::import(&identity.to_full_json().unwrap()).unwrap();
+        assert_eq!(exported.name.as_deref(), Some("Erin B"));
+        assert_eq!(exported.did, did);
+        assert_eq!(exported.public_key, public_key);
+        assert!(content.verify().unwrap());
+        
+        identity.rename(None);
+        assert!(!identity.to_public_json().unwrap().contains("Erin"));
+    }
+
+    #[test]
+    fn test_from_keys_requires_matching_pair() {
+        let (private_hex, public_hex) = crate::crypto::generate_keypair_hex().unwrap();
+        let (_, other_public_hex) = crate::crypto::generate_keypair_hex().unwrap();
+        
+        let identity = Identity::from_keys(&private_hex, &public_hex, Some("Alice".to_string())).unwrap();
+        assert_eq!(identity.public_key, public_hex);
+        assert_eq!(identity.name.as_deref(), Some("Alice"));
+        let signature = identity.sign(b"data").unwrap();
+        assert!(verify_signature(&identity.verifying_key().unwrap(), b"data", &signature).is_ok());
+        
+        assert!(matches!(
+            Identity::from_keys(&private_hex, &other_public_hex, None),
+            Err(CryptoError::InvalidKey(_))
+        ));
+        assert!(Identity::from_keys("zz", &public_hex, None).is_err());
+        assert!(Identity::from_keys(&private_hex[..32], &public_hex, None).is_err());
     }
 }
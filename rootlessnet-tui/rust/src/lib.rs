@@ -3,72 +3,157 @@
 //! A decentralized blockchain network protocol implementation in Rust.
 //! This library provides the cryptographic foundation for the RootlessNet protocol,
 //! including identity management, content signing, and end-to-end encrypted messaging.
+//!
+//! With `--no-default-features` only the crypto core is built. It is written
+//! against `core` and `alloc` and pulls in no OS-dependent crates, so it
+//! compiles for `wasm32-unknown-unknown`; see the feature list in `Cargo.toml`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
-use pyo3::exceptions::PyRuntimeError;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
 
 mod crypto;
+#[cfg(feature = "python")]
 mod identity;
+#[cfg(feature = "python")]
 mod content;
+#[cfg(feature = "python")]
 mod messaging;
+#[cfg(feature = "python")]
+mod exceptions;
 
 pub use crypto::*;
+#[cfg(feature = "python")]
 pub use identity::*;
+#[cfg(feature = "python")]
 pub use content::*;
+#[cfg(feature = "python")]
 pub use messaging::*;
 
 /// RootlessNet Core Python Module
+#[cfg(feature = "python")]
 #[pymodule]
-fn rootlessnet_core(_py: Python, m: &PyModule) -> PyResult<()> {
+fn rootlessnet_core(py: Python, m: &PyModule) -> PyResult<()> {
+    exceptions::register(py, m)?;
     m.add_class::<PyIdentity>()?;
+    m.add_class::<PublicIdentity>()?;
     m.add_class::<PyContent>()?;
     m.add_class::<PyMessaging>()?;
+    m.add_class::<MessageHeader>()?;
     m.add_function(wrap_pyfunction!(create_identity, m)?)?;
     m.add_function(wrap_pyfunction!(create_content, m)?)?;
     m.add_function(wrap_pyfunction!(verify_content, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_content_batch, m)?)?;
     m.add_function(wrap_pyfunction!(encrypt_message, m)?)?;
     m.add_function(wrap_pyfunction!(decrypt_message, m)?)?;
+    m.add_function(wrap_pyfunction!(py_encrypt_message_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(py_decrypt_message_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(py_encrypt_sealed_message, m)?)?;
+    m.add_function(wrap_pyfunction!(py_decrypt_sealed_message, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_message_header, m)?)?;
     m.add_function(wrap_pyfunction!(generate_keypair, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_base58, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_base58, m)?)?;
+    m.add_function(wrap_pyfunction!(py_hkdf_extract, m)?)?;
+    m.add_function(wrap_pyfunction!(py_hkdf_expand, m)?)?;
     Ok(())
 }
 
 /// Generate a new Ed25519 keypair
+#[cfg(feature = "python")]
 #[pyfunction]
 fn generate_keypair() -> PyResult<(String, String)> {
     crypto::generate_keypair_hex()
-        .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+        .map_err(PyErr::from)
+}
+
+/// Encode bytes as base58
+#[cfg(feature = "python")]
+#[pyfunction]
+fn encode_base58(data: Vec<u8>) -> String {
+    crypto::base58_encode(&data)
+}
+
+/// Decode a base58 string
+#[cfg(feature = "python")]
+#[pyfunction]
+fn decode_base58(encoded: &str) -> PyResult<Vec<u8>> {
+    crypto::base58_decode(encoded)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// HKDF-SHA256 extract step
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "hkdf_extract")]
+fn py_hkdf_extract(salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+    crypto::hkdf_extract(salt, ikm)
+}
+
+/// HKDF-SHA256 expand step
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "hkdf_expand")]
+fn py_hkdf_expand(prk: &[u8], info: &[u8], length: usize) -> PyResult<Vec<u8>> {
+    crypto::hkdf_expand(prk, info, length)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
 }
 
 /// Create a new identity
+#[cfg(feature = "python")]
 #[pyfunction]
 fn create_identity(name: Option<String>) -> PyResult<PyIdentity> {
     PyIdentity::new(name)
 }
 
 /// Create new content
+#[cfg(feature = "python")]
 #[pyfunction]
 fn create_content(content: String, identity: &PyIdentity) -> PyResult<PyContent> {
     PyContent::new(content, identity)
 }
 
 /// Verify content signature
+#[cfg(feature = "python")]
 #[pyfunction]
 fn verify_content(content: &PyContent) -> PyResult<bool> {
     content.verify()
 }
 
+/// Verify a feed of content, returning one result per item
+#[cfg(feature = "python")]
+#[pyfunction]
+fn verify_content_batch(contents: Vec<PyContent>) -> Vec<bool> {
+    let contents: Vec<Content> = contents.iter().map(|c| c.inner().clone()).collect();
+    content::verify_batch(&contents)
+}
+
 /// Encrypt a message for a recipient
+///
+/// Passing the same `idempotency_key` on a retry keeps the message ID.
+#[cfg(feature = "python")]
 #[pyfunction]
+#[pyo3(signature = (message, sender, recipient_public_key, idempotency_key=None))]
 fn encrypt_message(
     message: String,
     sender: &PyIdentity,
     recipient_public_key: String,
+    idempotency_key: Option<String>,
 ) -> PyResult<String> {
-    messaging::encrypt_message_for_recipient(&message, sender, &recipient_public_key)
-        .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    match idempotency_key {
+        Some(key) => messaging::encrypt_message_idempotent(&message, sender, &recipient_public_key, &key),
+        None => messaging::encrypt_message_for_recipient(&message, sender, &recipient_public_key),
+    }
+    .map_err(PyErr::from)
 }
 
 /// Decrypt a message
+#[cfg(feature = "python")]
 #[pyfunction]
 fn decrypt_message(
     encrypted_message: String,
@@ -76,5 +161,66 @@ fn decrypt_message(
     sender_public_key: String,
 ) -> PyResult<String> {
     messaging::decrypt_message_from_sender(&encrypted_message, recipient, &sender_public_key)
-        .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+        .map_err(PyErr::from)
+}
+
+/// Encrypt a binary payload for a recipient
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encrypt_message_bytes")]
+fn py_encrypt_message_bytes(
+    message: Vec<u8>,
+    sender: &PyIdentity,
+    recipient_public_key: String,
+) -> PyResult<String> {
+    messaging::encrypt_message_bytes(&message, sender, &recipient_public_key)
+        .map_err(PyErr::from)
+}
+
+/// Decrypt a binary payload, rejecting plaintexts over `max_size` bytes
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "decrypt_message_bytes")]
+#[pyo3(signature = (encrypted_message, recipient, sender_public_key, max_size=messaging::DEFAULT_MAX_MESSAGE_SIZE))]
+fn py_decrypt_message_bytes(
+    encrypted_message: String,
+    recipient: &PyIdentity,
+    sender_public_key: String,
+    max_size: usize,
+) -> PyResult<Vec<u8>> {
+    messaging::decrypt_message_bytes(&encrypted_message, recipient, &sender_public_key, max_size)
+        .map_err(PyErr::from)
+}
+
+/// Encrypt a message whose sender only the recipient can learn
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "encrypt_sealed_message")]
+fn py_encrypt_sealed_message(
+    message: String,
+    sender: &PyIdentity,
+    recipient_public_key: String,
+) -> PyResult<String> {
+    messaging::encrypt_sealed_message(&message, sender, &recipient_public_key)
+        .map_err(PyErr::from)
+}
+
+/// Decrypt a sealed message, returning `(sender, message)`
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "decrypt_sealed_message")]
+fn py_decrypt_sealed_message(
+    encrypted_message: String,
+    recipient: &PyIdentity,
+) -> PyResult<(PublicIdentity, String)> {
+    messaging::decrypt_sealed_message(&encrypted_message, recipient)
+        .map_err(PyErr::from)
+}
+
+/// Read an encrypted message's sender, timestamp and ID without decrypting it
+#[cfg(feature = "python")]
+#[pyfunction]
+fn parse_message_header(encrypted: &str) -> PyResult<MessageHeader> {
+    messaging::parse_header(encrypted)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
 }
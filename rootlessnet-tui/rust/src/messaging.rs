@@ -2,13 +2,24 @@
 //!
 //! Provides X25519 key exchange and XChaCha20-Poly1305 encryption for secure messaging.
 
-use crate::crypto::{encrypt_data, decrypt_data, derive_key, hash_blake3, CryptoError};
-use crate::identity::PyIdentity;
+use crate::crypto::{
+    encrypt_data_with_nonce, encrypt_data_with_rng, decrypt_data, derive_key, derive_session_nonce,
+    hash_blake3, AeadAlgorithm, CryptoError, KEY_COMMITMENT_LEN,
+};
+use crate::identity::{PublicIdentity, PyIdentity};
 use pyo3::prelude::*;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use x25519_dalek::{PublicKey, StaticSecret};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Largest plaintext `decrypt_message_from_sender` accepts by default (1 MiB)
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// Most bytes `encrypt_data` adds to a plaintext: algorithm tag, 24-byte
+/// nonce, 16-byte tag and an optional key commitment
+const MAX_CIPHERTEXT_OVERHEAD: usize = 1 + 24 + 16 + KEY_COMMITMENT_LEN;
+
 /// Encrypted message structure
 #[derive(Clone, Serialize, Deserialize)]
 pub struct EncryptedMessage {
@@ -24,49 +35,231 @@ pub struct EncryptedMessage {
     pub message_id: String,
 }
 
+/// Sealed-sender message: the sender is only visible after decryption
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SealedMessage {
+    /// Ephemeral public key for key exchange
+    pub ephemeral_public_key: String,
+    /// Encrypted `SealedPayload`
+    pub ciphertext: String,
+    /// Timestamp
+    pub timestamp: u64,
+    /// Message ID
+    pub message_id: String,
+}
+
+/// Plaintext inside a `SealedMessage`: the sender's certificate and the message
+#[derive(Serialize, Deserialize)]
+struct SealedPayload {
+    sender_public_key: String,
+    /// Sender's signature over `sealed_signing_bytes` (hex)
+    signature: String,
+    message: String,
+}
+
+/// Domain tag for the sender signature inside a sealed message
+const SEALED_SENDER_DOMAIN: &str = "rootless:sealed-sender:v1";
+
+/// HKDF info for sealed message keys, which can't name the sender
+const SEALED_KEY_INFO: &[u8] = b"rootlessnet:messaging:sealed";
+
+/// BLAKE3 key derivation context for idempotent message IDs
+const IDEMPOTENT_ID_CONTEXT: &str = "RootlessNet idempotent message id v1";
+
+/// Routing fields of an encrypted message, readable without decrypting it
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct MessageHeader {
+    /// Sender's public key
+    #[pyo3(get)]
+    pub sender_public_key: String,
+    /// Timestamp
+    #[pyo3(get)]
+    pub timestamp: u64,
+    /// Message ID
+    #[pyo3(get)]
+    pub message_id: String,
+}
+
+/// Read the header of an encrypted message
+///
+/// The sender and timestamp are unauthenticated until the message is
+/// decrypted; use them for sorting and routing, not for trust decisions.
+pub fn parse_header(encrypted_message: &str) -> Result<MessageHeader, CryptoError> {
+    serde_json::from_str(encrypted_message)
+        .map_err(|e| CryptoError::InvalidEncoding(e.to_string()))
+}
+
+/// Symmetric messaging session with deterministic nonces
+///
+/// Each message nonce is derived from the session key and a counter via HKDF,
+/// so both ends can reproduce it without sharing RNG state. The counter only
+/// moves forward, so a nonce is never reused within a session. One-shot
+/// messages keep using random nonces through `encrypt_data`.
+pub struct MessageSession {
+    key: [u8; 32],
+    next_counter: u64,
+    algorithm: AeadAlgorithm,
+}
+
+impl MessageSession {
+    /// Start a session at counter 0
+    pub fn new(session_key: [u8; 32]) -> Self {
+        MessageSession {
+            key: session_key,
+            next_counter: 0,
+            algorithm: AeadAlgorithm::default(),
+        }
+    }
+    
+    /// Counter that the next encrypted message will use
+    pub fn counter(&self) -> u64 {
+        self.next_counter
+    }
+    
+    /// Encrypt the next message in the session
+    ///
+    /// Fails once the counter is exhausted rather than wrapping around.
+    pub fn encrypt(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if self.next_counter == u64::MAX {
+            return Err(CryptoError::EncryptionFailed(
+                "Session message counter exhausted".to_string()
+            ));
+        }
+        
+        let nonce = derive_session_nonce(&self.key, self.next_counter, self.algorithm)?;
+        let ciphertext = encrypt_data_with_nonce(&self.key, &nonce, plaintext, aad, self.algorithm, false)?;
+        self.next_counter += 1;
+        Ok(ciphertext)
+    }
+    
+    /// Decrypt a message from this session
+    pub fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        decrypt_data(&self.key, ciphertext, aad)
+    }
+}
+
 /// Encrypt a message for a recipient
+///
+/// The key mixes in a static-static exchange with the sender's identity key,
+/// so only the sender can produce a message that decrypts under their
+/// public key. An empty message is allowed and decrypts to an empty string.
 pub fn encrypt_message_for_recipient(
     message: &str,
     sender: &PyIdentity,
     recipient_public_key: &str,
 ) -> Result<String, CryptoError> {
-    // Decode recipient's public key (use hash of Ed25519 key for X25519)
-    let recipient_pk_bytes = hex::decode(recipient_public_key)
-        .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
-    
+    encrypt_message_for_recipient_with_rng(&mut rand::rngs::OsRng, message.as_bytes(), sender, recipient_public_key, None)
+}
+
+/// Encrypt a binary payload for a recipient
+///
+/// Decrypt with `decrypt_message_bytes`; the text variant rejects payloads
+/// that aren't UTF-8.
+pub fn encrypt_message_bytes(
+    message: &[u8],
+    sender: &PyIdentity,
+    recipient_public_key: &str,
+) -> Result<String, CryptoError> {
+    encrypt_message_for_recipient_with_rng(&mut rand::rngs::OsRng, message, sender, recipient_public_key, None)
+}
+
+/// Hash the fields that fix an idempotent message ID
+///
+/// Each part is prefixed with its length, so moving bytes from one field
+/// to the next (key "a:b" with message "c" against key "a" with message
+/// "b:c") changes the hash.
+fn idempotent_message_hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_derive_key(IDEMPOTENT_ID_CONTEXT);
+    for part in parts {
+        hasher.update(&(part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Encrypt a message whose ID is fixed by a client-chosen idempotency key
+///
+/// Resending with the same key, sender, recipient and message yields the
+/// same `message_id`, so receivers can drop duplicate deliveries of one
+/// logical send. This only dedupes honest retries: anyone who captured the
+/// blob can still resend it verbatim, so replay protection must come from
+/// the receiver tracking IDs it has already accepted.
+pub fn encrypt_message_idempotent(
+    message: &str,
+    sender: &PyIdentity,
+    recipient_public_key: &str,
+    idempotency_key: &str,
+) -> Result<String, CryptoError> {
+    encrypt_message_for_recipient_with_rng(
+        &mut rand::rngs::OsRng,
+        message.as_bytes(),
+        sender,
+        recipient_public_key,
+        Some(idempotency_key),
+    )
+}
+
+/// `encrypt_message_for_recipient` with the ephemeral key and nonce drawn from `rng`
+///
+/// Lets tests inject a seeded RNG. Production code must use
+/// `encrypt_message_for_recipient`, which draws from `OsRng`.
+pub fn encrypt_message_for_recipient_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    message: &[u8],
+    sender: &PyIdentity,
+    recipient_public_key: &str,
+    idempotency_key: Option<&str>,
+) -> Result<String, CryptoError> {
     // Generate ephemeral X25519 keypair
-    let ephemeral_secret = StaticSecret::random_from_rng(rand::rngs::OsRng);
+    let ephemeral_secret = StaticSecret::random_from_rng(&mut *rng);
     let ephemeral_public = PublicKey::from(&ephemeral_secret);
     
-    // Derive recipient X25519 public key from Ed25519 (simplified for demo)
-    let recipient_x25519_pk = derive_x25519_from_ed25519(&recipient_pk_bytes)?;
-    let recipient_pk = PublicKey::from(recipient_x25519_pk);
+    // Recipient's X25519 key is the Montgomery form of their Ed25519 key
+    let recipient_pk = PublicIdentity::new(recipient_public_key)?.x25519_public();
     
-    // Perform key exchange
-    let shared_secret = ephemeral_secret.diffie_hellman(&recipient_pk);
+    // Ephemeral-static exchange for secrecy, static-static to bind the sender
+    let ephemeral_shared = ephemeral_secret.diffie_hellman(&recipient_pk);
+    let static_shared = sender.inner().x25519_secret()?.diffie_hellman(&recipient_pk);
+    let key_material = [ephemeral_shared.as_bytes().as_slice(), static_shared.as_bytes()].concat();
     
     // Derive encryption key
-    let info = format!("rootlessnet:messaging:{}", sender.did());
+    let info = format!("rootlessnet:messaging:{}", sender.public_key());
     let encryption_key = derive_key(
-        shared_secret.as_bytes(),
+        &key_material,
         b"rootlessnet-messaging-v2",
         info.as_bytes(),
         32,
     )?;
     
-    // Encrypt message
-    let mut key_array = [0u8; 32];
-    key_array.copy_from_slice(&encryption_key);
-    let ciphertext = encrypt_data(&key_array, message.as_bytes())?;
-    
     // Create message ID
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs();
-    let message_hash = hash_blake3(format!("{}:{}", message, timestamp).as_bytes());
+    let message_hash = match idempotency_key {
+        Some(key) => idempotent_message_hash(&[
+            sender.public_key().as_bytes(),
+            recipient_public_key.as_bytes(),
+            key.as_bytes(),
+            message,
+        ]),
+        None => hash_blake3(&[message, format!(":{}", timestamp).as_bytes()].concat()),
+    };
     let message_id = bs58::encode(&message_hash[..16]).into_string();
     
+    // Encrypt message, binding the ciphertext to its message ID
+    let mut key_array = [0u8; 32];
+    key_array.copy_from_slice(&encryption_key);
+    let ciphertext = encrypt_data_with_rng(
+        rng,
+        &key_array,
+        message,
+        message_id.as_bytes(),
+        AeadAlgorithm::default(),
+        false,
+    )?;
+    
     // Create encrypted message object
     let encrypted_msg = EncryptedMessage {
         sender_public_key: sender.public_key(),
@@ -80,12 +273,30 @@ pub fn encrypt_message_for_recipient(
         .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))
 }
 
-/// Decrypt a message from a sender
+/// Decrypt a text message from a sender
+///
+/// Plaintexts over `DEFAULT_MAX_MESSAGE_SIZE` or that aren't UTF-8 are
+/// rejected; use `decrypt_message_bytes` for binary payloads.
 pub fn decrypt_message_from_sender(
     encrypted_message: &str,
     recipient: &PyIdentity,
     sender_public_key: &str,
 ) -> Result<String, CryptoError> {
+    let plaintext = decrypt_message_bytes(encrypted_message, recipient, sender_public_key, DEFAULT_MAX_MESSAGE_SIZE)?;
+    message_text(plaintext)
+}
+
+/// Decrypt a binary message from a sender
+///
+/// Messages whose plaintext would exceed `max_size` bytes are rejected
+/// before the ciphertext is decoded, so an oversized blob costs no more
+/// than parsing its JSON.
+pub fn decrypt_message_bytes(
+    encrypted_message: &str,
+    recipient: &PyIdentity,
+    sender_public_key: &str,
+    max_size: usize,
+) -> Result<Vec<u8>, CryptoError> {
     // Parse encrypted message
     let msg: EncryptedMessage = serde_json::from_str(encrypted_message)
         .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
@@ -97,6 +308,12 @@ pub fn decrypt_message_from_sender(
         ));
     }
     
+    if msg.ciphertext.len() / 2 > max_size.saturating_add(MAX_CIPHERTEXT_OVERHEAD) {
+        return Err(CryptoError::DecryptionFailed(format!(
+            "Message exceeds the {} byte limit", max_size
+        )));
+    }
+    
     // Decode ephemeral public key
     let ephemeral_pk_bytes = hex::decode(&msg.ephemeral_public_key)
         .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
@@ -106,18 +323,18 @@ pub fn decrypt_message_from_sender(
     let ephemeral_pk = PublicKey::from(ephemeral_pk_array);
     
     // Derive recipient's X25519 secret from identity
-    let recipient_pk_bytes = hex::decode(&recipient.public_key())
-        .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
-    let recipient_secret_bytes = derive_x25519_from_ed25519(&recipient_pk_bytes)?;
-    let recipient_secret = StaticSecret::from(recipient_secret_bytes);
+    let recipient_secret = recipient.inner().x25519_secret()?;
     
-    // Perform key exchange
-    let shared_secret = recipient_secret.diffie_hellman(&ephemeral_pk);
+    // Only the claimed sender's static key reproduces the static-static secret
+    let sender_pk = PublicIdentity::new(&msg.sender_public_key)?.x25519_public();
+    let ephemeral_shared = recipient_secret.diffie_hellman(&ephemeral_pk);
+    let static_shared = recipient_secret.diffie_hellman(&sender_pk);
+    let key_material = [ephemeral_shared.as_bytes().as_slice(), static_shared.as_bytes()].concat();
     
     // Derive decryption key
     let info = format!("rootlessnet:messaging:{}", msg.sender_public_key);
     let decryption_key = derive_key(
-        shared_secret.as_bytes(),
+        &key_material,
         b"rootlessnet-messaging-v2",
         info.as_bytes(),
         32,
@@ -129,33 +346,105 @@ pub fn decrypt_message_from_sender(
     
     let mut key_array = [0u8; 32];
     key_array.copy_from_slice(&decryption_key);
-    let plaintext = decrypt_data(&key_array, &ciphertext)?;
-    
+    let plaintext = decrypt_data(&key_array, &ciphertext, msg.message_id.as_bytes())?;
+    if plaintext.len() > max_size {
+        return Err(CryptoError::DecryptionFailed(format!(
+            "Message exceeds the {} byte limit", max_size
+        )));
+    }
+    Ok(plaintext)
+}
+
+/// Interpret a decrypted plaintext as text
+fn message_text(plaintext: Vec<u8>) -> Result<String, CryptoError> {
     String::from_utf8(plaintext)
-        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))
+        .map_err(|_| CryptoError::DecryptionFailed(
+            "Message is not valid UTF-8; decrypt it as bytes".to_string()
+        ))
 }
 
-/// Derive X25519 key from Ed25519 key using RFC 8032 conversion
-/// Note: In production, use separate X25519 keypairs for better security isolation
-fn derive_x25519_from_ed25519(ed25519_key: &[u8]) -> Result<[u8; 32], CryptoError> {
-    // For proper Ed25519 to X25519 conversion, we hash the Ed25519 public key
-    // and clamp the result to make it a valid X25519 scalar
-    // This follows the approach used by libsodium's crypto_sign_ed25519_pk_to_curve25519
+/// Encrypt a message whose sender is hidden from everyone but the recipient
+///
+/// The sender's public key and a signature binding it to the recipient,
+/// message ID and text travel inside the ciphertext, so an interceptor sees
+/// only the ephemeral key, timestamp and ID.
+pub fn encrypt_sealed_message(
+    message: &str,
+    sender: &PyIdentity,
+    recipient_public_key: &str,
+) -> Result<String, CryptoError> {
+    let mut rng = rand::rngs::OsRng;
+    let recipient_pk = PublicIdentity::new(recipient_public_key)?.x25519_public();
+    let ephemeral_secret = StaticSecret::random_from_rng(rng);
+    let ephemeral_public = PublicKey::from(&ephemeral_secret);
+    let key = sealed_message_key(ephemeral_secret.diffie_hellman(&recipient_pk).as_bytes())?;
     
-    use sha2::{Sha512, Digest};
-    let mut hasher = Sha512::new();
-    hasher.update(ed25519_key);
-    let hash = hasher.finalize();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut id_seed = [0u8; 16];
+    rng.fill_bytes(&mut id_seed);
+    let message_id = bs58::encode(id_seed).into_string();
     
-    let mut result = [0u8; 32];
-    result.copy_from_slice(&hash[..32]);
+    let signature = sender.inner().sign(&sealed_signing_bytes(recipient_public_key, &message_id, message))?;
+    let payload = serde_json::to_vec(&SealedPayload {
+        sender_public_key: sender.public_key(),
+        signature: hex::encode(signature),
+        message: message.to_string(),
+    })
+    .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+    let ciphertext = encrypt_data_with_rng(&mut rng, &key, &payload, message_id.as_bytes(), AeadAlgorithm::default(), false)?;
     
-    // Clamp the scalar as per X25519 specification
-    result[0] &= 248;
-    result[31] &= 127;
-    result[31] |= 64;
+    serde_json::to_string(&SealedMessage {
+        ephemeral_public_key: hex::encode(ephemeral_public.as_bytes()),
+        ciphertext: hex::encode(ciphertext),
+        timestamp,
+        message_id,
+    })
+    .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))
+}
+
+/// Decrypt a sealed message, returning the verified sender and the text
+pub fn decrypt_sealed_message(
+    encrypted_message: &str,
+    recipient: &PyIdentity,
+) -> Result<(PublicIdentity, String), CryptoError> {
+    let msg: SealedMessage = serde_json::from_str(encrypted_message)
+        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+    let ephemeral_pk: [u8; 32] = hex::decode(&msg.ephemeral_public_key)
+        .map_err(|e| CryptoError::InvalidKey(e.to_string()))?
+        .try_into()
+        .map_err(|_| CryptoError::InvalidKey("Invalid ephemeral key length".to_string()))?;
+    
+    let recipient_secret = recipient.inner().x25519_secret()?;
+    let key = sealed_message_key(recipient_secret.diffie_hellman(&PublicKey::from(ephemeral_pk)).as_bytes())?;
+    
+    let ciphertext = hex::decode(&msg.ciphertext)
+        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
+    let payload: SealedPayload = serde_json::from_slice(&decrypt_data(&key, &ciphertext, msg.message_id.as_bytes())?)
+        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))?;
     
-    Ok(result)
+    let sender = PublicIdentity::new(&payload.sender_public_key)?;
+    let signature = hex::decode(&payload.signature)
+        .map_err(|_| CryptoError::SignatureVerificationFailed)?;
+    let signed = sealed_signing_bytes(&recipient.public_key(), &msg.message_id, &payload.message);
+    if !sender.verify(&signed, &signature) {
+        return Err(CryptoError::SignatureVerificationFailed);
+    }
+    Ok((sender, payload.message))
+}
+
+/// Message key for a sealed message from the X25519 shared secret
+fn sealed_message_key(shared_secret: &[u8]) -> Result<[u8; 32], CryptoError> {
+    derive_key(shared_secret, b"rootlessnet-messaging-v2", SEALED_KEY_INFO, 32)?
+        .try_into()
+        .map_err(|_| CryptoError::KeyDerivationFailed("Invalid key length".to_string()))
+}
+
+/// Bytes the sender signs inside a sealed message
+fn sealed_signing_bytes(recipient_public_key: &str, message_id: &str, message: &str) -> Vec<u8> {
+    format!("{}\0{}:{}:{}", SEALED_SENDER_DOMAIN, recipient_public_key, message_id, message).into_bytes()
 }
 
 /// Python wrapper for Messaging operations
@@ -170,25 +459,80 @@ impl PyMessaging {
     }
 
     /// Encrypt a message for a recipient
+    ///
+    /// Passing the same `idempotency_key` on a retry keeps the message ID.
+    #[pyo3(signature = (message, sender, recipient_public_key, idempotency_key=None))]
     pub fn encrypt(
         &self,
         message: String,
         sender: &PyIdentity,
         recipient_public_key: String,
+        idempotency_key: Option<String>,
+    ) -> PyResult<String> {
+        match idempotency_key {
+            Some(key) => encrypt_message_idempotent(&message, sender, &recipient_public_key, &key),
+            None => encrypt_message_for_recipient(&message, sender, &recipient_public_key),
+        }
+        .map_err(PyErr::from)
+    }
+
+    /// Encrypt a message without revealing the sender outside the ciphertext
+    pub fn encrypt_sealed(
+        &self,
+        message: String,
+        sender: &PyIdentity,
+        recipient_public_key: String,
     ) -> PyResult<String> {
-        encrypt_message_for_recipient(&message, sender, &recipient_public_key)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+        encrypt_sealed_message(&message, sender, &recipient_public_key)
+            .map_err(PyErr::from)
+    }
+
+    /// Decrypt a sealed message, returning `(sender, message)`
+    pub fn decrypt_sealed(
+        &self,
+        encrypted_message: String,
+        recipient: &PyIdentity,
+    ) -> PyResult<(PublicIdentity, String)> {
+        decrypt_sealed_message(&encrypted_message, recipient)
+            .map_err(PyErr::from)
     }
 
-    /// Decrypt a message from a sender
+    /// Decrypt a text message from a sender
+    #[pyo3(signature = (encrypted_message, recipient, sender_public_key, max_size=DEFAULT_MAX_MESSAGE_SIZE))]
     pub fn decrypt(
         &self,
         encrypted_message: String,
         recipient: &PyIdentity,
         sender_public_key: String,
+        max_size: usize,
+    ) -> PyResult<String> {
+        decrypt_message_bytes(&encrypted_message, recipient, &sender_public_key, max_size)
+            .and_then(message_text)
+            .map_err(PyErr::from)
+    }
+
+    /// Encrypt a binary payload for a recipient
+    pub fn encrypt_bytes(
+        &self,
+        message: Vec<u8>,
+        sender: &PyIdentity,
+        recipient_public_key: String,
     ) -> PyResult<String> {
-        decrypt_message_from_sender(&encrypted_message, recipient, &sender_public_key)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+        encrypt_message_bytes(&message, sender, &recipient_public_key)
+            .map_err(PyErr::from)
+    }
+
+    /// Decrypt a binary payload from a sender
+    #[pyo3(signature = (encrypted_message, recipient, sender_public_key, max_size=DEFAULT_MAX_MESSAGE_SIZE))]
+    pub fn decrypt_bytes(
+        &self,
+        encrypted_message: String,
+        recipient: &PyIdentity,
+        sender_public_key: String,
+        max_size: usize,
+    ) -> PyResult<Vec<u8>> {
+        decrypt_message_bytes(&encrypted_message, recipient, &sender_public_key, max_size)
+            .map_err(PyErr::from)
     }
 }
 
@@ -212,4 +556,206 @@ mod tests {
         assert_eq!(parsed.sender_public_key, msg.sender_public_key);
         assert_eq!(parsed.message_id, msg.message_id);
     }
+
+    #[test]
+    fn test_session_nonces_are_distinct_and_reproducible() {
+        let key = [7u8; 32];
+        let mut alice = MessageSession::new(key);
+        let first = alice.encrypt(b"hello", b"").unwrap();
+        let second = alice.encrypt(b"hello", b"").unwrap();
+        assert_eq!(alice.counter(), 2);
+        
+        // Tag byte, then the 24-byte nonce
+        assert_ne!(first[1..25], second[1..25]);
+        assert_eq!(first[1..25], derive_session_nonce(&key, 0, AeadAlgorithm::default()).unwrap()[..]);
+        
+        let mut replay = MessageSession::new(key);
+        assert_eq!(replay.encrypt(b"hello", b"").unwrap(), first);
+        assert_eq!(replay.decrypt(&second, b"").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_session_counter_does_not_wrap() {
+        let mut session = MessageSession::new([7u8; 32]);
+        session.next_counter = u64::MAX;
+        assert!(session.encrypt(b"hello", b"").is_err());
+        assert_eq!(session.counter(), u64::MAX);
+    }
+
+    #[test]
+    fn test_session_empty_message_round_trip() {
+        let mut session = MessageSession::new([7u8; 32]);
+        let ciphertext = session.encrypt(b"", b"msg-1").unwrap();
+        assert!(session.decrypt(&ciphertext, b"msg-1").unwrap().is_empty());
+        assert!(session.decrypt(&ciphertext[..ciphertext.len() - 1], b"msg-1").is_err());
+    }
+
+    #[test]
+    fn test_empty_message_round_trip() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        let encrypted = encrypt_message_for_recipient("", &sender, &recipient.public_key()).unwrap();
+        assert_eq!(decrypt_message_from_sender(&encrypted, &recipient, &sender.public_key()).unwrap(), "");
+    }
+
+    #[test]
+    fn test_header_parses_without_decrypting() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = crate::identity::Identity::new(None);
+        let encrypted = encrypt_message_for_recipient("hello", &sender, &recipient.public_key).unwrap();
+        
+        let header = parse_header(&encrypted).unwrap();
+        let msg: EncryptedMessage = serde_json::from_str(&encrypted).unwrap();
+        assert_eq!(header.sender_public_key, sender.public_key());
+        assert_eq!(header.message_id, msg.message_id);
+        assert_eq!(header.timestamp, msg.timestamp);
+        
+        assert!(parse_header("not json").is_err());
+    }
+
+    #[test]
+    fn test_idempotency_key_fixes_message_id() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = crate::identity::Identity::new(None).public_key;
+        let message_id = |encrypted: String| parse_header(&encrypted).unwrap().message_id;
+        
+        let first = encrypt_message_idempotent("hello", &sender, &recipient, "send-1").unwrap();
+        let retry = encrypt_message_idempotent("hello", &sender, &recipient, "send-1").unwrap();
+        let other = encrypt_message_idempotent("hello", &sender, &recipient, "send-2").unwrap();
+        
+        assert_ne!(first, retry);
+        assert_eq!(message_id(first.clone()), message_id(retry));
+        assert_ne!(message_id(first), message_id(other));
+        
+        let split_early = encrypt_message_idempotent("c", &sender, &recipient, "a:b").unwrap();
+        let split_late = encrypt_message_idempotent("b:c", &sender, &recipient, "a").unwrap();
+        assert_ne!(message_id(split_early), message_id(split_late));
+    }
+
+    #[test]
+    fn test_sealed_sender_hidden_until_decrypted() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        let sealed = encrypt_sealed_message("hello", &sender, &recipient.public_key()).unwrap();
+        
+        assert!(!sealed.contains(&sender.public_key()));
+        assert!(!sealed.contains(&sender.did()));
+        assert!(parse_header(&sealed).is_err());
+        
+        let (from, message) = decrypt_sealed_message(&sealed, &recipient).unwrap();
+        assert_eq!(from.public_key(), sender.public_key());
+        assert_eq!(message, "hello");
+        
+        let eavesdropper = PyIdentity::from(crate::identity::Identity::new(None));
+        assert!(decrypt_sealed_message(&sealed, &eavesdropper).is_err());
+    }
+
+    #[test]
+    fn test_sealed_sender_signature_is_checked() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let impostor = crate::identity::Identity::new(None);
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        let sealed = encrypt_sealed_message("hello", &sender, &recipient.public_key()).unwrap();
+        
+        // Re-seal the same payload under a claimed sender who never signed it
+        let msg: SealedMessage = serde_json::from_str(&sealed).unwrap();
+        let recipient_secret = recipient.inner().x25519_secret().unwrap();
+        let ephemeral: [u8; 32] = hex::decode(&msg.ephemeral_public_key).unwrap().try_into().unwrap();
+        let key = sealed_message_key(recipient_secret.diffie_hellman(&PublicKey::from(ephemeral)).as_bytes()).unwrap();
+        let plaintext = decrypt_data(&key, &hex::decode(&msg.ciphertext).unwrap(), msg.message_id.as_bytes()).unwrap();
+        let mut payload: SealedPayload = serde_json::from_slice(&plaintext).unwrap();
+        payload.sender_public_key = impostor.public_key.clone();
+        
+        let forged = SealedMessage {
+            ciphertext: hex::encode(crate::crypto::encrypt_data(
+                &key, &serde_json::to_vec(&payload).unwrap(), msg.message_id.as_bytes(), AeadAlgorithm::default(), false,
+            ).unwrap()),
+            ..msg
+        };
+        let forged = serde_json::to_string(&forged).unwrap();
+        assert!(matches!(
+            decrypt_sealed_message(&forged, &recipient),
+            Err(CryptoError::SignatureVerificationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_one_shot_message_round_trip() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        let encrypted = encrypt_message_for_recipient("hello", &sender, &recipient.public_key()).unwrap();
+        
+        assert_eq!(decrypt_message_from_sender(&encrypted, &recipient, &sender.public_key()).unwrap(), "hello");
+        
+        let eavesdropper = PyIdentity::from(crate::identity::Identity::new(None));
+        assert!(decrypt_message_from_sender(&encrypted, &eavesdropper, &sender.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_one_shot_sender_cannot_be_spoofed() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let impostor = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        
+        // The impostor claims the sender's key but holds only their own
+        // secret: they can do the ephemeral exchange alone, which was all
+        // the key used to need, or add their own static exchange
+        let recipient_pk = PublicIdentity::new(&recipient.public_key()).unwrap().x25519_public();
+        let ephemeral_secret = StaticSecret::random_from_rng(rand::rngs::OsRng);
+        let ephemeral_shared = ephemeral_secret.diffie_hellman(&recipient_pk);
+        let impostor_shared = impostor.inner().x25519_secret().unwrap().diffie_hellman(&recipient_pk);
+        let info = format!("rootlessnet:messaging:{}", sender.public_key());
+        
+        for key_material in [
+            ephemeral_shared.as_bytes().to_vec(),
+            [ephemeral_shared.as_bytes().as_slice(), impostor_shared.as_bytes()].concat(),
+        ] {
+            let key: [u8; 32] = derive_key(&key_material, b"rootlessnet-messaging-v2", info.as_bytes(), 32)
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let spoofed = serde_json::to_string(&EncryptedMessage {
+                sender_public_key: sender.public_key(),
+                ephemeral_public_key: hex::encode(PublicKey::from(&ephemeral_secret).as_bytes()),
+                ciphertext: hex::encode(crate::crypto::encrypt_data(
+                    &key, b"pay mallory", b"id", AeadAlgorithm::default(), false,
+                ).unwrap()),
+                timestamp: 0,
+                message_id: "id".to_string(),
+            }).unwrap();
+            
+            assert!(decrypt_message_from_sender(&spoofed, &recipient, &sender.public_key()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_binary_message_round_trip() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        let payload = [0xff, 0x00, 0xfe, 0x80, 0x01];
+        let encrypted = encrypt_message_bytes(&payload, &sender, &recipient.public_key()).unwrap();
+        
+        let decrypted = decrypt_message_bytes(&encrypted, &recipient, &sender.public_key(), 64).unwrap();
+        assert_eq!(decrypted, payload);
+        assert!(decrypt_message_from_sender(&encrypted, &recipient, &sender.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_oversized_plaintext_rejected() {
+        let sender = PyIdentity::from(crate::identity::Identity::new(None));
+        let recipient = PyIdentity::from(crate::identity::Identity::new(None));
+        let encrypted = encrypt_message_bytes(&[7u8; 100], &sender, &recipient.public_key()).unwrap();
+        
+        assert!(decrypt_message_bytes(&encrypted, &recipient, &sender.public_key(), 100).is_ok());
+        assert!(decrypt_message_bytes(&encrypted, &recipient, &sender.public_key(), 99).is_err());
+        
+        // A huge ciphertext is turned away before it is decoded
+        let mut msg: EncryptedMessage = serde_json::from_str(&encrypted).unwrap();
+        msg.ciphertext = "00".repeat(DEFAULT_MAX_MESSAGE_SIZE + MAX_CIPHERTEXT_OVERHEAD + 1);
+        let huge = serde_json::to_string(&msg).unwrap();
+        assert!(matches!(
+            decrypt_message_from_sender(&huge, &recipient, &sender.public_key()),
+            Err(CryptoError::DecryptionFailed(e)) if e.contains("limit")
+        ));
+    }
 }
@@ -2,11 +2,203 @@
 //! Simple blockchain implementation for decentralized content storage
 
 use pyo3::prelude::*;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
-use chrono::Utc;
+use chrono::{SecondsFormat, Utc};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io::{BufReader, Cursor, Read};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use memmap2::Mmap;
 
-use crate::content::Content;
+use crate::content::{to_rfc3339, Content, ContentType};
+use crate::crypto::{canonical_value, domain_separated, ATTESTATION_DOMAIN, BLOCK_COAUTHOR_DOMAIN, BLOCK_DOMAIN, CHECKPOINT_DOMAIN};
+use crate::schema::{self, default_schema_version, SCHEMA_VERSION};
+use crate::identity::{verifying_key_from_public_key, UserIdentity};
+
+/// Maximum number of seconds a block timestamp may be ahead of local time
+const MAX_FUTURE_DRIFT_SECS: i64 = 120;
+
+/// Maximum nonce tried before mining gives up
+const MAX_MINING_NONCE: u64 = 1_000_000;
+
+/// Default number of nonces between progress callbacks
+const MINING_PROGRESS_INTERVAL: u64 = 10_000;
+
+/// Default cap on a block's content data (16 MiB)
+const DEFAULT_MAX_CONTENT_SIZE: usize = 16 * 1024 * 1024;
+
+/// Default cap on a serialized block (20 MiB)
+const DEFAULT_MAX_BLOCK_SIZE: usize = 20 * 1024 * 1024;
+
+/// Lowest difficulty a block after genesis may claim, unless lowered locally
+const DEFAULT_MIN_DIFFICULTY: usize = 1;
+
+/// Highest difficulty whose expected 16^difficulty attempts fit within
+/// `MAX_MINING_NONCE`, so mining can be expected to finish
+const MAX_DIFFICULTY: usize = MAX_MINING_NONCE.ilog(16) as usize;
+
+/// Recent blocks by one author that raise their difficulty by one step
+const AUTHOR_BLOCKS_PER_DIFFICULTY_STEP: usize = 5;
+
+/// Hashes timed by the lazy calibration behind `estimate_mine_time_secs`
+const CALIBRATION_HASHES: u64 = 2_000;
+
+/// Outcome of a proof-of-work search
+#[pyclass]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MiningStatus {
+    /// A hash meeting the difficulty was found
+    Mined,
+    /// Mining was stopped through the cancellation flag
+    Cancelled,
+    /// All nonces were tried without meeting the difficulty
+    Exhausted,
+}
+
+/// Hash function used to link blocks
+#[pyclass]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashAlgo {
+    /// SHA-256, used by all chains created before the choice existed
+    #[default]
+    Sha256,
+    /// BLAKE3, considerably faster for mining and validation
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Hex digest of `data`
+    fn hex_digest(self, data: &[u8]) -> String {
+        match self {
+            HashAlgo::Sha256 => hex::encode(Sha256::digest(data)),
+            HashAlgo::Blake3 => blake3::hash(data).to_hex().to_string(),
+        }
+    }
+}
+
+/// Shared flag used to stop a running miner from another thread
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct MiningCancelToken {
+    flag: Arc<AtomicBool>,
+}
+
+#[pymethods]
+impl MiningCancelToken {
+    /// Create a new, unset cancellation token
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Request that the miner stops
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+    
+    /// Check whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+impl MiningCancelToken {
+    /// Get the underlying flag to pass to `Block::mine_with`
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.flag)
+    }
+}
+
+/// A notary's signature over a block hash and the time it saw the block
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Attestation {
+    /// Notary's public key
+    #[pyo3(get)]
+    pub notary: String,
+    
+    /// Unix timestamp asserted by the notary
+    #[pyo3(get)]
+    pub timestamp: i64,
+    
+    /// Notary's Ed25519 signature over `hash:timestamp` (hex)
+    #[pyo3(get)]
+    pub signature: String,
+}
+
+impl Attestation {
+    /// Bytes signed by the notary
+    fn payload(block_hash: &str, timestamp: i64) -> Vec<u8> {
+        domain_separated(ATTESTATION_DOMAIN, format!("{}:{}", block_hash, timestamp).as_bytes())
+    }
+    
+    /// Check the signature against the notary's public key
+    fn verify(&self, block_hash: &str) -> bool {
+        verify_hex_signature(&self.notary, &Self::payload(block_hash, self.timestamp), &self.signature)
+    }
+}
+
+/// An authority's signature vouching for the block hash at a height
+///
+/// Lets new nodes of a private deployment trust every block up to the
+/// checkpoint without validating them one by one.
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Index of the checkpointed block
+    #[pyo3(get)]
+    pub height: u64,
+    
+    /// Hash of the checkpointed block
+    #[pyo3(get)]
+    pub block_hash: String,
+    
+    /// Authority's public key
+    #[pyo3(get)]
+    pub authority: String,
+    
+    /// Authority's Ed25519 signature over the height and hash (hex)
+    #[pyo3(get)]
+    pub signature: String,
+}
+
+impl Checkpoint {
+    /// Bytes signed by the authority
+    fn payload(height: u64, block_hash: &str) -> Vec<u8> {
+        domain_separated(CHECKPOINT_DOMAIN, format!("{}:{}", height, block_hash).as_bytes())
+    }
+}
+
+/// A block without its content, exchanged first during sync
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlockHeader {
+    /// Block index
+    #[pyo3(get)]
+    pub index: u64,
+    
+    /// Unix timestamp when block was created
+    #[pyo3(get)]
+    pub timestamp: i64,
+    
+    /// Hash of the block
+    #[pyo3(get)]
+    pub hash: String,
+    
+    /// Hash of the previous block
+    #[pyo3(get)]
+    pub previous_hash: String,
+    
+    /// Author's public key
+    #[pyo3(get)]
+    pub author: String,
+}
 
 /// A single block in the blockchain
 #[pyclass]
@@ -39,6 +231,46 @@ pub struct Block {
     /// Nonce for proof of work (simple)
     #[pyo3(get)]
     pub nonce: u64,
+    
+    /// Hash function this block was hashed with
+    #[pyo3(get)]
+    #[serde(default)]
+    pub hash_algo: HashAlgo,
+    
+    /// Leading zero hex digits the block was mined to
+    #[pyo3(get)]
+    #[serde(default)]
+    pub difficulty: usize,
+    
+    /// Author's Ed25519 signature over the block hash (hex, empty if unsigned)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub signature: String,
+    
+    /// Co-authors' public keys for group-authored content
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    
+    /// Co-author signatures over the block hash, aligned with `authors` (hex, empty if missing)
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<String>,
+    
+    /// Co-author signatures required, 0 meaning all of them
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub threshold: usize,
+    
+    /// Third-party timestamp attestations (not covered by the block hash)
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attestations: Vec<Attestation>,
+    
+    /// Serialization format version
+    #[pyo3(get)]
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
 }
 
 #[pymethods]
@@ -60,56 +292,192 @@ impl Block {
             previous_hash,
             hash: String::new(),
             nonce: 0,
+            hash_algo: HashAlgo::default(),
+            difficulty: 0,
+            signature: String::new(),
+            authors: Vec::new(),
+            signatures: Vec::new(),
+            threshold: 0,
+            attestations: Vec::new(),
+            schema_version: SCHEMA_VERSION,
         };
         block.hash = block.calculate_hash();
         block
     }
     
+    /// Header of this block, without the content
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            index: self.index,
+            timestamp: self.timestamp,
+            hash: self.hash.clone(),
+            previous_hash: self.previous_hash.clone(),
+            author: self.author.clone(),
+        }
+    }
+    
     /// Calculate hash of the block
     pub fn calculate_hash(&self) -> String {
-        let data = format!(
-            "{}{}{}{}{}{}",
-            self.index,
-            self.timestamp,
-            serde_json::to_string(&self.content).unwrap_or_default(),
-            self.author,
-            self.previous_hash,
-            self.nonce
-        );
-        
-        let mut hasher = Sha256::new();
-        hasher.update(data.as_bytes());
-        hex::encode(hasher.finalize())
+        hash_with_nonce(&mut self.fields_without_nonce(), self.nonce, self.hash_algo)
+    }
+    
+    /// Canonical JSON of every hashed field (all but `hash` and `signature`)
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        canonical_bytes_with_nonce(&mut self.fields_without_nonce(), self.nonce)
+    }
+    
+    /// Whether the recomputed hash has `difficulty` leading zeros
+    ///
+    /// Ignores the stored `hash`, so a block whose nonce or contents were
+    /// altered after mining fails even if its hash field was left alone.
+    /// Difficulty 0 accepts any hash without re-hashing.
+    pub fn meets_difficulty(&self, difficulty: usize) -> bool {
+        difficulty == 0
+            || (difficulty <= 64 && self.calculate_hash().starts_with(&"0".repeat(difficulty)))
     }
     
     /// Simple proof of work (find hash starting with prefix)
-    pub fn mine(&mut self, difficulty: usize) -> PyResult<()> {
-        let prefix = "0".repeat(difficulty);
-        while !self.hash.starts_with(&prefix) {
-            self.nonce += 1;
-            self.hash = self.calculate_hash();
-            
-            // Prevent infinite loop
-            if self.nonce > 1_000_000 {
-                break;
+    ///
+    /// Releases the GIL while searching. `progress` is called with the current
+    /// nonce every `progress_interval` nonces; `cancel` stops the search early.
+    #[pyo3(signature = (difficulty, cancel=None, progress=None, progress_interval=MINING_PROGRESS_INTERVAL))]
+    pub fn mine(
+        &mut self,
+        py: Python<'_>,
+        difficulty: usize,
+        cancel: Option<MiningCancelToken>,
+        progress: Option<PyObject>,
+        progress_interval: u64,
+    ) -> PyResult<MiningStatus> {
+        let cancel = cancel.unwrap_or_default().flag();
+        py.allow_threads(|| {
+            self.mine_with(difficulty, cancel, progress_interval, |nonce| match &progress {
+                Some(callback) => Python::with_gil(|py| callback.call1(py, (nonce,)).map(|_| ())),
+                None => Ok(()),
+            })
+        })
+    }
+    
+    /// Sign the block hash with the author's identity
+    pub fn sign(&mut self, identity: &UserIdentity) -> PyResult<()> {
+        if identity.public_key != self.author {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Identity does not match block author"
+            ));
+        }
+        self.signature = hex::encode(identity.sign_payload(&self.author_payload())?);
+        Ok(())
+    }
+    
+    /// Verify the author's signature over the block hash
+    pub fn verify_signature(&self) -> bool {
+        match self.signature_parts() {
+            Some((verifying_key, signature)) => {
+                verifying_key.verify(&self.author_payload(), &signature).is_ok()
+            }
+            None => false,
+        }
+    }
+    
+    /// Make this a group-authored block needing `threshold` of `authors` to sign
+    ///
+    /// A threshold of 0 requires every co-author. The co-authors are hashed,
+    /// so call this before mining; existing co-author signatures are cleared.
+    #[pyo3(signature = (authors, threshold=0))]
+    pub fn set_authors(&mut self, authors: Vec<String>, threshold: usize) -> PyResult<()> {
+        let mut seen = HashSet::with_capacity(authors.len());
+        for author in &authors {
+            verifying_key_from_public_key(author)?;
+            if !seen.insert(author.as_str()) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Duplicate co-author: {}", author)
+                ));
             }
         }
+        if threshold > authors.len() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Threshold {} exceeds {} co-authors",
+                threshold, authors.len()
+            )));
+        }
+        
+        self.signatures = vec![String::new(); authors.len()];
+        self.authors = authors;
+        self.threshold = threshold;
+        self.hash = self.calculate_hash();
+        Ok(())
+    }
+    
+    /// Add a co-author's signature over the block hash
+    pub fn add_signature(&mut self, identity: &UserIdentity) -> PyResult<()> {
+        let position = self.authors.iter()
+            .position(|author| *author == identity.public_key)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Identity is not a co-author of this block"
+            ))?;
+        
+        let signature = hex::encode(identity.sign_payload(&self.coauthor_payload())?);
+        self.signatures.resize(self.authors.len(), String::new());
+        self.signatures[position] = signature;
+        Ok(())
+    }
+    
+    /// Whether enough co-authors have validly signed the block hash
+    ///
+    /// Always true for blocks without co-authors.
+    pub fn verify_signatures(&self) -> bool {
+        if self.authors.is_empty() {
+            return true;
+        }
+        
+        let payload = self.coauthor_payload();
+        let valid = self.authors.iter()
+            .zip(&self.signatures)
+            .filter(|(author, signature)| verify_hex_signature(author, &payload, signature))
+            .count();
+        let required = match self.threshold {
+            0 => self.authors.len(),
+            threshold => threshold,
+        };
+        valid >= required
+    }
+    
+    /// Have a notary co-sign the block hash with the current time
+    pub fn add_attestation(&mut self, notary: &UserIdentity) -> PyResult<()> {
+        let timestamp = Utc::now().timestamp();
+        let signature = notary.sign_payload(&Attestation::payload(&self.hash, timestamp))?;
+        
+        self.attestations.push(Attestation {
+            notary: notary.public_key.clone(),
+            timestamp,
+            signature: hex::encode(signature),
+        });
         Ok(())
     }
     
+    /// Check every attestation was signed by its notary over this block's hash
+    pub fn verify_attestations(&self) -> bool {
+        self.attestations.iter().all(|a| a.verify(&self.hash))
+    }
+    
     /// Convert block to JSON string
     pub fn to_json(&self) -> PyResult<String> {
         serde_json::to_string_pretty(self)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
     
+    /// Block timestamp as an RFC 3339 UTC string, e.g. `2023-11-14T22:13:20Z`
+    pub fn created_at_rfc3339(&self) -> PyResult<String> {
+        to_rfc3339(self.timestamp)
+    }
+    
     /// Get block info as formatted string
     pub fn info(&self) -> String {
         format!(
             "Block #{}\n\
              Timestamp: {}\n\
              Author: {}\n\
-             Content Type: {:?}\n\
+             Content Type: {}\n\
              Hash: {}\n\
              Previous: {}",
             self.index,
@@ -117,13 +485,207 @@ impl Block {
                 .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
                 .unwrap_or_else(|| "Unknown".to_string()),
             self.author,
-            self.content.content_type,
+            self.content.type_name(),
             self.hash,
             self.previous_hash
         )
     }
 }
 
+impl Block {
+    /// Hashed fields except the nonce, keyed in canonical order
+    ///
+    /// Built once per mining run so only the nonce changes between attempts.
+    ///
+    /// Schema versions are format markers, not content, and stay unhashed so
+    /// migrating old data does not change block hashes. The hash algorithm and
+    /// difficulty are only included when not the defaults, for the same reason.
+    fn fields_without_nonce(&self) -> BTreeMap<&'static str, Value> {
+        let mut content = canonical_value(&self.content);
+        if let Some(fields) = content.as_object_mut() {
+            fields.remove("schema_version");
+        }
+        
+        let mut fields = BTreeMap::from([
+            ("author", Value::from(self.author.as_str())),
+            ("content", content),
+            ("index", Value::from(self.index)),
+            ("previous_hash", Value::from(self.previous_hash.as_str())),
+            ("timestamp", Value::from(self.timestamp)),
+        ]);
+        if self.hash_algo != HashAlgo::Sha256 {
+            fields.insert("hash_algo", canonical_value(&self.hash_algo));
+        }
+        if self.difficulty > 0 {
+            fields.insert("difficulty", Value::from(self.difficulty));
+        }
+        if !self.authors.is_empty() {
+            fields.insert("authors", Value::from(self.authors.clone()));
+            fields.insert("threshold", Value::from(self.threshold));
+        }
+        fields
+    }
+    
+    /// Commit to the difficulty about to be mined, rehashing if it changed
+    fn record_difficulty(&mut self, difficulty: usize) {
+        if self.difficulty != difficulty {
+            self.difficulty = difficulty;
+            self.hash = self.calculate_hash();
+        }
+    }
+    
+    /// Bytes the author signs: the block hash under `BLOCK_DOMAIN`
+    fn author_payload(&self) -> Vec<u8> {
+        domain_separated(BLOCK_DOMAIN, self.hash.as_bytes())
+    }
+    
+    /// Bytes each co-author signs: the block hash under `BLOCK_COAUTHOR_DOMAIN`
+    fn coauthor_payload(&self) -> Vec<u8> {
+        domain_separated(BLOCK_COAUTHOR_DOMAIN, self.hash.as_bytes())
+    }
+    
+    /// Decode the author's verifying key and the block signature
+    fn signature_parts(&self) -> Option<(VerifyingKey, Signature)> {
+        let verifying_key = verifying_key_from_public_key(&self.author).ok()?;
+        let signature_bytes: [u8; 64] = hex::decode(&self.signature).ok()?.try_into().ok()?;
+        Some((verifying_key, Signature::from_bytes(&signature_bytes)))
+    }
+    
+    /// Proof of work with cancellation and progress reporting
+    ///
+    /// Difficulty 0 returns at once without touching the nonce, for tests and
+    /// private chains that don't need proof of work.
+    pub fn mine_with<F>(
+        &mut self,
+        difficulty: usize,
+        cancel: Arc<AtomicBool>,
+        progress_interval: u64,
+        mut progress: F,
+    ) -> PyResult<MiningStatus>
+    where
+        F: FnMut(u64) -> PyResult<()>,
+    {
+        check_difficulty(difficulty)?;
+        self.record_difficulty(difficulty);
+        if difficulty == 0 {
+            return Ok(MiningStatus::Mined);
+        }
+        
+        let prefix = "0".repeat(difficulty);
+        while !self.hash.starts_with(&prefix) {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(MiningStatus::Cancelled);
+            }
+            
+            // Prevent infinite loop
+            if self.nonce >= MAX_MINING_NONCE {
+                return Ok(MiningStatus::Exhausted);
+            }
+            
+            self.nonce += 1;
+            self.hash = self.calculate_hash();
+            
+            if progress_interval > 0 && self.nonce.is_multiple_of(progress_interval) {
+                progress(self.nonce)?;
+            }
+        }
+        Ok(MiningStatus::Mined)
+    }
+    
+    /// Proof of work spread across all cores
+    ///
+    /// Each rayon thread walks its own stride of the nonce space; the first hit
+    /// sets a shared flag so the other threads stop.
+    #[cfg(feature = "parallel")]
+    pub fn mine_parallel(&mut self, difficulty: usize, cancel: Arc<AtomicBool>) -> PyResult<MiningStatus> {
+        use rayon::prelude::*;
+        
+        check_difficulty(difficulty)?;
+        self.record_difficulty(difficulty);
+        
+        let prefix = "0".repeat(difficulty);
+        if self.hash.starts_with(&prefix) {
+            return Ok(MiningStatus::Mined);
+        }
+        
+        let fields = self.fields_without_nonce();
+        let hash_algo = self.hash_algo;
+        let start = self.nonce + 1;
+        let threads = rayon::current_num_threads() as u64;
+        let found = AtomicBool::new(false);
+        
+        let hit = (0..threads).into_par_iter()
+            .filter_map(|offset| {
+                let mut fields = fields.clone();
+                let mut nonce = start + offset;
+                while nonce <= MAX_MINING_NONCE {
+                    if found.load(Ordering::Relaxed) || cancel.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    let hash = hash_with_nonce(&mut fields, nonce, hash_algo);
+                    if hash.starts_with(&prefix) {
+                        found.store(true, Ordering::Relaxed);
+                        return Some((nonce, hash));
+                    }
+                    nonce += threads;
+                }
+                None
+            })
+            .min_by_key(|(nonce, _)| *nonce);
+        
+        match hit {
+            Some((nonce, hash)) => {
+                self.nonce = nonce;
+                self.hash = hash;
+                Ok(MiningStatus::Mined)
+            }
+            None if cancel.load(Ordering::Relaxed) => Ok(MiningStatus::Cancelled),
+            None => {
+                self.nonce = MAX_MINING_NONCE;
+                self.hash = self.calculate_hash();
+                Ok(MiningStatus::Exhausted)
+            }
+        }
+    }
+}
+
+/// Summary statistics computed by `Blockchain::stats`
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChainStats {
+    /// Number of blocks, genesis included
+    #[pyo3(get)]
+    pub total_blocks: usize,
+    
+    /// Block count per author
+    #[pyo3(get)]
+    pub blocks_per_author: BTreeMap<String, usize>,
+    
+    /// Block count per content type name
+    #[pyo3(get)]
+    pub content_types: BTreeMap<String, usize>,
+    
+    /// Sum of content sizes in bytes
+    #[pyo3(get)]
+    pub total_content_bytes: u64,
+    
+    /// Mean seconds between consecutive blocks (0 with fewer than two blocks)
+    #[pyo3(get)]
+    pub average_block_interval: f64,
+}
+
+/// An author's blocks together with the blocks linking them to the tip
+///
+/// Block hashes cover the full block, not a Merkle root, so the proof that a
+/// block belongs to the chain is every later block up to the tip. The bundle
+/// therefore holds the run of blocks from the author's first post onward.
+#[derive(Serialize, Deserialize)]
+struct AuthorBundle {
+    author: String,
+    tip_hash: String,
+    blocks: Vec<Block>,
+}
+
 /// The full blockchain
 #[pyclass]
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -135,6 +697,102 @@ pub struct Blockchain {
     /// Mining difficulty
     #[pyo3(get)]
     pub difficulty: usize,
+    
+    /// Genesis block hash recorded at creation (empty for chains saved before it existed)
+    #[pyo3(get)]
+    #[serde(default)]
+    pub genesis_hash: String,
+    
+    /// Hash function for newly added blocks
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub hash_algo: HashAlgo,
+    
+    /// Number of recent blocks scanned by the per-author spam policy (0 = off)
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub author_window: usize,
+    
+    /// Whether `is_valid` also re-derives every content ID from its data
+    ///
+    /// Local policy, never read from a serialized chain.
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub check_content_integrity: bool,
+    
+    /// Seconds after posting during which content may be edited (0 = no limit)
+    ///
+    /// Local policy, never read from a serialized chain.
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub edit_window_secs: i64,
+    
+    /// Largest accepted content data in bytes
+    ///
+    /// Local policy, never read from a serialized chain, so an imported
+    /// chain can't raise the limit its own blocks are checked against.
+    #[pyo3(get, set)]
+    #[serde(skip, default = "default_max_content_size")]
+    pub max_content_size: usize,
+    
+    /// Largest accepted serialized block in bytes (local policy, like `max_content_size`)
+    #[pyo3(get, set)]
+    #[serde(skip, default = "default_max_block_size")]
+    pub max_block_size: usize,
+    
+    /// Serialization format version
+    #[pyo3(get)]
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    
+    /// Whether `add_block` rejects content already stored in the chain
+    ///
+    /// Local policy, never read from a serialized chain.
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub reject_duplicates: bool,
+    
+    /// Lowest difficulty accepted on any block after genesis
+    ///
+    /// A block's own `difficulty` is only a claim, and difficulty 0 needs no
+    /// work at all, so validation rejects blocks below this floor. It is
+    /// local policy and never read from a serialized chain.
+    #[pyo3(get, set)]
+    #[serde(skip, default = "default_min_difficulty")]
+    pub min_difficulty: usize,
+    
+    /// Whether every block after genesis must carry an author signature
+    ///
+    /// Unsigned blocks are accepted otherwise, so whoever relays a chain can
+    /// strip a signature without validation noticing. Local policy, never
+    /// read from a serialized chain.
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub require_signatures: bool,
+    
+    /// Most audit log entries kept in memory (0 = logging off)
+    #[pyo3(get, set)]
+    #[serde(skip)]
+    pub audit_capacity: usize,
+    
+    /// Recent chain mutations, oldest first
+    #[serde(skip)]
+    audit: VecDeque<String>,
+    
+    /// Callables invoked with each newly added block
+    #[serde(skip)]
+    observers: Vec<PyObject>,
+    
+    /// BLAKE3 of content data to the first block holding it
+    ///
+    /// Kept in step by `push_block` and `rebuild_content_index`, so blocks
+    /// must be added through `Blockchain` methods.
+    #[serde(skip)]
+    content_index: HashMap<blake3::Hash, u64>,
+    
+    /// Hashes per second measured on first use of `estimate_mine_time_secs`
+    #[serde(skip)]
+    hash_rate: OnceLock<f64>,
 }
 
 #[pymethods]
@@ -143,7 +801,7 @@ impl Blockchain {
     #[new]
     pub fn new() -> Self {
         let genesis_content = Content::new(
-            crate::content::ContentType::Text,
+            ContentType::Text,
             "Genesis Block - RootlessNet Protocol".to_string(),
             "Genesis".to_string(),
             "The beginning of the decentralized network".to_string(),
@@ -159,10 +817,42 @@ impl Blockchain {
             "0".repeat(64),
         );
         
-        Blockchain {
+        let mut chain = Blockchain {
+            genesis_hash: genesis_block.hash.clone(),
             chain: vec![genesis_block],
             difficulty: 2, // Start with low difficulty
-        }
+            hash_algo: HashAlgo::default(),
+            author_window: 0,
+            check_content_integrity: false,
+            edit_window_secs: 0,
+            max_content_size: DEFAULT_MAX_CONTENT_SIZE,
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            schema_version: SCHEMA_VERSION,
+            audit_capacity: 0,
+            reject_duplicates: false,
+            min_difficulty: DEFAULT_MIN_DIFFICULTY,
+            require_signatures: false,
+            audit: VecDeque::new(),
+            observers: Vec::new(),
+            content_index: HashMap::new(),
+            hash_rate: OnceLock::new(),
+        };
+        chain.rebuild_content_index();
+        chain
+    }
+    
+    /// Create a new blockchain whose blocks, genesis included, use `hash_algo`
+    #[staticmethod]
+    pub fn with_hash_algo(hash_algo: HashAlgo) -> Self {
+        let mut chain = Blockchain::new();
+        chain.hash_algo = hash_algo;
+        
+        let genesis = &mut chain.chain[0];
+        genesis.hash_algo = hash_algo;
+        genesis.hash = genesis.calculate_hash();
+        chain.genesis_hash = genesis.hash.clone();
+        chain.rebuild_content_index();
+        chain
     }
     
     /// Get the latest block
@@ -170,60 +860,293 @@ impl Blockchain {
         self.chain.last().cloned()
     }
     
+    /// Hash of the latest block
+    pub fn tip_hash(&self) -> Option<String> {
+        self.chain.last().map(|b| b.hash.clone())
+    }
+    
+    /// Index of the latest block
+    pub fn height(&self) -> u64 {
+        self.chain.last().map_or(0, |b| b.index)
+    }
+    
     /// Add a new block with content
-    pub fn add_block(&mut self, content: Content, author: String) -> Block {
-        let previous_block = self.get_latest_block().unwrap();
-        let mut new_block = Block::new(
-            previous_block.index + 1,
-            content,
-            author,
-            previous_block.hash.clone(),
-        );
-        
-        // Mine the block (simple PoW)
-        let _ = new_block.mine(self.difficulty);
-        
-        self.chain.push(new_block.clone());
-        new_block
+    ///
+    /// Fails without modifying the chain if no nonce meets the difficulty,
+    /// or if `require_signatures` is set.
+    pub fn add_block(&mut self, content: Content, author: String) -> PyResult<Block> {
+        if self.require_signatures {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Chain requires signed blocks; use add_signed_block"
+            ));
+        }
+        let new_block = self.mine_next_block(content, author)?;
+        self.push_block(new_block.clone());
+        self.record_audit("add_block");
+        Ok(new_block)
+    }
+    
+    /// Add a new block signed by the author's identity
+    pub fn add_signed_block(&mut self, content: Content, identity: &UserIdentity) -> PyResult<Block> {
+        let mut new_block = self.mine_next_block(content, identity.public_key.clone())?;
+        new_block.sign(identity)?;
+        self.push_block(new_block.clone());
+        self.record_audit("add_signed_block");
+        Ok(new_block)
+    }
+    
+    /// Register a callable invoked with each block added to the chain
+    ///
+    /// Callbacks run synchronously on the thread that added the block, with
+    /// the GIL held, after the block is appended. An exception raised by a
+    /// callback is reported as unraisable and does not undo the block or
+    /// stop later callbacks. Callbacks must not add blocks to the same chain.
+    pub fn on_block_added(&mut self, callback: PyObject) {
+        self.observers.push(callback);
     }
     
     /// Verify the entire blockchain
+    ///
+    /// Each block's proof of work is checked against the difficulty it was
+    /// mined at, not the chain's current difficulty. Content IDs are only
+    /// re-derived when `check_content_integrity` is set.
+    ///
+    /// The genesis block is compared with the chain's own `genesis_hash`,
+    /// which a chain received from elsewhere can set to anything. Check
+    /// imported chains with `is_valid_with_genesis` instead.
     pub fn is_valid(&self) -> bool {
-        for i in 1..self.chain.len() {
-            let current = &self.chain[i];
-            let previous = &self.chain[i - 1];
-            
-            // Check hash
-            if current.hash != current.calculate_hash() {
-                return false;
+        self.check_genesis().is_ok() && self.blocks_are_valid()
+    }
+    
+    /// Verify the entire blockchain, requiring the genesis block hash `expected`
+    ///
+    /// Same checks as `is_valid`, but the genesis block is compared with a
+    /// hash the caller already trusts rather than the serialized
+    /// `genesis_hash`.
+    pub fn is_valid_with_genesis(&self, expected: &str) -> bool {
+        self.check_genesis_against(expected).is_ok() && self.blocks_are_valid()
+    }
+    
+    /// Validate blocks `start` through `end` inclusive
+    ///
+    /// Runs the per-block checks of `is_valid`, including the link from
+    /// `start` to the block before it, so a node can check just the suffix it
+    /// appended. Chain-wide duplicate hash detection is skipped.
+    pub fn verify_range(&self, start: u64, end: u64) -> bool {
+        if start > end || end as usize >= self.chain.len() {
+            return false;
+        }
+        if start == 0 && self.check_genesis().is_err() {
+            return false;
+        }
+        let max_timestamp = Utc::now().timestamp() + MAX_FUTURE_DRIFT_SECS;
+        (start.max(1) as usize..=end as usize).all(|i| {
+            let block = &self.chain[i];
+            self.check_block(i, max_timestamp) && self.check_signature(block).is_ok()
+        })
+    }
+    
+    /// Append blocks received from a peer that continue from the local tip
+    ///
+    /// Every block is validated before any is appended, so the chain is left
+    /// unchanged if any of them is rejected.
+    pub fn append_blocks(&mut self, blocks: Vec<Block>) -> PyResult<()> {
+        let max_timestamp = Utc::now().timestamp() + MAX_FUTURE_DRIFT_SECS;
+        
+        let mut seen: HashSet<&str> = self.chain.iter().map(|b| b.hash.as_str()).collect();
+        let mut previous = self.chain.last().unwrap();
+        for (position, block) in blocks.iter().enumerate() {
+            if !seen.insert(block.hash.as_str()) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Duplicate hash on block #{}", block.index)
+                ));
             }
-            
-            // Check previous hash link
-            if current.previous_hash != previous.hash {
-                return false;
+            if block.index != previous.index + 1 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Expected block #{} but got #{}", previous.index + 1, block.index)
+                ));
+            }
+            check_link(previous, block, max_timestamp)
+                .and_then(|_| self.check_pow(block))
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            self.check_block_size(block)
+                .and_then(|_| self.check_signature(block))
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            if !block.verify_signatures() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Too few co-author signatures on block #{}", block.index)
+                ));
             }
+            let earlier = self.chain.iter().chain(&blocks[..position]);
+            self.check_edit_window(block, |id| earlier.clone().find(|b| b.content.id == id))
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            previous = block;
+        }
+        
+        let count = blocks.len();
+        for block in blocks {
+            self.push_block(block);
+        }
+        self.record_audit(&format!("append_blocks({})", count));
+        Ok(())
+    }
+    
+    /// Verify all block signatures in a single batch
+    ///
+    /// Falls back to checking blocks one at a time when the batch fails, so the
+    /// error names the first bad block. Unsigned blocks are skipped unless
+    /// `require_signatures` is set.
+    pub fn verify_signatures_batch(&self) -> PyResult<()> {
+        match self.first_invalid_signature() {
+            Some(index) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Invalid signature on block #{}", index)
+            )),
+            None => Ok(()),
         }
-        true
+    }
+    
+    /// Index of the first block whose content data matches `content`
+    ///
+    /// A single index lookup: every constructor rebuilds the index and
+    /// every appended block updates it, so a miss needs no chain scan.
+    pub fn find_duplicate(&self, content: &Content) -> Option<u64> {
+        self.content_index.get(&content_key(content)).copied()
     }
     
     /// Get block by index
-    pub fn get_block(&self, index: u64) -> Option<Block> {
-        self.chain.get(index as usize).cloned()
+    #[pyo3(signature = (index, include_expired=true))]
+    pub fn get_block(&self, index: u64, include_expired: bool) -> Option<Block> {
+        let now = Utc::now().timestamp();
+        self.chain.get(index as usize)
+            .filter(|b| include_expired || !b.content.is_expired(now))
+            .cloned()
     }
     
     /// Get block by hash
-    pub fn get_block_by_hash(&self, hash: &str) -> Option<Block> {
-        self.chain.iter().find(|b| b.hash == hash).cloned()
+    #[pyo3(signature = (hash, include_expired=true))]
+    pub fn get_block_by_hash(&self, hash: &str, include_expired: bool) -> Option<Block> {
+        let now = Utc::now().timestamp();
+        self.chain.iter()
+            .find(|b| b.hash == hash)
+            .filter(|b| include_expired || !b.content.is_expired(now))
+            .cloned()
     }
     
     /// Get all blocks by author
-    pub fn get_blocks_by_author(&self, author: &str) -> Vec<Block> {
+    #[pyo3(signature = (author, include_expired=true))]
+    pub fn get_blocks_by_author(&self, author: &str, include_expired: bool) -> Vec<Block> {
+        let now = Utc::now().timestamp();
         self.chain.iter()
             .filter(|b| b.author == author)
+            .filter(|b| include_expired || !b.content.is_expired(now))
+            .cloned()
+            .collect()
+    }
+    
+    /// Blocks whose title, description or text body contain `query`
+    ///
+    /// Matching is case-insensitive. Bodies of binary and encrypted content
+    /// are not searched. Results are in chain order.
+    pub fn search(&self, query: &str) -> Vec<Block> {
+        let query = query.to_lowercase();
+        self.chain.iter()
+            .filter(|b| {
+                let content = &b.content;
+                let searchable_body = content.content_type == ContentType::Text && !content.encrypted;
+                content.title.to_lowercase().contains(&query)
+                    || content.description.to_lowercase().contains(&query)
+                    || (searchable_body && content.data.to_lowercase().contains(&query))
+            })
             .cloned()
             .collect()
     }
     
+    /// Export an author's blocks with the proof linking them to the tip
+    pub fn export_author_bundle(&self, author: &str) -> PyResult<String> {
+        let first = self.chain.iter()
+            .position(|b| b.author == author)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("No blocks by author {}", author)
+            ))?;
+        
+        let bundle = AuthorBundle {
+            author: author.to_string(),
+            tip_hash: self.chain.last().unwrap().hash.clone(),
+            blocks: self.chain[first..].to_vec(),
+        };
+        serde_json::to_string(&bundle)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+    
+    /// Check that a bundle from `export_author_bundle` ends at `tip_hash`
+    ///
+    /// Every bundled block must hash correctly and link to the one before
+    /// it, and the author's signed blocks must verify.
+    #[staticmethod]
+    pub fn verify_author_bundle(bundle: &str, tip_hash: &str) -> PyResult<bool> {
+        let bundle: AuthorBundle = serde_json::from_str(bundle)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let max_timestamp = Utc::now().timestamp() + MAX_FUTURE_DRIFT_SECS;
+        
+        let Some(first) = bundle.blocks.first() else {
+            return Ok(false);
+        };
+        if first.author != bundle.author
+            || first.hash != first.calculate_hash()
+            || bundle.tip_hash != tip_hash
+            || bundle.blocks.last().unwrap().hash != tip_hash
+        {
+            return Ok(false);
+        }
+        
+        let linked = bundle.blocks.windows(2)
+            .all(|pair| check_link(&pair[0], &pair[1], max_timestamp).is_ok());
+        let signed = bundle.blocks.iter()
+            .filter(|b| b.author == bundle.author && !b.signature.is_empty())
+            .all(|b| b.verify_signature());
+        Ok(linked && signed)
+    }
+    
+    /// Whether `original_id` may still be superseded at time `now`
+    ///
+    /// The window runs from the block timestamp of the first version of the
+    /// post, so editing an edit does not extend it, and neither does a
+    /// forged `created_at`. Unknown content can't be edited.
+    pub fn can_edit(&self, original_id: &str, now: i64) -> bool {
+        let created_at = first_version_timestamp(original_id, |id| {
+            self.chain.iter().find(|b| b.content.id == id)
+        });
+        created_at.is_some_and(|created_at| {
+            self.edit_window_secs == 0 || now - created_at <= self.edit_window_secs
+        })
+    }
+    
+    /// Follow edits of a content ID forward to its newest version
+    ///
+    /// Only edits made by the author of the original block within the edit
+    /// window are followed.
+    pub fn latest_version(&self, content_id: &str) -> PyResult<Content> {
+        let mut current = self.chain.iter()
+            .find(|b| b.content.id == content_id)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>(
+                format!("Content not found: {}", content_id)
+            ))?;
+        
+        while let Some(edit) = self.chain.iter()
+            .rev()
+            .filter(|b| b.index > current.index && b.author == current.author)
+            .filter(|b| {
+                let earlier = &self.chain[..b.index as usize];
+                self.check_edit_window(b, |id| earlier.iter().find(|e| e.content.id == id)).is_ok()
+            })
+            .find(|b| b.content.supersedes.as_deref() == Some(current.content.id.as_str()))
+        {
+            current = edit;
+        }
+        
+        Ok(current.content.clone())
+    }
+    
     /// Get total number of blocks
     pub fn len(&self) -> usize {
         self.chain.len()
@@ -236,12 +1159,184 @@ impl Blockchain {
     }
     
     /// Import blockchain from JSON
+    ///
+    /// Older schema versions are migrated; newer ones and chains in which
+    /// two blocks share a hash are rejected.
     #[staticmethod]
     pub fn from_json(json_str: &str) -> PyResult<Self> {
-        serde_json::from_str(json_str)
+        let value: Value = serde_json::from_str(json_str)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Self::from_value(value)
+    }
+    
+    /// Export blockchain as compact MessagePack
+    pub fn to_bytes(&self) -> PyResult<Vec<u8>> {
+        rmp_serde::to_vec_named(self)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+    
+    /// Import blockchain from MessagePack, with the same checks as `from_json`
+    #[staticmethod]
+    pub fn from_bytes(data: Vec<u8>) -> PyResult<Self> {
+        let value: Value = rmp_serde::from_slice(&data)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Self::from_value(value)
+    }
+    
+    /// Import a blockchain from a JSON file, reading it block by block
+    ///
+    /// Blocks are validated as they are read and loading stops at the first
+    /// invalid one, whose index is reported in the error. See `from_reader`
+    /// for what this does and doesn't save in memory.
+    #[staticmethod]
+    pub fn from_json_reader(path: &str) -> PyResult<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+        Self::from_reader(BufReader::new(file))
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
     
+    /// Expected seconds to mine one block at `difficulty` on this machine
+    ///
+    /// Expected attempts (16^difficulty) divided by a hash rate measured
+    /// over a short burst the first time this is called, then cached.
+    /// Difficulty 0 needs no search and returns 0.
+    pub fn estimate_mine_time_secs(&self, difficulty: usize) -> f64 {
+        if difficulty == 0 {
+            return 0.0;
+        }
+        let hash_rate = *self.hash_rate.get_or_init(|| {
+            let mut fields = self.chain[0].fields_without_nonce();
+            let start = std::time::Instant::now();
+            for nonce in 0..CALIBRATION_HASHES {
+                hash_with_nonce(&mut fields, nonce, self.hash_algo);
+            }
+            CALIBRATION_HASHES as f64 / start.elapsed().as_secs_f64().max(1e-9)
+        });
+        16f64.powi(difficulty as i32) / hash_rate
+    }
+    
+    /// Change the mining difficulty for blocks added from now on
+    ///
+    /// Blocks already in the chain are not re-checked against it. Values
+    /// above `MAX_DIFFICULTY` would usually run out of nonces before finding
+    /// a hash and are rejected.
+    pub fn set_difficulty(&mut self, difficulty: usize) -> PyResult<()> {
+        if difficulty > MAX_DIFFICULTY {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Difficulty {} is out of range (0-{})",
+                difficulty, MAX_DIFFICULTY
+            )));
+        }
+        self.difficulty = difficulty;
+        Ok(())
+    }
+    
+    /// Difficulty an author must meet for their next block
+    ///
+    /// Anti-spam policy: with `author_window` set, every few blocks the
+    /// author has among the last `author_window` blocks add one step of
+    /// difficulty, so flooding the chain gets exponentially more expensive
+    /// while new or occasional authors mine at the base difficulty. A base
+    /// difficulty below `min_difficulty` is raised to it. The extra steps
+    /// stop at `MAX_DIFFICULTY`, past which mining would run out of nonces.
+    pub fn required_difficulty_for(&self, author: &str) -> usize {
+        let recent = self.chain.iter()
+            .rev()
+            .take(self.author_window)
+            .filter(|b| b.author == author)
+            .count();
+        let base = self.difficulty.max(self.min_difficulty);
+        base.max((base + recent / AUTHOR_BLOCKS_PER_DIFFICULTY_STEP).min(MAX_DIFFICULTY))
+    }
+    
+    /// Headers of every block, for a sync handshake
+    pub fn headers(&self) -> Vec<BlockHeader> {
+        self.chain.iter().map(Block::header).collect()
+    }
+    
+    /// Check headers form a contiguous, linked run with non-decreasing timestamps
+    ///
+    /// Without content the hashes themselves cannot be recomputed, so this
+    /// only screens a peer's offer; the full blocks still need `is_valid`.
+    #[staticmethod]
+    pub fn verify_headers(headers: Vec<BlockHeader>) -> bool {
+        if let Some(first) = headers.first() {
+            if first.index == 0 && first.previous_hash != "0".repeat(64) {
+                return false;
+            }
+        }
+        headers.windows(2).all(|pair| {
+            pair[1].index == pair[0].index + 1
+                && pair[1].previous_hash == pair[0].hash
+                && pair[1].timestamp >= pair[0].timestamp
+        })
+    }
+    
+    /// Recorded chain mutations, oldest first
+    ///
+    /// Each entry reads `<time> <operation> tip=<hash> prev=<digest>`, where
+    /// `prev` is a BLAKE3 digest of the entry before it, so editing or
+    /// dropping an entry from the middle is detectable. Only the newest
+    /// `audit_capacity` entries are kept, and none while it is 0.
+    pub fn audit_log(&self) -> Vec<String> {
+        self.audit.iter().cloned().collect()
+    }
+    
+    /// Sign the hash of the block at `height` as a trusted checkpoint
+    pub fn create_checkpoint(&self, authority: &UserIdentity, height: u64) -> PyResult<Checkpoint> {
+        let block = self.chain.get(height as usize)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("No block at height {}", height)
+            ))?;
+        let signature = authority.sign_payload(&Checkpoint::payload(height, &block.hash))?;
+        
+        Ok(Checkpoint {
+            height,
+            block_hash: block.hash.clone(),
+            authority: authority.public_key.clone(),
+            signature: hex::encode(signature),
+        })
+    }
+    
+    /// Check a checkpoint was signed by `authority_public_key` and matches this chain
+    ///
+    /// Fails if the signature does not cover the checkpoint's height and hash,
+    /// or if the local block at that height has a different hash.
+    pub fn verify_checkpoint(&self, checkpoint: &Checkpoint, authority_public_key: &str) -> bool {
+        if checkpoint.authority != authority_public_key {
+            return false;
+        }
+        let payload = Checkpoint::payload(checkpoint.height, &checkpoint.block_hash);
+        if !verify_hex_signature(authority_public_key, &payload, &checkpoint.signature) {
+            return false;
+        }
+        self.chain.get(checkpoint.height as usize)
+            .is_some_and(|block| block.hash == checkpoint.block_hash)
+    }
+    
+    /// Per-author, per-type and size statistics, computed in one pass
+    pub fn stats(&self) -> ChainStats {
+        let mut stats = ChainStats {
+            total_blocks: self.chain.len(),
+            ..ChainStats::default()
+        };
+        
+        for block in &self.chain {
+            *stats.blocks_per_author.entry(block.author.clone()).or_default() += 1;
+            *stats.content_types.entry(block.content.type_name()).or_default() += 1;
+            stats.total_content_bytes += block.content.size;
+        }
+        
+        if let (Some(first), Some(last)) = (self.chain.first(), self.chain.last()) {
+            if self.chain.len() > 1 {
+                stats.average_block_interval =
+                    (last.timestamp - first.timestamp) as f64 / (self.chain.len() - 1) as f64;
+            }
+        }
+        stats
+    }
+    
     /// Get blockchain info summary
     pub fn info(&self) -> String {
         format!(
@@ -259,62 +1354,2218 @@ impl Blockchain {
     }
 }
 
-impl Default for Blockchain {
-    fn default() -> Self {
-        Self::new()
+impl Blockchain {
+    /// Checks `is_valid` runs on every block after genesis
+    ///
+    /// The author signature is left out so `is_valid` can batch it.
+    fn check_block(&self, i: usize, max_timestamp: i64) -> bool {
+        let block = &self.chain[i];
+        check_link(&self.chain[i - 1], block, max_timestamp).is_ok()
+            && self.check_pow(block).is_ok()
+            && block.verify_signatures()
+            && (!self.check_content_integrity || block.content.verify_integrity())
+            && self.check_edit_window(block, |id| self.chain[..i].iter().find(|b| b.content.id == id)).is_ok()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::content::ContentType;
-
-    #[test]
-    fn test_blockchain_creation() {
-        let chain = Blockchain::new();
-        assert_eq!(chain.chain.len(), 1);
-        assert!(chain.is_valid());
+    
+    /// Check an edit was made within `edit_window_secs` of its first version
+    ///
+    /// Compares block timestamps, which the chain validates, rather than
+    /// the current time, so a peer's late edit fails the same way on every
+    /// node. `find` looks up a content ID among the blocks before `block`.
+    /// Blocks that edit nothing, or content it can't find, pass.
+    fn check_edit_window<'a>(&self, block: &'a Block, find: impl Fn(&str) -> Option<&'a Block>) -> Result<(), String> {
+        let Some(original_id) = &block.content.supersedes else {
+            return Ok(());
+        };
+        if self.edit_window_secs == 0 {
+            return Ok(());
+        }
+        match first_version_timestamp(original_id, find) {
+            Some(created_at) if block.timestamp - created_at > self.edit_window_secs => Err(format!(
+                "Block #{} edits content after the {}s edit window",
+                block.index, self.edit_window_secs
+            )),
+            _ => Ok(()),
+        }
     }
-
-    #[test]
-    fn test_add_block() {
-        let mut chain = Blockchain::new();
+    
+    /// Blocks in index order, genesis first
+    pub fn iter(&self) -> std::slice::Iter<'_, Block> {
+        self.chain.iter()
+    }
+    
+    /// Blocks with an index greater than `index`
+    pub fn blocks_after(&self, index: u64) -> impl Iterator<Item = &Block> {
+        self.chain.iter().skip_while(move |b| b.index <= index)
+    }
+    
+    /// Migrate, decode and sanity-check a serialized chain
+    fn from_value(mut value: Value) -> PyResult<Self> {
+        schema::upgrade(&mut value, "Blockchain")?;
+        if let Some(blocks) = value.get_mut("chain").and_then(Value::as_array_mut) {
+            for block in blocks {
+                schema::upgrade(block, "Block")?;
+                if let Some(content) = block.get_mut("content") {
+                    schema::upgrade(content, "Content")?;
+                }
+            }
+        }
         
-        let content = Content::new(
-            ContentType::Text,
-            "Hello World".to_string(),
-            "Test Post".to_string(),
-            "A test description".to_string(),
-            None,
-            None,
-            None,
-        );
+        let mut chain: Blockchain = serde_json::from_value(value)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         
-        chain.add_block(content, "test_author".to_string());
+        if let Some(index) = chain.first_duplicate_hash() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Duplicate hash on block #{}", index)
+            ));
+        }
+        for block in &chain.chain {
+            chain.check_block_size(block)
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        }
+        chain.rebuild_content_index();
+        Ok(chain)
+    }
+    
+    /// Proof of work at the block's claimed difficulty, which must meet `min_difficulty`
+    fn check_pow(&self, block: &Block) -> Result<(), String> {
+        if block.difficulty < self.min_difficulty {
+            return Err(format!(
+                "Block #{} claims difficulty {}, below the minimum of {}",
+                block.index, block.difficulty, self.min_difficulty
+            ));
+        }
+        if !verify_pow(block, block.difficulty) {
+            return Err(format!("Block #{} does not meet its difficulty", block.index));
+        }
+        Ok(())
+    }
+    
+    /// Check the author's signature if the block has one
+    ///
+    /// Unsigned blocks after genesis fail when `require_signatures` is set.
+    fn check_signature(&self, block: &Block) -> Result<(), String> {
+        if block.signature.is_empty() {
+            if self.require_signatures && block.index > 0 {
+                return Err(format!("Block #{} is unsigned", block.index));
+            }
+            return Ok(());
+        }
+        if !block.verify_signature() {
+            return Err(format!("Invalid signature on block #{}", block.index));
+        }
+        Ok(())
+    }
+    
+    /// Reject content larger than `max_content_size`
+    fn check_content_size(&self, content: &Content) -> Result<(), String> {
+        if content.data.len() > self.max_content_size {
+            return Err(format!(
+                "Content is {} bytes, limit is {}",
+                content.data.len(), self.max_content_size
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Reject blocks over the content or serialized block size limits
+    fn check_block_size(&self, block: &Block) -> Result<(), String> {
+        self.check_content_size(&block.content)
+            .map_err(|e| format!("Block #{}: {}", block.index, e))?;
         
-        assert_eq!(chain.chain.len(), 2);
-        assert!(chain.is_valid());
+        let size = serde_json::to_vec(block).map_err(|e| e.to_string())?.len();
+        if size > self.max_block_size {
+            return Err(format!(
+                "Block #{} is {} bytes, limit is {}",
+                block.index, size, self.max_block_size
+            ));
+        }
+        Ok(())
     }
-
-    #[test]
-    fn test_blockchain_validation() {
-        let mut chain = Blockchain::new();
+    
+    /// Read a JSON chain from `reader`, validating each block as it is parsed
+    ///
+    /// Neither the file nor a JSON tree of the whole chain is held in
+    /// memory, but every block is kept, so memory still grows with the
+    /// chain. Blocks are checked against the default size limits; limits
+    /// written in the file are ignored.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, StreamError> {
+        let failure = RefCell::new(None);
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let result = ChainVisitor { failure: &failure }
+            .deserialize(&mut deserializer)
+            .and_then(|chain| deserializer.end().map(|_| chain));
         
-        for i in 0..5 {
-            let content = Content::new(
-                ContentType::Text,
-                format!("Content {}", i),
-                format!("Title {}", i),
-                format!("Description {}", i),
-                None,
-                None,
-                None,
-            );
-            chain.add_block(content, format!("author_{}", i));
+        let (blocks, mut rest) = match result {
+            Ok(parts) => parts,
+            Err(e) => return Err(failure.into_inner().unwrap_or(StreamError {
+                index: None,
+                message: e.to_string(),
+            })),
+        };
+        
+        rest.insert("chain".to_string(), Value::Array(Vec::new()));
+        let mut value = Value::Object(rest);
+        let unsupported = |_| StreamError {
+            index: None,
+            message: "Blockchain uses an unsupported schema version".to_string(),
+        };
+        schema::upgrade(&mut value, "Blockchain").map_err(unsupported)?;
+        let mut chain: Blockchain = serde_json::from_value(value)
+            .map_err(|e| StreamError { index: None, message: e.to_string() })?;
+        chain.chain = blocks;
+        chain.rebuild_content_index();
+        Ok(chain)
+    }
+    
+    /// Check the genesis block is well formed and matches `genesis_hash`
+    ///
+    /// Fails when `genesis_hash` is empty, as in chains saved before it
+    /// existed; check those with `check_genesis_against`.
+    pub fn check_genesis(&self) -> Result<(), String> {
+        self.check_genesis_against(&self.genesis_hash)
+    }
+    
+    /// Check the genesis block is well formed and has hash `expected`
+    pub fn check_genesis_against(&self, expected: &str) -> Result<(), String> {
+        if expected.is_empty() {
+            return Err("No genesis hash to check the genesis block against".to_string());
+        }
+        let genesis = self.chain.first().ok_or("Chain has no genesis block")?;
+        if genesis.index != 0 || genesis.previous_hash != "0".repeat(64) {
+            return Err("Genesis block is not at the start of the chain".to_string());
+        }
+        if genesis.hash != genesis.calculate_hash() {
+            return Err("Genesis block hash does not match its contents".to_string());
+        }
+        if genesis.hash != expected {
+            return Err("Genesis block differs from the one this chain was created with".to_string());
+        }
+        Ok(())
+    }
+    
+    /// Every check of `is_valid` after the genesis block
+    fn blocks_are_valid(&self) -> bool {
+        let max_timestamp = Utc::now().timestamp() + MAX_FUTURE_DRIFT_SECS;
+        (1..self.chain.len()).all(|i| self.check_block(i, max_timestamp))
+            && self.first_duplicate_hash().is_none()
+            && self.verify_signatures_batch().is_ok()
+    }
+    
+    /// Index of the first block whose hash already appeared earlier in the chain
+    pub fn first_duplicate_hash(&self) -> Option<u64> {
+        let mut seen = HashSet::with_capacity(self.chain.len());
+        self.chain.iter()
+            .find(|b| !seen.insert(b.hash.as_str()))
+            .map(|b| b.index)
+    }
+    
+    /// Index of the first signed block whose signature does not verify
+    ///
+    /// With `require_signatures` set, an unsigned block after genesis also
+    /// counts as invalid.
+    pub fn first_invalid_signature(&self) -> Option<u64> {
+        if self.require_signatures {
+            if let Some(unsigned) = self.chain.iter().skip(1).find(|b| b.signature.is_empty()) {
+                return Some(unsigned.index);
+            }
         }
+        let signed: Vec<&Block> = self.chain.iter()
+            .filter(|b| !b.signature.is_empty())
+            .collect();
         
-        assert_eq!(chain.chain.len(), 6);
-        assert!(chain.is_valid());
+        let mut messages = Vec::with_capacity(signed.len());
+        let mut signatures = Vec::with_capacity(signed.len());
+        let mut verifying_keys = Vec::with_capacity(signed.len());
+        for block in &signed {
+            let Some((verifying_key, signature)) = block.signature_parts() else {
+                return Some(block.index);
+            };
+            messages.push(block.author_payload());
+            signatures.push(signature);
+            verifying_keys.push(verifying_key);
+        }
+        
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        if ed25519_dalek::verify_batch(&messages, &signatures, &verifying_keys).is_ok() {
+            return None;
+        }
+        
+        // The batch only says something is wrong; find which block
+        signed.into_iter()
+            .find(|b| !b.verify_signature())
+            .map(|b| b.index)
+    }
+    
+    /// Append an audit entry for `operation` if logging is on
+    fn record_audit(&mut self, operation: &str) {
+        if self.audit_capacity == 0 {
+            return;
+        }
+        
+        let previous = self.audit.back()
+            .map(|entry| blake3::hash(entry.as_bytes()).to_hex()[..16].to_string())
+            .unwrap_or_else(|| "0".repeat(16));
+        let entry = format!(
+            "{} {} tip={} prev={}",
+            Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            operation,
+            self.chain.last().unwrap().hash,
+            previous
+        );
+        
+        while self.audit.len() >= self.audit_capacity {
+            self.audit.pop_front();
+        }
+        self.audit.push_back(entry);
+    }
+    
+    /// Re-derive the content deduplication index from `chain`
+    fn rebuild_content_index(&mut self) {
+        self.content_index.clear();
+        for block in &self.chain {
+            self.content_index.entry(content_key(&block.content)).or_insert(block.index);
+        }
+    }
+    
+    /// Append a block and notify observers
+    fn push_block(&mut self, block: Block) {
+        self.content_index.entry(content_key(&block.content)).or_insert(block.index);
+        self.chain.push(block.clone());
+        notify_observers(&self.observers, &block);
+    }
+    
+    /// Build and mine the block that would follow the current tip
+    fn mine_next_block(&self, content: Content, author: String) -> PyResult<Block> {
+        self.check_content_size(&content)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        if self.reject_duplicates {
+            if let Some(index) = self.find_duplicate(&content) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Content is already stored in block #{}", index)
+                ));
+            }
+        }
+        if let Some(supersedes) = &content.supersedes {
+            let original = self.chain.iter()
+                .find(|b| &b.content.id == supersedes)
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Superseded content not found: {}", supersedes)
+                ))?;
+            if original.author != author {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "Only the original author can edit content"
+                ));
+            }
+            if !self.can_edit(supersedes, Utc::now().timestamp()) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Edit window of {}s has closed", self.edit_window_secs)
+                ));
+            }
+        }
+        
+        let difficulty = self.required_difficulty_for(&author);
+        let previous_block = self.get_latest_block().unwrap();
+        let mut new_block = Block::new(
+            previous_block.index + 1,
+            content,
+            author,
+            previous_block.hash.clone(),
+        );
+        new_block.hash_algo = self.hash_algo;
+        new_block.hash = new_block.calculate_hash();
+        
+        // Mine the block (simple PoW)
+        #[cfg(feature = "parallel")]
+        let status = new_block.mine_parallel(difficulty, Arc::default())?;
+        #[cfg(not(feature = "parallel"))]
+        let status = new_block.mine_with(difficulty, Arc::default(), 0, |_| Ok(()))?;
+        if status != MiningStatus::Mined {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                format!("Mining failed ({:?}) at difficulty {}", status, difficulty)
+            ));
+        }
+        self.check_block_size(&new_block)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        Ok(new_block)
+    }
+}
+
+/// Deduplication key: BLAKE3 of the content data
+fn content_key(content: &Content) -> blake3::Hash {
+    blake3::hash(content.data.as_bytes())
+}
+
+fn default_max_content_size() -> usize {
+    DEFAULT_MAX_CONTENT_SIZE
+}
+
+fn default_max_block_size() -> usize {
+    DEFAULT_MAX_BLOCK_SIZE
+}
+
+fn default_min_difficulty() -> usize {
+    DEFAULT_MIN_DIFFICULTY
+}
+
+/// Failure in `Blockchain::from_reader`, with the index of the offending block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamError {
+    /// Index of the first invalid block, if a block was at fault
+    pub index: Option<u64>,
+    pub message: String,
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Splits a serialized chain into its blocks and remaining top-level fields
+struct ChainVisitor<'a> {
+    failure: &'a RefCell<Option<StreamError>>,
+}
+
+impl<'de> DeserializeSeed<'de> for ChainVisitor<'_> {
+    type Value = (Vec<Block>, serde_json::Map<String, Value>);
+    
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ChainVisitor<'_> {
+    type Value = (Vec<Block>, serde_json::Map<String, Value>);
+    
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a blockchain object")
+    }
+    
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut blocks = Vec::new();
+        let mut rest = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "chain" {
+                blocks = map.next_value_seed(BlocksVisitor { failure: self.failure })?;
+            } else {
+                rest.insert(key, map.next_value()?);
+            }
+        }
+        Ok((blocks, rest))
+    }
+}
+
+/// Reads the block array one element at a time, validating as it goes
+struct BlocksVisitor<'a> {
+    failure: &'a RefCell<Option<StreamError>>,
+}
+
+impl BlocksVisitor<'_> {
+    /// Record why block `index` was rejected and turn it into a parse error
+    fn reject<E: de::Error>(&self, index: u64, message: String) -> E {
+        let error = E::custom(&message);
+        *self.failure.borrow_mut() = Some(StreamError { index: Some(index), message });
+        error
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for BlocksVisitor<'_> {
+    type Value = Vec<Block>;
+    
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for BlocksVisitor<'_> {
+    type Value = Vec<Block>;
+    
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of blocks")
+    }
+    
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let limits = Blockchain::new();
+        let max_timestamp = Utc::now().timestamp() + MAX_FUTURE_DRIFT_SECS;
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut seen = HashSet::new();
+        
+        while let Some(mut value) = seq.next_element::<Value>()? {
+            let index = blocks.len() as u64;
+            let upgraded = schema::upgrade(&mut value, "Block").and_then(|_| match value.get_mut("content") {
+                Some(content) => schema::upgrade(content, "Content"),
+                None => Ok(()),
+            });
+            if upgraded.is_err() {
+                return Err(self.reject(index, format!("Block #{} uses an unsupported schema version", index)));
+            }
+            let block: Block = serde_json::from_value(value)
+                .map_err(|e| self.reject(index, format!("Block #{}: {}", index, e)))?;
+            
+            if block.index != index {
+                return Err(self.reject(index, format!("Expected block #{} but got #{}", index, block.index)));
+            }
+            if !seen.insert(block.hash.clone()) {
+                return Err(self.reject(index, format!("Duplicate hash on block #{}", index)));
+            }
+            if let Some(previous) = blocks.last() {
+                check_link(previous, &block, max_timestamp)
+                    .and_then(|_| limits.check_pow(&block))
+                    .map_err(|e| self.reject(index, e))?;
+            }
+            limits.check_block_size(&block)
+                .and_then(|_| limits.check_signature(&block))
+                .map_err(|e| self.reject(index, e))?;
+            if !block.verify_signatures() {
+                return Err(self.reject(index, format!("Too few co-author signatures on block #{}", index)));
+            }
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+}
+
+/// Check a hex Ed25519 signature, treating anything unparseable as invalid
+fn verify_hex_signature(public_key: &str, message: &[u8], signature: &str) -> bool {
+    let Ok(verifying_key) = verifying_key_from_public_key(public_key) else {
+        return false;
+    };
+    let Some(signature_bytes) = hex::decode(signature).ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+    else {
+        return false;
+    };
+    verifying_key.verify(message, &Signature::from_bytes(&signature_bytes)).is_ok()
+}
+
+/// Used by serde to omit default thresholds
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Re-hash a block and check it has `difficulty` leading zeros
+///
+/// A cheap spot check for blocks received from peers; the stored `hash` is
+/// not trusted. Use `Blockchain.is_valid` for full validation.
+#[pyfunction]
+pub fn verify_pow(block: &Block, difficulty: usize) -> bool {
+    block.meets_difficulty(difficulty)
+}
+
+/// Check that `current` correctly follows `previous`
+fn check_link(previous: &Block, current: &Block, max_timestamp: i64) -> Result<(), String> {
+    // Check hash
+    if current.hash != current.calculate_hash() {
+        return Err(format!("Block #{} hash does not match its contents", current.index));
+    }
+    
+    // Check previous hash link
+    if current.previous_hash != previous.hash {
+        return Err(format!(
+            "Block #{} does not link to block #{} ({})",
+            current.index, previous.index, previous.hash
+        ));
+    }
+    
+    // Check timestamps never go backwards or too far into the future
+    if current.timestamp < previous.timestamp || current.timestamp > max_timestamp {
+        return Err(format!("Block #{} has an invalid timestamp", current.index));
+    }
+    Ok(())
+}
+
+/// Block timestamp of the first version of `original_id`
+///
+/// Follows `supersedes` back through the blocks `find` returns, stopping
+/// at content it can't find or an ID already visited.
+fn first_version_timestamp<'a>(original_id: &'a str, find: impl Fn(&str) -> Option<&'a Block>) -> Option<i64> {
+    let mut created_at = None;
+    let mut visited = HashSet::new();
+    let mut id = original_id;
+    while visited.insert(id) {
+        let Some(block) = find(id) else {
+            break;
+        };
+        created_at = Some(block.timestamp);
+        match &block.content.supersedes {
+            Some(previous) => id = previous,
+            None => break,
+        }
+    }
+    created_at
+}
+
+/// Canonical block bytes for the given nonce
+fn canonical_bytes_with_nonce(fields: &mut BTreeMap<&'static str, Value>, nonce: u64) -> Vec<u8> {
+    fields.insert("nonce", Value::from(nonce));
+    // Keys come from a BTreeMap and values are already canonical
+    serde_json::to_vec(fields).unwrap_or_default()
+}
+
+/// Hash of the canonical block bytes for the given nonce
+fn hash_with_nonce(fields: &mut BTreeMap<&'static str, Value>, nonce: u64, hash_algo: HashAlgo) -> String {
+    hash_algo.hex_digest(&canonical_bytes_with_nonce(fields, nonce))
+}
+
+/// Reject difficulties no 256-bit hex digest can satisfy
+fn check_difficulty(difficulty: usize) -> PyResult<()> {
+    // A 256-bit hex digest only has 64 characters to match against
+    if difficulty > 64 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Difficulty {} exceeds hash length", difficulty)
+        ));
+    }
+    Ok(())
+}
+
+impl Default for Blockchain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> IntoIterator for &'a Blockchain {
+    type Item = &'a Block;
+    type IntoIter = std::slice::Iter<'a, Block>;
+    
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Call each observer with a newly added block, reporting their errors as unraisable
+fn notify_observers(observers: &[PyObject], block: &Block) {
+    if observers.is_empty() {
+        return;
+    }
+    
+    Python::with_gil(|py| {
+        for callback in observers {
+            if let Err(e) = callback.call1(py, (block.clone(),)) {
+                e.write_unraisable(py, Some(callback.as_ref(py)));
+            }
+        }
+    });
+}
+
+/// A `Blockchain` that several Python threads can add blocks to at once
+///
+/// `Blockchain` itself rejects overlapping calls from other threads with a
+/// borrow error. This wrapper serializes them behind a mutex instead. The
+/// GIL is released while waiting for the lock and while mining, so one
+/// thread mining doesn't stall the others. Observers registered on the
+/// wrapped chain run with the GIL held but after the lock is released, so
+/// they may call back into this wrapper; with several threads adding
+/// blocks, they may see blocks out of chain order.
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct SharedBlockchain {
+    inner: Arc<Mutex<Blockchain>>,
+}
+
+#[pymethods]
+impl SharedBlockchain {
+    /// Wrap a copy of `chain`, or a new chain if none is given
+    ///
+    /// Later changes to `chain` itself are not seen here; use `snapshot`
+    /// to read the wrapped chain back.
+    #[new]
+    #[pyo3(signature = (chain=None))]
+    pub fn new(chain: Option<Blockchain>) -> Self {
+        SharedBlockchain { inner: Arc::new(Mutex::new(chain.unwrap_or_default())) }
+    }
+    
+    /// Mine and append a block; concurrent calls are applied one at a time
+    #[pyo3(name = "add_block")]
+    fn py_add_block(&self, py: Python<'_>, content: Content, author: String) -> PyResult<Block> {
+        py.allow_threads(|| self.add_block(content, author))
+    }
+    
+    /// Mine, sign and append a block
+    #[pyo3(name = "add_signed_block")]
+    fn py_add_signed_block(&self, py: Python<'_>, content: Content, identity: UserIdentity) -> PyResult<Block> {
+        py.allow_threads(|| self.add_signed_block(content, &identity))
+    }
+    
+    /// Number of blocks, genesis included
+    #[pyo3(name = "len")]
+    fn py_len(&self, py: Python<'_>) -> PyResult<usize> {
+        py.allow_threads(|| Ok(self.lock()?.len()))
+    }
+    
+    /// Verify the entire blockchain
+    #[pyo3(name = "is_valid")]
+    fn py_is_valid(&self, py: Python<'_>) -> PyResult<bool> {
+        py.allow_threads(|| Ok(self.lock()?.is_valid()))
+    }
+    
+    /// Verify the entire blockchain against a trusted genesis block hash
+    #[pyo3(name = "is_valid_with_genesis")]
+    fn py_is_valid_with_genesis(&self, py: Python<'_>, expected: &str) -> PyResult<bool> {
+        py.allow_threads(|| Ok(self.lock()?.is_valid_with_genesis(expected)))
+    }
+    
+    /// Copy of the chain as it is now
+    #[pyo3(name = "snapshot")]
+    fn py_snapshot(&self, py: Python<'_>) -> PyResult<Blockchain> {
+        py.allow_threads(|| self.snapshot())
+    }
+}
+
+impl SharedBlockchain {
+    /// Mine and append a block under the lock, then notify observers
+    pub fn add_block(&self, content: Content, author: String) -> PyResult<Block> {
+        self.append_then_notify(|chain| chain.add_block(content, author))
+    }
+    
+    /// Mine, sign and append a block under the lock, then notify observers
+    pub fn add_signed_block(&self, content: Content, identity: &UserIdentity) -> PyResult<Block> {
+        self.append_then_notify(|chain| chain.add_signed_block(content, identity))
+    }
+    
+    /// Run `append` with the chain's observers detached, and call them once
+    /// the lock is released, so an observer calling back in can't deadlock
+    fn append_then_notify(&self, append: impl FnOnce(&mut Blockchain) -> PyResult<Block>) -> PyResult<Block> {
+        let (block, observers) = {
+            let mut chain = self.lock()?;
+            let observers = std::mem::take(&mut chain.observers);
+            let block = append(&mut chain);
+            chain.observers = observers.clone();
+            (block?, observers)
+        };
+        notify_observers(&observers, &block);
+        Ok(block)
+    }
+    
+    /// Copy of the chain as it is now
+    pub fn snapshot(&self) -> PyResult<Blockchain> {
+        Ok(self.lock()?.clone())
+    }
+    
+    fn lock(&self) -> PyResult<MutexGuard<'_, Blockchain>> {
+        self.inner.lock().map_err(|_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+            "Blockchain lock poisoned by a panic in another thread"
+        ))
+    }
+}
+
+/// Read-only random access to a chain file written by `Blockchain.to_bytes`
+///
+/// The file is memory-mapped and only the byte offset of each block is held
+/// in memory; a block is decoded when `get_block` asks for it. There is no
+/// way to add blocks, and the file must not be modified while it is open.
+#[pyclass]
+pub struct MmapBlockchain {
+    map: Mmap,
+    /// Start of each block, followed by the end of the last one
+    offsets: Vec<usize>,
+}
+
+#[pymethods]
+impl MmapBlockchain {
+    /// Map the chain file at `path` and index its blocks
+    #[new]
+    pub fn new(path: &str) -> PyResult<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+        // SAFETY: the mapping is only read, and callers are told not to
+        // modify the file while it is open.
+        let map = unsafe { Mmap::map(&file) }
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+        let offsets = index_block_offsets(&map)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        Ok(MmapBlockchain { map, offsets })
+    }
+    
+    /// Decode the block at `index`, or None past the end of the chain
+    pub fn get_block(&self, index: u64) -> PyResult<Option<Block>> {
+        let Some(range) = self.block_range(index) else {
+            return Ok(None);
+        };
+        let mut value: Value = rmp_serde::from_slice(&self.map[range])
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Block #{}: {}", index, e)
+            ))?;
+        schema::upgrade(&mut value, "Block")?;
+        if let Some(content) = value.get_mut("content") {
+            schema::upgrade(content, "Content")?;
+        }
+        serde_json::from_value(value)
+            .map(Some)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Block #{}: {}", index, e)
+            ))
+    }
+    
+    /// Get total number of blocks
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    
+    /// Whether the file holds no blocks at all
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl MmapBlockchain {
+    /// Bytes of the mapped file holding block `index`
+    fn block_range(&self, index: u64) -> Option<std::ops::Range<usize>> {
+        let index = usize::try_from(index).ok()?;
+        let end = *self.offsets.get(index + 1)?;
+        Some(self.offsets[index]..end)
+    }
+}
+
+/// Find where each block starts in a MessagePack chain without decoding it
+///
+/// Blocks are walked with `IgnoredAny`, so only their framing is read.
+/// Returns one offset per block plus the end of the last block.
+fn index_block_offsets(data: &[u8]) -> Result<Vec<usize>, String> {
+    let mut cursor = Cursor::new(data);
+    let skip = |cursor: &mut Cursor<&[u8]>| {
+        de::IgnoredAny::deserialize(&mut rmp_serde::Deserializer::new(cursor))
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    };
+    
+    let fields = rmp::decode::read_map_len(&mut cursor)
+        .map_err(|e| format!("Not a serialized blockchain: {}", e))?;
+    for _ in 0..fields {
+        let key = String::deserialize(&mut rmp_serde::Deserializer::new(&mut cursor))
+            .map_err(|e| e.to_string())?;
+        if key != "chain" {
+            skip(&mut cursor)?;
+            continue;
+        }
+        
+        let len = rmp::decode::read_array_len(&mut cursor)
+            .map_err(|e| format!("Invalid block list: {}", e))?;
+        // Every block takes at least one byte, so the header can't claim more
+        let remaining = data.len() - cursor.position() as usize;
+        let mut offsets = Vec::with_capacity((len as usize).min(remaining) + 1);
+        for index in 0..len {
+            offsets.push(cursor.position() as usize);
+            skip(&mut cursor).map_err(|e| format!("Block #{}: {}", index, e))?;
+        }
+        offsets.push(cursor.position() as usize);
+        return Ok(offsets);
+    }
+    Err("Serialized blockchain has no chain field".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blockchain_creation() {
+        let chain = Blockchain::new();
+        assert_eq!(chain.chain.len(), 1);
+        assert!(chain.is_valid());
+    }
+
+    #[test]
+    fn test_add_block() {
+        let mut chain = Blockchain::new();
+        
+        let content = Content::new(
+            ContentType::Text,
+            "Hello World".to_string(),
+            "Test Post".to_string(),
+            "A test description".to_string(),
+            None,
+            None,
+            None,
+        );
+        
+        chain.add_block(content, "test_author".to_string()).unwrap();
+        
+        assert_eq!(chain.chain.len(), 2);
+        assert!(chain.is_valid());
+    }
+
+    #[test]
+    fn test_blockchain_validation() {
+        let mut chain = Blockchain::new();
+        
+        for i in 0..5 {
+            let content = Content::new(
+                ContentType::Text,
+                format!("Content {}", i),
+                format!("Title {}", i),
+                format!("Description {}", i),
+                None,
+                None,
+                None,
+            );
+            chain.add_block(content, format!("author_{}", i)).unwrap();
+        }
+        
+        assert_eq!(chain.chain.len(), 6);
+        assert!(chain.is_valid());
+    }
+
+    #[test]
+    fn test_mine_meets_difficulty() {
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        
+        let status = block.mine_with(2, Arc::default(), 0, |_| Ok(())).unwrap();
+        
+        assert_eq!(status, MiningStatus::Mined);
+        assert!(block.hash.starts_with("00"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_mine_parallel_meets_difficulty() {
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        
+        let status = block.mine_parallel(3, Arc::default()).unwrap();
+        
+        assert_eq!(status, MiningStatus::Mined);
+        assert!(block.hash.starts_with("000"));
+        assert_eq!(block.hash, block.calculate_hash());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_mine_parallel_cancelled() {
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        
+        let cancel = Arc::new(AtomicBool::new(true));
+        let status = block.mine_parallel(64, cancel).unwrap();
+        
+        assert_eq!(status, MiningStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_block_hash_covers_canonical_bytes() {
+        let block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        
+        let bytes = block.canonical_bytes();
+        let text = String::from_utf8(bytes.clone()).unwrap();
+        assert!(text.starts_with(r#"{"author":"author","content":{"content_type":"Text","#));
+        assert!(text.ends_with(&format!(r#""nonce":0,"previous_hash":"{}","timestamp":{}}}"#, "0".repeat(64), block.timestamp)));
+        assert_eq!(block.hash, hex::encode(Sha256::digest(&bytes)));
+    }
+
+    #[test]
+    fn test_mine_cancelled_mid_search() {
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        
+        let token = MiningCancelToken::new();
+        let mut calls = 0;
+        let status = block.mine_with(64, token.flag(), 100, |_| {
+            calls += 1;
+            token.cancel();
+            Ok(())
+        }).unwrap();
+        
+        assert_eq!(status, MiningStatus::Cancelled);
+        assert_eq!(calls, 1);
+        assert_eq!(block.nonce, 100);
+    }
+
+    #[test]
+    fn test_mine_reports_exhaustion() {
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        block.nonce = MAX_MINING_NONCE - 10;
+        
+        let status = block.mine_with(64, Arc::default(), 0, |_| Ok(())).unwrap();
+        
+        assert_eq!(status, MiningStatus::Exhausted);
+        assert!(!block.hash.starts_with(&"0".repeat(64)));
+    }
+
+    #[test]
+    fn test_add_block_fails_on_unreachable_difficulty() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 65;
+        
+        let result = chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string());
+        
+        assert!(result.is_err());
+        assert_eq!(chain.chain.len(), 1);
+    }
+
+    #[test]
+    fn test_signed_block_verifies() {
+        let mut chain = Blockchain::new();
+        let identity = UserIdentity::new();
+        
+        let block = chain.add_signed_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), &identity).unwrap();
+        
+        assert!(block.verify_signature());
+        assert!(chain.is_valid());
+    }
+
+    #[test]
+    fn test_block_signatures_are_domain_separated() {
+        let identity = UserIdentity::new();
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(), String::new(), "Hello".to_string(),
+        ), identity.public_key.clone(), "0".repeat(64));
+        block.set_authors(vec![identity.public_key.clone()], 1).unwrap();
+        
+        // A signature over the bare hash, as `UserIdentity.sign` makes, is not a block signature
+        block.signature = hex::encode(identity.sign(block.hash.as_bytes()).unwrap());
+        assert!(!block.verify_signature());
+        
+        // Nor does the author's signature count as a co-author's
+        block.sign(&identity).unwrap();
+        assert!(block.verify_signature());
+        block.signatures = vec![block.signature.clone()];
+        assert!(!block.verify_signatures());
+        block.add_signature(&identity).unwrap();
+        assert!(block.verify_signatures());
+    }
+
+    #[test]
+    fn test_required_signatures_catch_stripping() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let identity = UserIdentity::new();
+        for body in ["one", "two"] {
+            chain.add_signed_block(Content::text("Post".to_string(), String::new(), body.to_string()), &identity).unwrap();
+        }
+        
+        let mut stripped = chain.clone();
+        stripped.chain[2].signature.clear();
+        assert!(stripped.is_valid());
+        
+        stripped.require_signatures = true;
+        assert!(!stripped.is_valid());
+        assert!(!stripped.verify_range(2, 2));
+        assert!(stripped.verify_range(1, 1));
+        assert!(stripped.verify_signatures_batch().is_err());
+        assert!(stripped.add_block(Content::text("Post".to_string(), String::new(), "three".to_string()), "author".to_string()).is_err());
+        
+        let mut local = chain.clone();
+        local.chain.truncate(2);
+        local.rebuild_content_index();
+        local.require_signatures = true;
+        assert!(local.append_blocks(stripped.chain[2..].to_vec()).is_err());
+        assert!(local.append_blocks(chain.chain[2..].to_vec()).is_ok());
+        assert!(local.is_valid());
+    }
+
+    #[test]
+    fn test_batch_verification_detects_corrupted_signature() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let identity = UserIdentity::new();
+        
+        for i in 0..500 {
+            chain.add_signed_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                format!("Content {}", i),
+            ), &identity).unwrap();
+        }
+        assert!(chain.verify_signatures_batch().is_ok());
+        assert!(chain.is_valid());
+        
+        let mut signature = hex::decode(&chain.chain[250].signature).unwrap();
+        signature[0] ^= 0x01;
+        chain.chain[250].signature = hex::encode(signature);
+        
+        assert_eq!(chain.first_invalid_signature(), Some(250));
+        assert!(!chain.is_valid());
+    }
+
+    #[test]
+    fn test_block_dated_before_parent_is_invalid() {
+        let mut chain = Blockchain::new();
+        chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        let genesis_timestamp = chain.chain[0].timestamp;
+        let block = &mut chain.chain[1];
+        block.timestamp = genesis_timestamp - 10;
+        block.hash = block.calculate_hash();
+        
+        assert!(!chain.is_valid());
+    }
+
+    #[test]
+    fn test_block_dated_in_future_is_invalid() {
+        let mut chain = Blockchain::new();
+        chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        let block = &mut chain.chain[1];
+        block.timestamp = Utc::now().timestamp() + 3600;
+        block.hash = block.calculate_hash();
+        
+        assert!(!chain.is_valid());
+    }
+
+    #[test]
+    fn test_expired_content_filtered_when_requested() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        
+        let mut ephemeral = Content::text(
+            "Ephemeral".to_string(),
+            "Description".to_string(),
+            "Gone soon".to_string(),
+        );
+        ephemeral.expires_at = Some(Utc::now().timestamp() - 60);
+        let expired = chain.add_block(ephemeral, "author".to_string()).unwrap();
+        chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        assert!(expired.content.is_expired(Utc::now().timestamp()));
+        assert_eq!(chain.get_blocks_by_author("author", true).len(), 2);
+        assert_eq!(chain.get_blocks_by_author("author", false).len(), 1);
+        assert!(chain.get_block(1, true).is_some());
+        assert!(chain.get_block(1, false).is_none());
+        assert!(chain.get_block_by_hash(&expired.hash, false).is_none());
+        assert!(chain.get_block(2, false).is_some());
+    }
+
+    #[test]
+    fn test_latest_version_follows_edits() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        
+        let original = chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Version 0".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        let mut previous_id = original.content.id.clone();
+        for version in 1..=3 {
+            let mut edit = Content::text(
+                "Post".to_string(),
+                "Description".to_string(),
+                format!("Version {}", version),
+            );
+            edit.supersedes = Some(previous_id);
+            previous_id = chain.add_block(edit, "author".to_string()).unwrap().content.id;
+        }
+        
+        let latest = chain.latest_version(&original.content.id).unwrap();
+        assert_eq!(latest.data, "Version 3");
+        assert_eq!(latest.id, previous_id);
+        assert_eq!(chain.latest_version(&previous_id).unwrap().id, previous_id);
+    }
+
+    #[test]
+    fn test_edit_by_other_author_is_rejected() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        
+        let original = chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Original".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        let mut edit = Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hijacked".to_string(),
+        );
+        edit.supersedes = Some(original.content.id.clone());
+        assert!(chain.add_block(edit.clone(), "mallory".to_string()).is_err());
+        
+        // Edits slipped in by another author are ignored when resolving
+        let mut forged = chain.mine_next_block(edit.clone(), "author".to_string()).unwrap();
+        forged.author = "mallory".to_string();
+        chain.chain.push(forged);
+        assert_eq!(chain.latest_version(&original.content.id).unwrap().data, "Original");
+    }
+
+    #[test]
+    fn test_on_block_added_fires_per_block() {
+        pyo3::prepare_freethreaded_python();
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        
+        let seen = Python::with_gil(|py| {
+            let seen = pyo3::types::PyList::empty(py);
+            let globals = pyo3::types::PyDict::new(py);
+            globals.set_item("seen", seen).unwrap();
+            let callback = py.eval("lambda block: seen.append(block.index)", Some(globals), None).unwrap();
+            let failing = py.eval("lambda block: 1 / 0", None, None).unwrap();
+            chain.on_block_added(failing.into());
+            chain.on_block_added(callback.into());
+            PyObject::from(seen)
+        });
+        
+        for i in 0..2 {
+            chain.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                "Hello".to_string(),
+            ), "author".to_string()).unwrap();
+        }
+        
+        assert_eq!(chain.len(), 3);
+        assert!(chain.is_valid());
+        Python::with_gil(|py| {
+            let indices: Vec<u64> = seen.extract(py).unwrap();
+            assert_eq!(indices, vec![1, 2]);
+        });
+    }
+
+    #[test]
+    fn test_append_valid_tail() {
+        let mut source = Blockchain::new();
+        source.difficulty = 0;
+        for i in 0..3 {
+            source.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                "Hello".to_string(),
+            ), "author".to_string()).unwrap();
+        }
+        
+        let mut local = source.clone();
+        local.chain.truncate(2);
+        
+        local.append_blocks(source.chain[2..].to_vec()).unwrap();
+        assert_eq!(local.len(), 4);
+        assert!(local.is_valid());
+        assert_eq!(local.chain[3].hash, source.chain[3].hash);
+    }
+
+    #[test]
+    fn test_append_broken_tail_leaves_chain_unchanged() {
+        let mut source = Blockchain::new();
+        source.difficulty = 0;
+        for i in 0..3 {
+            source.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                "Hello".to_string(),
+            ), "author".to_string()).unwrap();
+        }
+        
+        let mut local = source.clone();
+        local.chain.truncate(2);
+        
+        let mut broken = source.chain[2..].to_vec();
+        broken[1].previous_hash = "0".repeat(64);
+        broken[1].hash = broken[1].calculate_hash();
+        assert!(local.append_blocks(broken).is_err());
+        assert_eq!(local.len(), 2);
+        
+        // A tail that skips a block is a gap
+        assert!(local.append_blocks(source.chain[3..].to_vec()).is_err());
+        assert_eq!(local.len(), 2);
+    }
+
+    #[test]
+    fn test_tip_hash_and_height_follow_latest_block() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        assert_eq!(chain.height(), 0);
+        assert_eq!(chain.tip_hash(), Some(chain.chain[0].hash.clone()));
+        
+        for i in 0..3 {
+            let block = chain.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                "Hello".to_string(),
+            ), "author".to_string()).unwrap();
+            assert_eq!(chain.tip_hash(), Some(block.hash));
+            assert_eq!(chain.height(), block.index);
+        }
+        assert_eq!(chain.height(), 3);
+    }
+
+    #[test]
+    fn test_duplicate_block_hash_is_rejected() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string()).unwrap();
+        assert_eq!(chain.first_duplicate_hash(), None);
+        
+        let mut duplicate = chain.chain[1].clone();
+        duplicate.index = 2;
+        chain.chain.push(duplicate);
+        
+        assert_eq!(chain.first_duplicate_hash(), Some(2));
+        assert!(!chain.is_valid());
+        assert!(Blockchain::from_json(&chain.to_json().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_from_json_rejects_future_schema_version() {
+        let chain = Blockchain::new();
+        let json = chain.to_json().unwrap();
+        
+        let mut value: Value = serde_json::from_str(&json).unwrap();
+        value["chain"][0]["schema_version"] = Value::from(SCHEMA_VERSION + 1);
+        assert!(Blockchain::from_json(&value.to_string()).is_err());
+        
+        let mut value: Value = serde_json::from_str(&json).unwrap();
+        value["schema_version"] = Value::from(SCHEMA_VERSION + 1);
+        assert!(Blockchain::from_json(&value.to_string()).is_err());
+        
+        // Data written before the field existed loads as version 1
+        let mut value: Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("schema_version");
+        value["chain"][0].as_object_mut().unwrap().remove("schema_version");
+        value["chain"][0]["content"].as_object_mut().unwrap().remove("schema_version");
+        let restored = Blockchain::from_json(&value.to_string()).unwrap();
+        assert_eq!(restored.schema_version, 1);
+        assert!(restored.is_valid());
+        assert_eq!(restored.chain[0].calculate_hash(), chain.chain[0].hash);
+    }
+
+    #[test]
+    fn test_binary_round_trip_is_smaller_than_json() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        
+        let mut file = Content::file(
+            "Archive".to_string(),
+            "Binary payload".to_string(),
+            "AAECAwQFBgcICQoLDA0ODw==".to_string(),
+            "payload.bin".to_string(),
+            None,
+        );
+        file.expires_at = Some(4_000_000_000);
+        chain.add_block(file, "author".to_string()).unwrap();
+        for i in 0..10 {
+            chain.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                "Hello".to_string(),
+            ), "author".to_string()).unwrap();
+        }
+        
+        let bytes = chain.to_bytes().unwrap();
+        let json = chain.to_json().unwrap();
+        // Field names are kept, so the saving comes from whitespace and binary framing
+        assert!(bytes.len() * 3 < json.len() * 2, "{} vs {} bytes", bytes.len(), json.len());
+        
+        let restored = Blockchain::from_bytes(bytes).unwrap();
+        assert!(restored.is_valid());
+        assert_eq!(restored.to_json().unwrap(), json);
+        assert_eq!(restored.chain[1].content.expires_at, Some(4_000_000_000));
+        assert_eq!(restored.chain[1].content.filename.as_deref(), Some("payload.bin"));
+        assert_eq!(restored.chain[1].content.mime_type, None);
+        assert_eq!(restored.chain[1].content.size, 16);
+    }
+
+    #[test]
+    fn test_stats_histogram() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        for text in ["Hello", "World"] {
+            chain.add_block(Content::text(
+                "Post".to_string(),
+                "Description".to_string(),
+                text.to_string(),
+            ), "alice".to_string()).unwrap();
+        }
+        chain.add_block(Content::picture(
+            "Pixel".to_string(),
+            "Description".to_string(),
+            "AAECAw==".to_string(),
+            "pixel.png".to_string(),
+            None,
+        ), "bob".to_string()).unwrap();
+        
+        for (i, block) in chain.chain.iter_mut().enumerate() {
+            block.timestamp = 1_000 + 10 * i as i64;
+        }
+        
+        let stats = chain.stats();
+        assert_eq!(stats.total_blocks, 4);
+        assert_eq!(stats.blocks_per_author["alice"], 2);
+        assert_eq!(stats.blocks_per_author["bob"], 1);
+        assert_eq!(stats.blocks_per_author["SYSTEM"], 1);
+        assert_eq!(stats.content_types["Text"], 3);
+        assert_eq!(stats.content_types["Picture"], 1);
+        assert_eq!(stats.total_content_bytes, chain.chain[0].content.size + 5 + 5 + 4);
+        assert_eq!(stats.average_block_interval, 10.0);
+    }
+
+    #[test]
+    fn test_required_difficulty_rises_for_prolific_author() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.min_difficulty = 0;
+        chain.author_window = 20;
+        
+        let mut required = Vec::new();
+        for i in 0..10 {
+            required.push(chain.required_difficulty_for("spammer"));
+            let block = chain.add_block(Content::text(
+                format!("Spam {}", i),
+                "Description".to_string(),
+                "Buy now".to_string(),
+            ), "spammer".to_string()).unwrap();
+            assert!(block.hash.starts_with(&"0".repeat(required[i])));
+        }
+        
+        assert_eq!(required, vec![0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+        assert_eq!(chain.required_difficulty_for("spammer"), 2);
+        assert_eq!(chain.required_difficulty_for("newcomer"), 0);
+        
+        chain.author_window = 0;
+        assert_eq!(chain.required_difficulty_for("spammer"), 0);
+    }
+
+    #[test]
+    fn test_required_difficulty_stops_at_max() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 1;
+        chain.author_window = 100;
+        let mut block = chain.chain[0].clone();
+        block.author = "spammer".to_string();
+        for _ in 0..50 {
+            chain.chain.push(block.clone());
+        }
+        
+        assert_eq!(MAX_DIFFICULTY, 4);
+        assert_eq!(chain.required_difficulty_for("spammer"), MAX_DIFFICULTY);
+    }
+
+    #[test]
+    fn test_attestations() {
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), "0".repeat(64));
+        let hash = block.hash.clone();
+        
+        let notary = UserIdentity::new();
+        block.add_attestation(&notary).unwrap();
+        assert!(block.verify_attestations());
+        assert_eq!(block.hash, hash);
+        
+        // Claiming another notary's key with our own signature is rejected
+        let forger = UserIdentity::new();
+        block.add_attestation(&forger).unwrap();
+        block.attestations[1].notary = notary.public_key.clone();
+        assert!(!block.verify_attestations());
+        
+        block.attestations.pop();
+        block.attestations[0].timestamp += 1;
+        assert!(!block.verify_attestations());
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.max_content_size = 1024;
+        
+        chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        let oversized = Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "x".repeat(1025),
+        );
+        assert!(chain.add_block(oversized.clone(), "author".to_string()).is_err());
+        assert_eq!(chain.len(), 2);
+        
+        chain.max_block_size = 512;
+        assert!(chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "x".repeat(600),
+        ), "author".to_string()).is_err());
+        
+        // Limits are local policy and never saved with the chain
+        let json = chain.to_json().unwrap();
+        assert!(!json.contains("max_block_size"));
+        assert_eq!(Blockchain::from_json(&json).unwrap().max_block_size, DEFAULT_MAX_BLOCK_SIZE);
+    }
+
+    #[test]
+    fn test_import_cannot_raise_its_own_size_limits() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.add_block(Content::text("Post".to_string(), String::new(), "Hello".to_string()), "author".to_string()).unwrap();
+        
+        // The crafted chain declares limits large enough for its oversized block
+        let mut json: Value = serde_json::from_str(&chain.to_json().unwrap()).unwrap();
+        json["max_content_size"] = Value::from(usize::MAX);
+        json["max_block_size"] = Value::from(usize::MAX);
+        json["chain"][1]["content"]["data"] = Value::from("x".repeat(DEFAULT_MAX_CONTENT_SIZE + 1));
+        let json = json.to_string();
+        
+        assert!(Blockchain::from_json(&json).is_err());
+        let err = Blockchain::from_reader(json.as_bytes()).unwrap_err();
+        assert_eq!(err.index, Some(1));
+    }
+
+    #[test]
+    fn test_from_reader_loads_large_chain() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let padding = "x".repeat(2048);
+        for i in 1..=2000 {
+            chain.add_block(Content::text(
+                "Post".to_string(),
+                "Description".to_string(),
+                format!("post-{}-{}", i, padding),
+            ), "author".to_string()).unwrap();
+        }
+        
+        let json = chain.to_json().unwrap();
+        assert!(json.len() > 4 * 1024 * 1024);
+        
+        let path = std::env::temp_dir().join(format!("rootless-reader-{}.json", std::process::id()));
+        std::fs::write(&path, &json).unwrap();
+        let loaded = Blockchain::from_json_reader(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.len(), 2001);
+        assert_eq!(loaded.tip_hash(), chain.tip_hash());
+        assert_eq!(loaded.difficulty, 0);
+        
+        // A tampered block stops the import at its index
+        let tampered = json.replace("post-1500-", "post-1500!");
+        let err = Blockchain::from_reader(tampered.as_bytes()).unwrap_err();
+        assert_eq!(err.index, Some(1500));
+    }
+
+    #[test]
+    fn test_set_difficulty_applies_to_new_blocks_only() {
+        let mut chain = Blockchain::new();
+        chain.set_difficulty(0).unwrap();
+        let early = chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Before".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        chain.set_difficulty(3).unwrap();
+        let late = chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "After".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        assert!(late.hash.starts_with("000"));
+        assert_eq!(chain.chain[1].hash, early.hash);
+        assert!(chain.is_valid());
+        
+        assert!(chain.set_difficulty(MAX_DIFFICULTY).is_ok());
+        assert!(chain.set_difficulty(MAX_DIFFICULTY + 1).is_err());
+        assert_eq!(chain.difficulty, MAX_DIFFICULTY);
+    }
+
+    #[test]
+    fn test_search_matches_text_fields_case_insensitively() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.add_block(Content::text(
+            "Rust Tips".to_string(),
+            "Borrowing".to_string(),
+            "Lifetimes explained".to_string(),
+        ), "author".to_string()).unwrap();
+        chain.add_block(Content::text(
+            "Cooking".to_string(),
+            "Recipes".to_string(),
+            "Bread needs time, not rust".to_string(),
+        ), "author".to_string()).unwrap();
+        chain.add_block(Content::text(
+            "Gardening".to_string(),
+            "Spring".to_string(),
+            "Plant early".to_string(),
+        ), "author".to_string()).unwrap();
+        chain.add_block(Content::new(
+            ContentType::File,
+            "cnVzdA==".to_string(),
+            "Attachment".to_string(),
+            "Binary".to_string(),
+            Some("rust.bin".to_string()),
+            None,
+            None,
+        ), "author".to_string()).unwrap();
+        
+        let hits: Vec<u64> = chain.search("RUST").iter().map(|b| b.index).collect();
+        assert_eq!(hits, vec![1, 2]);
+        assert_eq!(chain.search("spring").len(), 1);
+        assert!(chain.search("cnVzdA").is_empty());
+    }
+
+    #[test]
+    fn test_author_bundle_verifies_against_tip() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        for (i, author) in ["bob", "alice", "bob", "alice", "carol"].iter().enumerate() {
+            chain.add_block(Content::text(
+                "Post".to_string(),
+                "Description".to_string(),
+                format!("Post {}", i),
+            ), author.to_string()).unwrap();
+        }
+        let tip = chain.tip_hash().unwrap();
+        
+        let bundle = chain.export_author_bundle("alice").unwrap();
+        assert!(Blockchain::verify_author_bundle(&bundle, &tip).unwrap());
+        assert!(!Blockchain::verify_author_bundle(&bundle, &chain.chain[3].hash).unwrap());
+        assert!(chain.export_author_bundle("mallory").is_err());
+        
+        let mut value: Value = serde_json::from_str(&bundle).unwrap();
+        assert_eq!(value["blocks"].as_array().unwrap().len(), 4);
+        value["blocks"][0]["content"]["data"] = Value::from("Edited");
+        assert!(!Blockchain::verify_author_bundle(&value.to_string(), &tip).unwrap());
+    }
+
+    #[test]
+    fn test_blake3_chain_validates() {
+        let mut chain = Blockchain::with_hash_algo(HashAlgo::Blake3);
+        chain.difficulty = 1;
+        let block = chain.add_block(Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string()).unwrap();
+        
+        assert_eq!(chain.chain[0].hash_algo, HashAlgo::Blake3);
+        assert_eq!(block.hash_algo, HashAlgo::Blake3);
+        assert_eq!(block.hash, blake3::hash(&block.canonical_bytes()).to_hex().to_string());
+        assert!(block.hash.starts_with('0'));
+        assert!(chain.is_valid());
+        
+        // The algorithm is part of what the hash commits to
+        let mut relabelled = block.clone();
+        relabelled.hash_algo = HashAlgo::Sha256;
+        assert_ne!(relabelled.calculate_hash(), block.hash);
+        
+        let restored = Blockchain::from_json(&chain.to_json().unwrap()).unwrap();
+        assert_eq!(restored.chain[1].hash_algo, HashAlgo::Blake3);
+        assert!(restored.is_valid());
+    }
+
+    #[test]
+    fn test_audit_log_records_mutations_in_order() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let post = |text: &str| Content::text("Post".to_string(), "Description".to_string(), text.to_string());
+        
+        chain.add_block(post("untracked"), "author".to_string()).unwrap();
+        assert!(chain.audit_log().is_empty());
+        
+        chain.audit_capacity = 3;
+        let first = chain.add_block(post("one"), "author".to_string()).unwrap();
+        let second = chain.add_signed_block(post("two"), &UserIdentity::new()).unwrap();
+        
+        let mut source = chain.clone();
+        let third = source.add_block(post("three"), "author".to_string()).unwrap();
+        chain.append_blocks(vec![third.clone()]).unwrap();
+        
+        let log = chain.audit_log();
+        assert_eq!(log.len(), 3);
+        assert!(log[0].contains(&format!("add_block tip={} prev=0000000000000000", first.hash)));
+        assert!(log[1].contains(&format!("add_signed_block tip={}", second.hash)));
+        assert!(log[2].contains(&format!("append_blocks(1) tip={}", third.hash)));
+        assert!(log[2].ends_with(&blake3::hash(log[1].as_bytes()).to_hex()[..16]));
+        
+        // Bounded: the oldest entry is dropped
+        chain.add_block(post("four"), "author".to_string()).unwrap();
+        assert_eq!(chain.audit_log().len(), 3);
+        assert_eq!(chain.audit_log()[0], log[1]);
+    }
+
+    #[test]
+    fn test_verify_pow_rehashes_block() {
+        let mut chain = Blockchain::new();
+        let mut block = Block::new(1, Content::text(
+            "Post".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), "author".to_string(), chain.chain[0].hash.clone());
+        block.mine_with(2, Arc::default(), 0, |_| Ok(())).unwrap();
+        
+        assert_eq!(block.difficulty, 2);
+        assert!(verify_pow(&block, 2));
+        assert!(block.meets_difficulty(1));
+        
+        let mut tampered = block.clone();
+        tampered.nonce += 1;
+        assert!(!verify_pow(&tampered, 2));
+        assert_eq!(tampered.hash, block.hash);
+        
+        chain.chain.push(block);
+        assert!(chain.is_valid());
+        
+        // A consistent hash that no longer carries the claimed work
+        while chain.chain[1].meets_difficulty(2) {
+            chain.chain[1].nonce += 1;
+        }
+        chain.chain[1].hash = chain.chain[1].calculate_hash();
+        assert!(!chain.is_valid());
+    }
+
+    #[test]
+    fn test_two_of_three_co_authored_block() {
+        let authors: Vec<UserIdentity> = (0..3).map(|_| UserIdentity::new()).collect();
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.min_difficulty = 0;
+        let mut block = Block::new(1, Content::text(
+            "Joint".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), authors[0].public_key.clone(), chain.chain[0].hash.clone());
+        block.set_authors(authors.iter().map(|a| a.public_key.clone()).collect(), 2).unwrap();
+        
+        block.add_signature(&authors[0]).unwrap();
+        assert!(!block.verify_signatures());
+        chain.chain.push(block.clone());
+        assert!(!chain.is_valid());
+        
+        block.add_signature(&authors[2]).unwrap();
+        assert!(block.verify_signatures());
+        chain.chain[1] = block.clone();
+        assert!(chain.is_valid());
+        
+        assert!(block.add_signature(&UserIdentity::new()).is_err());
+        assert!(block.set_authors(vec![authors[0].public_key.clone()], 2).is_err());
+        
+        let restored: Block = serde_json::from_str(&block.to_json().unwrap()).unwrap();
+        assert_eq!(restored.calculate_hash(), block.hash);
+        assert!(restored.verify_signatures());
+    }
+
+    #[test]
+    fn test_from_reader_enforces_co_author_threshold() {
+        let authors: Vec<UserIdentity> = (0..3).map(|_| UserIdentity::new()).collect();
+        let mut chain = Blockchain::new();
+        let mut block = Block::new(1, Content::text(
+            "Joint".to_string(),
+            "Description".to_string(),
+            "Hello".to_string(),
+        ), authors[0].public_key.clone(), chain.chain[0].hash.clone());
+        block.set_authors(authors.iter().map(|a| a.public_key.clone()).collect(), 2).unwrap();
+        block.mine_with(1, Arc::default(), 0, |_| Ok(())).unwrap();
+        block.add_signature(&authors[0]).unwrap();
+        chain.chain.push(block.clone());
+        assert!(!chain.is_valid());
+        
+        let err = Blockchain::from_reader(chain.to_json().unwrap().as_bytes()).unwrap_err();
+        assert_eq!(err.index, Some(1));
+        assert!(err.message.contains("co-author"));
+        
+        block.add_signature(&authors[1]).unwrap();
+        chain.chain[1] = block;
+        assert!(chain.is_valid());
+        assert!(Blockchain::from_reader(chain.to_json().unwrap().as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_is_valid_optionally_checks_content_integrity() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.min_difficulty = 0;
+        let content = Content::text("Post".to_string(), String::new(), "Hello".to_string());
+        let mut corrupted = content.clone();
+        corrupted.data = "Jello".to_string();
+        
+        // The block hash covers the corrupted data, so only the ID check notices
+        let block = Block::new(1, corrupted, "author".to_string(), chain.chain[0].hash.clone());
+        chain.chain.push(block);
+        
+        assert!(chain.is_valid());
+        chain.check_content_integrity = true;
+        assert!(!chain.is_valid());
+        
+        // A saved chain doesn't carry the setting
+        let json = chain.to_json().unwrap();
+        assert!(!json.contains("check_content_integrity"));
+        assert!(!Blockchain::from_json(&json).unwrap().check_content_integrity);
+    }
+
+    #[test]
+    fn test_checkpoint_over_wrong_hash_fails() {
+        let authority = UserIdentity::new();
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        for body in ["one", "two"] {
+            chain.add_block(Content::text("Post".to_string(), String::new(), body.to_string()), "author".to_string()).unwrap();
+        }
+        
+        let checkpoint = chain.create_checkpoint(&authority, 2).unwrap();
+        assert!(chain.verify_checkpoint(&checkpoint, &authority.public_key));
+        assert!(!chain.verify_checkpoint(&checkpoint, &UserIdentity::new().public_key));
+        assert!(chain.create_checkpoint(&authority, 3).is_err());
+        
+        let mut forged = checkpoint.clone();
+        forged.block_hash = chain.chain[1].hash.clone();
+        assert!(!chain.verify_checkpoint(&forged, &authority.public_key));
+        
+        // Correctly signed, but over a block this chain does not have
+        let mut fork = Blockchain::new();
+        fork.difficulty = 0;
+        fork.add_block(Content::text("Post".to_string(), String::new(), "fork".to_string()), "author".to_string()).unwrap();
+        let other = fork.create_checkpoint(&authority, 1).unwrap();
+        assert!(!chain.verify_checkpoint(&other, &authority.public_key));
+    }
+
+    #[test]
+    fn test_tampered_genesis_is_invalid() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.min_difficulty = 0;
+        chain.add_block(Content::text("Post".to_string(), String::new(), "Hello".to_string()), "author".to_string()).unwrap();
+        assert!(chain.is_valid());
+        
+        let mut tampered = chain.clone();
+        tampered.chain[0].content.data = "A different beginning".to_string();
+        assert!(!tampered.is_valid());
+        
+        // Rehashing and relinking still differs from the recorded genesis
+        tampered.chain[0].hash = tampered.chain[0].calculate_hash();
+        tampered.chain[1].previous_hash = tampered.chain[0].hash.clone();
+        tampered.chain[1].hash = tampered.chain[1].calculate_hash();
+        assert!(!tampered.is_valid());
+        
+        // An imported chain can't opt out by clearing the recorded hash
+        tampered.genesis_hash.clear();
+        assert!(!tampered.is_valid());
+        
+        // Recording the forged hash makes it self-consistent, but not the
+        // chain the caller expects
+        tampered.genesis_hash = tampered.chain[0].hash.clone();
+        assert!(tampered.is_valid());
+        assert!(!tampered.is_valid_with_genesis(&chain.genesis_hash));
+        assert!(chain.is_valid_with_genesis(&chain.genesis_hash));
+        
+        // Chains saved before genesis_hash existed are checked against a known hash
+        let mut legacy = chain.clone();
+        legacy.genesis_hash.clear();
+        assert!(!legacy.is_valid());
+        assert!(legacy.is_valid_with_genesis(&chain.genesis_hash));
+        assert!(!legacy.is_valid_with_genesis(""));
+    }
+
+    #[test]
+    fn test_header_linkage_matches_chain_validity() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        for body in ["one", "two", "three"] {
+            chain.add_block(Content::text("Post".to_string(), String::new(), body.to_string()), "author".to_string()).unwrap();
+        }
+        
+        let headers = chain.headers();
+        assert_eq!(headers.len(), 4);
+        assert_eq!(headers[2], chain.chain[2].header());
+        assert!(chain.is_valid());
+        assert!(Blockchain::verify_headers(headers.clone()));
+        
+        let mut broken = chain.clone();
+        broken.chain[2].previous_hash = "f".repeat(64);
+        broken.chain[2].hash = broken.chain[2].calculate_hash();
+        assert!(!broken.is_valid());
+        assert!(!Blockchain::verify_headers(broken.headers()));
+        
+        let mut gap = headers;
+        gap.remove(2);
+        assert!(!Blockchain::verify_headers(gap));
+    }
+
+    #[test]
+    fn test_zero_difficulty_fast_path() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.min_difficulty = 0;
+        
+        let start = std::time::Instant::now();
+        for i in 0..1000 {
+            let content = Content::text(format!("Post {}", i), String::new(), "body".to_string());
+            let block = chain.add_block(content, "alice".to_string()).unwrap();
+            assert_eq!(block.nonce, 0);
+        }
+        // No nonce search: this takes well under a second even in debug builds
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+        
+        assert_eq!(chain.len(), 1001);
+        assert!(chain.is_valid());
+        assert!(chain.chain[500].meets_difficulty(0));
+        
+        // Linkage is still enforced
+        chain.chain[500].previous_hash = chain.chain[10].hash.clone();
+        chain.chain[500].hash = chain.chain[500].calculate_hash();
+        assert!(!chain.is_valid());
+    }
+
+    #[test]
+    fn test_duplicate_content_is_detected() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let upload = |title: &str| Content::new(
+            ContentType::Picture, "aGVsbG8gd29ybGQ=".to_string(), title.to_string(),
+            String::new(), None, Some("image/png".to_string()), None,
+        );
+        
+        chain.add_block(Content::text("a".to_string(), String::new(), "first".to_string()), "alice".to_string()).unwrap();
+        assert_eq!(chain.find_duplicate(&upload("Cat")), None);
+        chain.add_block(upload("Cat"), "alice".to_string()).unwrap();
+        assert_eq!(chain.find_duplicate(&upload("Same cat, new title")), Some(2));
+        
+        // Duplicates are allowed unless rejection is switched on
+        chain.add_block(upload("Again"), "bob".to_string()).unwrap();
+        chain.reject_duplicates = true;
+        assert!(chain.add_block(upload("Third time"), "bob".to_string()).is_err());
+        assert_eq!(chain.len(), 4);
+        
+        // The index is rebuilt on load
+        let restored = Blockchain::from_json(&chain.to_json().unwrap()).unwrap();
+        assert_eq!(restored.content_index, chain.content_index);
+        assert_eq!(restored.find_duplicate(&upload("Cat")), Some(2));
+        assert!(!restored.reject_duplicates);
+        
+        // Blocks received from a peer are indexed as they are appended
+        let dog = || Content::text("Dog".to_string(), String::new(), "woof".to_string());
+        let mut peer = Blockchain::from_json(&chain.to_json().unwrap()).unwrap();
+        peer.add_block(dog(), "carol".to_string()).unwrap();
+        let mut local = restored;
+        assert_eq!(local.find_duplicate(&dog()), None);
+        local.append_blocks(peer.chain[4..].to_vec()).unwrap();
+        assert_eq!(local.find_duplicate(&dog()), Some(4));
+    }
+
+    #[test]
+    fn test_iterates_blocks_in_order() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        for i in 0..5 {
+            chain.add_block(Content::text(format!("Post {}", i), String::new(), "body".to_string()), "alice".to_string()).unwrap();
+        }
+        
+        let mut expected = 0;
+        for block in &chain {
+            assert_eq!(block.index, expected);
+            expected += 1;
+        }
+        assert_eq!(expected, 6);
+        assert_eq!(chain.iter().filter(|b| b.author == "alice").count(), 5);
+        
+        let after: Vec<u64> = chain.blocks_after(2).map(|b| b.index).collect();
+        assert_eq!(after, vec![3, 4, 5]);
+        assert_eq!(chain.blocks_after(5).count(), 0);
+    }
+
+    #[test]
+    fn test_edit_window() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.edit_window_secs = 600;
+        let now = Utc::now().timestamp();
+        
+        let recent = chain.add_block(Content::text("Post".to_string(), String::new(), "Recent".to_string()), "author".to_string()).unwrap();
+        let old = chain.add_block(Content::text("Post".to_string(), String::new(), "Old".to_string()), "author".to_string()).unwrap();
+        chain.chain[2].timestamp = now - 3600;
+        
+        assert!(chain.can_edit(&recent.content.id, now));
+        assert!(!chain.can_edit(&recent.content.id, now + 601));
+        assert!(!chain.can_edit(&old.content.id, now));
+        assert!(!chain.can_edit("missing", now));
+        
+        // The author's own created_at can't hold the window open
+        let mut future_dated = Content::text("Post".to_string(), String::new(), "Future".to_string());
+        future_dated.created_at = now + 1_000_000;
+        let future_dated = chain.add_block(future_dated, "author".to_string()).unwrap();
+        assert!(!chain.can_edit(&future_dated.content.id, now + 601));
+        
+        let mut edit = Content::text("Post".to_string(), String::new(), "Recent, edited".to_string());
+        edit.supersedes = Some(recent.content.id.clone());
+        chain.add_block(edit, "author".to_string()).unwrap();
+        
+        let mut late_edit = Content::text("Post".to_string(), String::new(), "Old, edited".to_string());
+        late_edit.supersedes = Some(old.content.id.clone());
+        assert!(chain.add_block(late_edit.clone(), "author".to_string()).is_err());
+        
+        chain.edit_window_secs = 0;
+        assert!(chain.add_block(late_edit, "author".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_range_checks_only_the_window() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let identity = UserIdentity::new();
+        for i in 0..20 {
+            let content = Content::text(format!("Post {}", i), String::new(), "body".to_string());
+            chain.add_signed_block(content, &identity).unwrap();
+        }
+        let tip = chain.height();
+        
+        assert!(chain.verify_range(tip - 9, tip));
+        assert!(chain.verify_range(0, tip));
+        assert!(!chain.verify_range(tip - 9, tip + 1));
+        assert!(!chain.verify_range(5, 4));
+        
+        chain.chain[15].content.data = "corrupted".to_string();
+        assert!(!chain.verify_range(tip - 9, tip));
+        assert!(chain.verify_range(1, 14));
+        assert!(!chain.is_valid());
+        
+        // A re-hashed block still breaks the link from its successor
+        chain.chain[15].hash = chain.chain[15].calculate_hash();
+        assert!(!chain.verify_range(16, tip));
+    }
+
+    #[test]
+    fn test_mine_time_estimate_grows_with_difficulty() {
+        let chain = Blockchain::new();
+        assert_eq!(chain.estimate_mine_time_secs(0), 0.0);
+        
+        let estimates: Vec<f64> = (1..=6).map(|d| chain.estimate_mine_time_secs(d)).collect();
+        assert!(estimates[0] > 0.0);
+        assert!(estimates.windows(2).all(|w| w[1] > w[0]));
+        // Calibrated once, so each step is exactly 16x
+        assert!((estimates[1] / estimates[0] - 16.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_shared_blockchain_serializes_threads() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 1;
+        let shared = SharedBlockchain::new(Some(chain));
+        
+        let handles: Vec<_> = (0..4).map(|t| {
+            let shared = shared.clone();
+            std::thread::spawn(move || {
+                for i in 0..10 {
+                    let content = Content::text(format!("Thread {} post {}", t, i), String::new(), "body".to_string());
+                    shared.add_block(content, format!("author-{}", t)).unwrap();
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        
+        let chain = shared.snapshot().unwrap();
+        assert_eq!(chain.len(), 41);
+        assert!(chain.is_valid());
+    }
+
+    #[test]
+    fn test_shared_blockchain_observer_can_call_back() {
+        pyo3::prepare_freethreaded_python();
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        let shared = SharedBlockchain::new(None);
+        
+        let seen = Python::with_gil(|py| {
+            let seen = pyo3::types::PyList::empty(py);
+            let globals = pyo3::types::PyDict::new(py);
+            globals.set_item("seen", seen).unwrap();
+            globals.set_item("shared", Py::new(py, shared.clone()).unwrap()).unwrap();
+            let callback = py.eval("lambda block: seen.append(shared.len())", Some(globals), None).unwrap();
+            chain.on_block_added(callback.into());
+            PyObject::from(seen)
+        });
+        *shared.lock().unwrap() = chain;
+        
+        for i in 0..2 {
+            shared.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                "Hello".to_string(),
+            ), "author".to_string()).unwrap();
+        }
+        
+        Python::with_gil(|py| {
+            let lengths: Vec<usize> = seen.extract(py).unwrap();
+            assert_eq!(lengths, vec![2, 3]);
+        });
+    }
+
+    #[test]
+    fn test_mmap_blockchain_random_access() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        for i in 0..50 {
+            chain.add_block(Content::text(
+                format!("Post {}", i),
+                "Description".to_string(),
+                format!("body {}", i),
+            ), format!("author_{}", i % 3)).unwrap();
+        }
+        
+        let path = std::env::temp_dir().join(format!("rootless-mmap-{}.bin", std::process::id()));
+        std::fs::write(&path, chain.to_bytes().unwrap()).unwrap();
+        let mapped = MmapBlockchain::new(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let mapped = mapped.unwrap();
+        
+        assert_eq!(mapped.len(), 51);
+        for index in [37, 0, 50, 12, 37] {
+            let block = mapped.get_block(index).unwrap().unwrap();
+            assert_eq!(block.index, index);
+            assert_eq!(block.hash, chain.chain[index as usize].hash);
+            assert_eq!(block.content.data, chain.chain[index as usize].content.data);
+        }
+        assert!(mapped.get_block(51).unwrap().is_none());
+        
+        // Each block is decoded from its own slice of the file
+        let range = mapped.block_range(12).unwrap();
+        assert!(range.len() < mapped.map.len() / 10);
+    }
+
+    #[test]
+    fn test_mmap_blockchain_rejects_non_chain_file() {
+        let path = std::env::temp_dir().join(format!("rootless-mmap-bad-{}.bin", std::process::id()));
+        std::fs::write(&path, b"not a chain").unwrap();
+        let mapped = MmapBlockchain::new(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(mapped.is_err());
+    }
+
+    #[test]
+    fn test_block_offsets_ignore_inflated_length() {
+        // The header claims u32::MAX blocks but none follow
+        let mut data = Vec::new();
+        rmp::encode::write_map_len(&mut data, 1).unwrap();
+        rmp::encode::write_str(&mut data, "chain").unwrap();
+        rmp::encode::write_array_len(&mut data, u32::MAX).unwrap();
+        let err = index_block_offsets(&data).unwrap_err();
+        assert!(err.starts_with("Block #0"));
+    }
+
+    #[test]
+    fn test_block_claiming_zero_difficulty_is_rejected() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.add_block(Content::text("Post".to_string(), String::new(), "mined".to_string()), "alice".to_string()).unwrap();
+        assert_eq!(chain.chain[1].difficulty, 1);
+        
+        // A forged block that declares difficulty 0 does no work at all
+        let forged = Block::new(2, Content::text(
+            "Forged".to_string(), String::new(), "no work".to_string(),
+        ), "mallory".to_string(), chain.chain[1].hash.clone());
+        assert_eq!(forged.difficulty, 0);
+        
+        let mut receiver = chain.clone();
+        assert!(receiver.append_blocks(vec![forged.clone()]).is_err());
+        
+        chain.chain.push(forged);
+        assert!(!chain.is_valid());
+        assert!(!chain.verify_range(2, 2));
+        assert!(Blockchain::from_reader(chain.to_json().unwrap().as_bytes()).is_err());
+        
+        chain.min_difficulty = 0;
+        assert!(chain.is_valid());
+    }
+
+    #[test]
+    fn test_late_edit_from_peer_is_rejected() {
+        let now = Utc::now().timestamp();
+        let mut peer = Blockchain::new();
+        peer.chain[0].timestamp = now - 7200;
+        peer.chain[0].hash = peer.chain[0].calculate_hash();
+        peer.genesis_hash = peer.chain[0].hash.clone();
+        
+        let mined = |index: u64, content: Content, previous: &Block, timestamp: i64| {
+            let mut block = Block::new(index, content, "author".to_string(), previous.hash.clone());
+            block.timestamp = timestamp;
+            block.mine_with(1, Arc::default(), 0, |_| Ok(())).unwrap();
+            block
+        };
+        let original = mined(1, Content::text("Post".to_string(), String::new(), "Original".to_string()), &peer.chain[0], now - 3600);
+        let mut rewrite = Content::text("Post".to_string(), String::new(), "Rewritten".to_string());
+        rewrite.supersedes = Some(original.content.id.clone());
+        let rewrite = mined(2, rewrite, &original, now);
+        peer.chain.extend([original.clone(), rewrite.clone()]);
+        assert!(peer.is_valid());
+        
+        // The edit came an hour after the post; the window is measured
+        // between the two blocks, not from the time of the check
+        peer.edit_window_secs = 600;
+        assert!(!peer.is_valid());
+        assert_eq!(peer.latest_version(&original.content.id).unwrap().data, "Original");
+        
+        let mut local = Blockchain::new();
+        local.chain = peer.chain[..1].to_vec();
+        local.genesis_hash = peer.genesis_hash.clone();
+        local.edit_window_secs = 600;
+        assert!(local.append_blocks(vec![original.clone(), rewrite.clone()]).is_err());
+        assert_eq!(local.len(), 1);
+        local.edit_window_secs = 0;
+        assert!(local.append_blocks(vec![original, rewrite]).is_ok());
+        
+        // The window is local policy, so a loaded chain can't widen it
+        let json = peer.to_json().unwrap();
+        assert!(!json.contains("edit_window_secs"));
+        let mut loaded = Blockchain::from_json(&json).unwrap();
+        loaded.edit_window_secs = 600;
+        assert!(!loaded.is_valid());
+    }
+
+    #[test]
+    fn test_can_edit_stops_on_supersedes_cycle() {
+        let mut chain = Blockchain::new();
+        chain.difficulty = 0;
+        chain.edit_window_secs = 600;
+        let now = Utc::now().timestamp();
+        
+        let first = chain.add_block(Content::text("Post".to_string(), String::new(), "A".to_string()), "author".to_string()).unwrap();
+        let mut edit = Content::text("Post".to_string(), String::new(), "B".to_string());
+        edit.supersedes = Some(first.content.id.clone());
+        let edit = chain.add_block(edit, "author".to_string()).unwrap();
+        
+        // A -> B -> A
+        chain.chain[1].content.supersedes = Some(edit.content.id.clone());
+        assert!(chain.can_edit(&first.content.id, now));
+        assert!(chain.can_edit(&edit.content.id, now));
     }
 }
@@ -0,0 +1,187 @@
+//! Contacts Module
+//! Address book mapping human-readable names to public keys
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::identity::verifying_key_from_public_key;
+
+/// A name that two contact books map to different public keys
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContactConflict {
+    /// Contact name
+    #[pyo3(get)]
+    pub name: String,
+    
+    /// Public key kept in this book
+    #[pyo3(get)]
+    pub local_key: String,
+    
+    /// Public key offered by the other book
+    #[pyo3(get)]
+    pub remote_key: String,
+}
+
+/// Names mapped to validated public keys
+#[pyclass]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ContactBook {
+    /// Public key for each contact name
+    contacts: BTreeMap<String, String>,
+}
+
+#[pymethods]
+impl ContactBook {
+    /// Create an empty contact book
+    #[new]
+    pub fn new() -> Self {
+        ContactBook::default()
+    }
+    
+    /// Add a contact
+    ///
+    /// Fails if the name is taken or the public key does not parse.
+    pub fn add(&mut self, name: String, public_key: String) -> PyResult<()> {
+        if self.contacts.contains_key(&name) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Contact already exists: {}", name)
+            ));
+        }
+        verifying_key_from_public_key(&public_key)?;
+        
+        self.contacts.insert(name, public_key);
+        Ok(())
+    }
+    
+    /// Remove a contact, returning its public key
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.contacts.remove(name)
+    }
+    
+    /// Public key for a contact name
+    pub fn resolve(&self, name: &str) -> Option<String> {
+        self.contacts.get(name).cloned()
+    }
+    
+    /// Contact name for a public key
+    pub fn reverse(&self, public_key: &str) -> Option<String> {
+        self.contacts.iter()
+            .find(|(_, key)| key.as_str() == public_key)
+            .map(|(name, _)| name.clone())
+    }
+    
+    /// Contact names in sorted order
+    pub fn names(&self) -> Vec<String> {
+        self.contacts.keys().cloned().collect()
+    }
+    
+    /// Add every contact from `other`, reporting names that disagree
+    ///
+    /// Entries already present with the same key are skipped. A name that
+    /// maps to a different key is left untouched here and returned as a
+    /// conflict for the user to resolve.
+    pub fn merge(&mut self, other: &ContactBook) -> Vec<ContactConflict> {
+        let mut conflicts = Vec::new();
+        for (name, remote_key) in &other.contacts {
+            match self.contacts.get(name) {
+                None => {
+                    self.contacts.insert(name.clone(), remote_key.clone());
+                }
+                Some(local_key) if local_key != remote_key => conflicts.push(ContactConflict {
+                    name: name.clone(),
+                    local_key: local_key.clone(),
+                    remote_key: remote_key.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        conflicts
+    }
+    
+    /// Number of contacts
+    pub fn __len__(&self) -> usize {
+        self.contacts.len()
+    }
+    
+    /// Export contacts to JSON
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+    
+    /// Import contacts from JSON, validating every public key
+    #[staticmethod]
+    pub fn from_json(json_str: &str) -> PyResult<Self> {
+        let book: ContactBook = serde_json::from_str(json_str)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        
+        for public_key in book.contacts.values() {
+            verifying_key_from_public_key(public_key)?;
+        }
+        Ok(book)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::UserIdentity;
+
+    #[test]
+    fn test_add_resolve_reverse() {
+        let alice = UserIdentity::new();
+        let mut book = ContactBook::new();
+        book.add("alice".to_string(), alice.public_key.clone()).unwrap();
+        
+        assert_eq!(book.resolve("alice"), Some(alice.public_key.clone()));
+        assert_eq!(book.reverse(&alice.public_key), Some("alice".to_string()));
+        assert_eq!(book.resolve("bob"), None);
+        assert_eq!(book.reverse("unknown"), None);
+        
+        let restored = ContactBook::from_json(&book.to_json().unwrap()).unwrap();
+        assert_eq!(restored.resolve("alice"), Some(alice.public_key));
+    }
+
+    #[test]
+    fn test_duplicate_and_invalid_contacts_rejected() {
+        let mut book = ContactBook::new();
+        book.add("alice".to_string(), UserIdentity::new().public_key).unwrap();
+        
+        assert!(book.add("alice".to_string(), UserIdentity::new().public_key).is_err());
+        assert!(book.add("mallory".to_string(), "not a key".to_string()).is_err());
+        assert_eq!(book.names(), vec!["alice".to_string()]);
+        
+        book.remove("alice");
+        assert_eq!(book.__len__(), 0);
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts_and_combines_the_rest() {
+        let (alice, bob, carol, mallory) = (
+            UserIdentity::new().public_key,
+            UserIdentity::new().public_key,
+            UserIdentity::new().public_key,
+            UserIdentity::new().public_key,
+        );
+        let mut ours = ContactBook::new();
+        ours.add("alice".to_string(), alice.clone()).unwrap();
+        ours.add("bob".to_string(), bob.clone()).unwrap();
+        let mut theirs = ContactBook::new();
+        theirs.add("alice".to_string(), alice.clone()).unwrap();
+        theirs.add("bob".to_string(), mallory.clone()).unwrap();
+        theirs.add("carol".to_string(), carol.clone()).unwrap();
+        
+        let conflicts = ours.merge(&theirs);
+        
+        assert_eq!(conflicts, vec![ContactConflict {
+            name: "bob".to_string(),
+            local_key: bob.clone(),
+            remote_key: mallory,
+        }]);
+        assert_eq!(ours.names(), vec!["alice", "bob", "carol"]);
+        assert_eq!(ours.resolve("bob"), Some(bob));
+        assert_eq!(ours.resolve("carol"), Some(carol));
+    }
+}
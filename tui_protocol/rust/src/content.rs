@@ -2,13 +2,43 @@
 //! Defines content types that can be uploaded and stored
 
 use pyo3::prelude::*;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
-use chrono::Utc;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rand::{CryptoRng, Rng, RngCore};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use x25519_dalek::{PublicKey as X25519PublicKey, SharedSecret, StaticSecret};
+
+use crate::crypto::{
+    canonical_json, cid_from_digest, domain_separated, encrypt_with_rng, decrypt_with_key, parse_cid,
+    MULTI_CONTENT_DOMAIN,
+};
+use crate::identity::{verify_signature, verifying_key_from_public_key, UserIdentity};
+use crate::schema::{self, default_schema_version, SCHEMA_VERSION};
+
+/// BLAKE3 context for deriving per-recipient key-wrapping keys
+const KEY_WRAP_CONTEXT: &str = "RootlessNet content key wrap v1";
+
+/// Fields that `Content::redact` can blank after signing
+pub const REDACTABLE_FIELDS: [&str; 4] = ["description", "filename", "thumbnail", "title"];
+
+/// BLAKE3 context for redactable field commitments
+const REDACTION_CONTEXT: &str = "RootlessNet redactable field v1";
+
+/// Largest decoded thumbnail accepted by `Content::with_thumbnail`
+pub const MAX_THUMBNAIL_BYTES: usize = 32 * 1024;
 
 /// Types of content that can be uploaded
-#[pyclass]
+///
+/// Crosses into Python as a `ContentKind`, since pyo3 cannot expose an enum
+/// with a data-carrying variant as a class.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "ContentTypeRepr")]
 pub enum ContentType {
     /// Text content (posts, comments)
     Text,
@@ -18,33 +48,161 @@ pub enum ContentType {
     Video,
     /// Generic file content
     File,
+    /// Application-defined type such as a poll or reaction, stored as text
+    Custom(String),
 }
 
-#[pymethods]
 impl ContentType {
-    /// Get string representation
-    fn __str__(&self) -> String {
-        match self {
-            ContentType::Text => "Text".to_string(),
-            ContentType::Picture => "Picture".to_string(),
-            ContentType::Video => "Video".to_string(),
-            ContentType::File => "File".to_string(),
-        }
-    }
-    
     /// Create ContentType from string
-    #[staticmethod]
+    ///
+    /// Built-in names match case-insensitively; any other non-empty name
+    /// becomes `Custom` with its case preserved.
     pub fn from_str(s: &str) -> PyResult<Self> {
         match s.to_lowercase().as_str() {
             "text" => Ok(ContentType::Text),
             "picture" | "image" => Ok(ContentType::Picture),
             "video" => Ok(ContentType::Video),
             "file" => Ok(ContentType::File),
-            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Unknown content type: {}", s)
-            ))
+            "" => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Content type name is empty")),
+            _ => Ok(ContentType::Custom(s.to_string())),
         }
     }
+    
+    /// Kind of this type, without a custom name
+    pub fn kind(&self) -> ContentKind {
+        match self {
+            ContentType::Text => ContentKind::Text,
+            ContentType::Picture => ContentKind::Picture,
+            ContentType::Video => ContentKind::Video,
+            ContentType::File => ContentKind::File,
+            ContentType::Custom(_) => ContentKind::Custom,
+        }
+    }
+}
+
+impl fmt::Display for ContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentType::Text => f.write_str("Text"),
+            ContentType::Picture => f.write_str("Picture"),
+            ContentType::Video => f.write_str("Video"),
+            ContentType::File => f.write_str("File"),
+            ContentType::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for ContentType {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.kind().into_py(py)
+    }
+}
+
+/// Accepts a `ContentKind` other than Custom, or a type name
+impl<'source> FromPyObject<'source> for ContentType {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        match ob.extract::<ContentKind>() {
+            Ok(ContentKind::Text) => Ok(ContentType::Text),
+            Ok(ContentKind::Picture) => Ok(ContentType::Picture),
+            Ok(ContentKind::Video) => Ok(ContentType::Video),
+            Ok(ContentKind::File) => Ok(ContentType::File),
+            Ok(ContentKind::Custom) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "ContentType.Custom needs a name; pass the name or use Content.custom"
+            )),
+            Err(_) => ContentType::from_str(ob.extract()?),
+        }
+    }
+}
+
+/// Serialized form of `ContentType`, checked before it is accepted
+#[derive(Deserialize)]
+enum ContentTypeRepr {
+    Text,
+    Picture,
+    Video,
+    File,
+    Custom(String),
+}
+
+/// A custom name that parses as a built-in type would shadow it, so it is
+/// rejected rather than read as `Custom`.
+impl TryFrom<ContentTypeRepr> for ContentType {
+    type Error = String;
+    
+    fn try_from(repr: ContentTypeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            ContentTypeRepr::Text => Ok(ContentType::Text),
+            ContentTypeRepr::Picture => Ok(ContentType::Picture),
+            ContentTypeRepr::Video => Ok(ContentType::Video),
+            ContentTypeRepr::File => Ok(ContentType::File),
+            ContentTypeRepr::Custom(name) => {
+                check_custom_type(&name)?;
+                Ok(ContentType::Custom(name))
+            }
+        }
+    }
+}
+
+/// Kind of content, as exposed to Python under the name `ContentType`
+#[pyclass(name = "ContentType")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContentKind {
+    Text,
+    Picture,
+    Video,
+    File,
+    /// Application-defined type; the name is in `Content.custom_type`
+    Custom,
+}
+
+#[pymethods]
+impl ContentKind {
+    /// Get string representation
+    fn __str__(&self) -> String {
+        format!("{:?}", self)
+    }
+    
+    /// Create ContentType from string, mapping unknown names to Custom
+    #[staticmethod]
+    pub fn from_str(s: &str) -> PyResult<Self> {
+        ContentType::from_str(s).map(|content_type| content_type.kind())
+    }
+}
+
+/// Check an application-defined type name
+///
+/// The name must not be empty or match a built-in name, so a `Custom`
+/// post can't pass itself off as text or a picture.
+fn check_custom_type(name: &str) -> Result<(), String> {
+    match ContentType::from_str(name) {
+        Ok(ContentType::Custom(_)) => Ok(()),
+        Ok(_) => Err(format!("Custom content type {:?} shadows a built-in type", name)),
+        Err(_) => Err("Custom content type name is empty".to_string()),
+    }
+}
+
+/// Deserialize `Content::tags`, sorting and deduplicating them
+///
+/// `add_tag` relies on the order, so tags from a hand-written or foreign
+/// document are normalized on the way in.
+fn deserialize_tags<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let mut tags = Vec::<String>::deserialize(deserializer)?;
+    tags.sort();
+    tags.dedup();
+    Ok(tags)
+}
+
+/// Content key wrapped for a single recipient
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecipientKey {
+    /// Recipient's public key
+    pub recipient: String,
+    
+    /// Ephemeral X25519 public key used for the wrap (hex)
+    pub ephemeral_public_key: String,
+    
+    /// Content key encrypted for the recipient (hex, nonce prepended)
+    pub wrapped_key: String,
 }
 
 /// Content metadata and data
@@ -79,7 +237,7 @@ pub struct Content {
     #[pyo3(get)]
     pub mime_type: Option<String>,
     
-    /// Size in bytes
+    /// Size in bytes (decoded size for base64 media and files)
     #[pyo3(get)]
     pub size: u64,
     
@@ -87,14 +245,60 @@ pub struct Content {
     #[pyo3(get)]
     pub created_at: i64,
     
-    /// Tags for the content
+    /// Tags for the content, sorted and unique
     #[pyo3(get)]
+    #[serde(deserialize_with = "deserialize_tags")]
     pub tags: Vec<String>,
+    
+    /// Whether `data` is encrypted for a set of recipients
+    #[pyo3(get)]
+    #[serde(default)]
+    pub encrypted: bool,
+    
+    /// Content key wrapped for each recipient (empty unless encrypted)
+    #[serde(default)]
+    pub recipient_keys: Vec<RecipientKey>,
+    
+    /// Expiry timestamp for ephemeral content (None = never expires)
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    
+    /// ID of the content this version edits (None for an original post)
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
+    
+    /// Small base64 preview image for pictures and videos
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    
+    /// CIDs of media stored outside the block, in attachment order
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+    
+    /// Random salt per redactable field, set by `make_redactable`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub redaction_salts: BTreeMap<String, String>,
+    
+    /// Commitments standing in for fields removed by `redact`
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub redacted: BTreeMap<String, String>,
+    
+    /// Serialization format version
+    #[pyo3(get)]
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
 }
 
 #[pymethods]
 impl Content {
     /// Create new content
+    ///
+    /// Empty `data` is allowed and gives content with `size` 0.
     #[new]
     #[pyo3(signature = (content_type, data, title, description, filename=None, mime_type=None, tags=None))]
     pub fn new(
@@ -106,9 +310,13 @@ impl Content {
         mime_type: Option<String>,
         tags: Option<Vec<String>>,
     ) -> Self {
-        let size = data.len() as u64;
+        let size = decoded_len(&content_type, &data).unwrap_or(data.len()) as u64;
         let created_at = Utc::now().timestamp();
         
+        let mut tags = tags.unwrap_or_default();
+        tags.sort();
+        tags.dedup();
+        
         // Generate content ID from hash
         let id = Self::generate_id(&data, created_at);
         
@@ -122,7 +330,16 @@ impl Content {
             mime_type,
             size,
             created_at,
-            tags: tags.unwrap_or_default(),
+            tags,
+            encrypted: false,
+            recipient_keys: Vec::new(),
+            expires_at: None,
+            supersedes: None,
+            thumbnail: None,
+            attachments: Vec::new(),
+            redaction_salts: BTreeMap::new(),
+            redacted: BTreeMap::new(),
+            schema_version: SCHEMA_VERSION,
         }
     }
     
@@ -140,6 +357,38 @@ impl Content {
         )
     }
     
+    /// Create content of an application-defined type, such as a poll
+    ///
+    /// `data` is stored as text. `type_name` must not be empty or name a
+    /// built-in type.
+    #[staticmethod]
+    #[pyo3(signature = (type_name, data, title, description, tags=None))]
+    pub fn custom(
+        type_name: String,
+        data: String,
+        title: String,
+        description: String,
+        tags: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        check_custom_type(&type_name).map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        Ok(Content::new(ContentType::Custom(type_name), data, title, description, None, None, tags))
+    }
+    
+    /// Name of an application-defined type, set when `content_type` is Custom
+    #[getter]
+    pub fn custom_type(&self) -> Option<String> {
+        match &self.content_type {
+            ContentType::Custom(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+    
+    /// Custom type name for Custom content, otherwise the built-in name
+    #[getter]
+    pub fn type_name(&self) -> String {
+        self.content_type.to_string()
+    }
+    
     /// Create picture content from base64 data
     #[staticmethod]
     #[pyo3(signature = (title, description, base64_data, filename, mime_type=None))]
@@ -150,7 +399,8 @@ impl Content {
         filename: String,
         mime_type: Option<String>,
     ) -> Self {
-        let mime = mime_type.unwrap_or_else(|| {
+        let base64_data = normalize_base64(&base64_data);
+        let mime = mime_type.or_else(|| detect_mime(&base64_data)).unwrap_or_else(|| {
             if filename.ends_with(".png") {
                 "image/png".to_string()
             } else if filename.ends_with(".jpg") || filename.ends_with(".jpeg") {
@@ -183,7 +433,8 @@ impl Content {
         filename: String,
         mime_type: Option<String>,
     ) -> Self {
-        let mime = mime_type.unwrap_or_else(|| {
+        let base64_data = normalize_base64(&base64_data);
+        let mime = mime_type.or_else(|| detect_mime(&base64_data)).unwrap_or_else(|| {
             if filename.ends_with(".mp4") {
                 "video/mp4".to_string()
             } else if filename.ends_with(".webm") {
@@ -216,6 +467,8 @@ impl Content {
         filename: String,
         mime_type: Option<String>,
     ) -> Self {
+        let base64_data = normalize_base64(&base64_data);
+        let mime_type = mime_type.or_else(|| detect_mime(&base64_data));
         Content::new(
             ContentType::File,
             base64_data,
@@ -229,35 +482,214 @@ impl Content {
     
     /// Generate content ID from data hash
     #[staticmethod]
-    fn generate_id(data: &str, timestamp: i64) -> String {
+    pub(crate) fn generate_id(data: &str, timestamp: i64) -> String {
         let mut hasher = Sha256::new();
         hasher.update(data.as_bytes());
         hasher.update(timestamp.to_string().as_bytes());
         hex::encode(hasher.finalize())
     }
     
-    /// Add a tag
+    /// Check the stored ID still matches `data` and `created_at`
+    ///
+    /// Catches corruption in transit independently of any author signature;
+    /// it is not a defence against deliberate tampering, which can recompute
+    /// the ID.
+    pub fn verify_integrity(&self) -> bool {
+        self.id == Self::generate_id(&self.data, self.created_at)
+    }
+    
+    /// Equal when the IDs derived from both contents' data match
+    ///
+    /// The ID is recomputed rather than read from `id`, so tampered data
+    /// never compares equal to the original.
+    fn __eq__(&self, other: &Self) -> bool {
+        Self::generate_id(&self.data, self.created_at) == Self::generate_id(&other.data, other.created_at)
+    }
+    
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        Self::generate_id(&self.data, self.created_at).hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    /// Copy of this picture or video with a base64 preview attached
+    pub fn with_thumbnail(&self, thumbnail: String) -> PyResult<Content> {
+        if !matches!(self.content_type, ContentType::Picture | ContentType::Video) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Thumbnails are only supported for pictures and videos"
+            ));
+        }
+        
+        let size = BASE64.decode(&thumbnail)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+            .len();
+        if size > MAX_THUMBNAIL_BYTES {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Thumbnail is {} bytes, limit is {}", size, MAX_THUMBNAIL_BYTES)
+            ));
+        }
+        
+        let mut content = self.clone();
+        content.thumbnail = Some(thumbnail);
+        Ok(content)
+    }
+    
+    /// Add a tag, keeping tags sorted and unique
     pub fn add_tag(&mut self, tag: String) {
-        if !self.tags.contains(&tag) {
-            self.tags.push(tag);
+        if let Err(position) = self.tags.binary_search(&tag) {
+            self.tags.insert(position, tag);
         }
     }
     
+    /// Reference externally stored media by its CID
+    ///
+    /// The CID is covered by the signature and block hash, so it cannot be
+    /// swapped afterwards.
+    pub fn add_attachment(&mut self, cid: String) -> PyResult<()> {
+        parse_cid(&cid)?;
+        if self.attachments.contains(&cid) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Attachment already added: {}", cid)
+            ));
+        }
+        self.attachments.push(cid);
+        Ok(())
+    }
+    
     /// Remove a tag
     pub fn remove_tag(&mut self, tag: &str) {
         self.tags.retain(|t| t != tag);
     }
     
+    /// Sign `REDACTABLE_FIELDS` as salted commitments instead of values
+    ///
+    /// Must be called before the content is signed. Afterwards any of those
+    /// fields can be removed with `redact` without breaking the signature.
+    pub fn make_redactable(&mut self) {
+        let mut rng = rand::thread_rng();
+        for field in REDACTABLE_FIELDS {
+            self.redaction_salts.entry(field.to_string())
+                .or_insert_with(|| hex::encode(rng.gen::<[u8; 32]>()));
+        }
+    }
+    
+    /// Blank a redactable field, keeping only its commitment
+    ///
+    /// The salt is discarded with the value, so the original can't be
+    /// recovered by guessing. Signatures over `signing_bytes` still verify,
+    /// but the block hash of a chain holding the content does change.
+    pub fn redact(&mut self, field: &str) -> PyResult<()> {
+        let commitment = self.field_commitment(field)
+            .filter(|_| !self.redacted.contains_key(field))
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Field '{}' is not redactable", field)
+            ))?;
+        
+        match field {
+            "description" => self.description.clear(),
+            "filename" => self.filename = None,
+            "thumbnail" => self.thumbnail = None,
+            "title" => self.title.clear(),
+            _ => unreachable!("field_commitment only covers REDACTABLE_FIELDS"),
+        }
+        self.redaction_salts.remove(field);
+        self.redacted.insert(field.to_string(), commitment);
+        Ok(())
+    }
+    
+    /// Encrypt the data so only the given public keys can read it
+    ///
+    /// The data is encrypted once under a random content key, which is then
+    /// wrapped for each recipient via X25519 with an ephemeral key.
+    pub fn encrypt_for(&self, recipients: Vec<String>) -> PyResult<Content> {
+        self.encrypt_for_with_rng(recipients, &mut rand::thread_rng())
+    }
+    
+    /// Decrypt the data using a recipient's identity
+    pub fn decrypt_with(&self, identity: &UserIdentity) -> PyResult<String> {
+        if !self.encrypted {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Content is not encrypted"));
+        }
+        
+        let recipient_key = self.recipient_keys.iter()
+            .find(|k| k.recipient == identity.public_key)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Identity is not a recipient of this content"
+            ))?;
+        
+        let signing_key = identity.signing_key()?;
+        let secret = StaticSecret::from(signing_key.to_scalar_bytes());
+        let own_public = X25519PublicKey::from(signing_key.verifying_key().to_montgomery().to_bytes());
+        
+        let ephemeral_bytes: [u8; 32] = hex::decode(&recipient_key.ephemeral_public_key)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+            .try_into()
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid ephemeral key length"))?;
+        let ephemeral_public = X25519PublicKey::from(ephemeral_bytes);
+        
+        let shared = secret.diffie_hellman(&ephemeral_public);
+        let wrap_key = derive_wrap_key(&shared, &ephemeral_public, &own_public)?;
+        let wrapped_key = hex::decode(&recipient_key.wrapped_key)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let content_key: [u8; 32] = decrypt_with_key(&wrap_key, &wrapped_key)?
+            .try_into()
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid content key length"))?;
+        
+        let ciphertext = hex::decode(&self.data)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let plaintext = decrypt_with_key(&content_key, &ciphertext)?;
+        
+        String::from_utf8(plaintext)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+    
+    /// Real byte count of the data
+    ///
+    /// Text is measured as UTF-8; other types are base64-decoded first.
+    /// Encrypted data is measured as stored.
+    pub fn decoded_size(&self) -> PyResult<u64> {
+        if self.encrypted {
+            return Ok(self.data.len() as u64);
+        }
+        decoded_len(&self.content_type, &self.data)
+            .map(|len| len as u64)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+    
+    /// Creation time as an RFC 3339 UTC string, e.g. `2023-11-14T22:13:20Z`
+    pub fn created_at_rfc3339(&self) -> PyResult<String> {
+        to_rfc3339(self.created_at)
+    }
+    
+    /// Whether the content has expired at the given timestamp
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+    
+    /// Public keys the content is encrypted for
+    pub fn recipients(&self) -> Vec<String> {
+        self.recipient_keys.iter().map(|k| k.recipient.clone()).collect()
+    }
+    
+    /// Canonical JSON of every field (sorted keys, no whitespace)
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        canonical_json(self)
+    }
+    
     /// Convert to JSON
     pub fn to_json(&self) -> PyResult<String> {
         serde_json::to_string_pretty(self)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
     
-    /// Create from JSON
+    /// Create from JSON, migrating older schema versions
     #[staticmethod]
     pub fn from_json(json_str: &str) -> PyResult<Self> {
-        serde_json::from_str(json_str)
+        let mut value: serde_json::Value = serde_json::from_str(json_str)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        schema::upgrade(&mut value, "Content")?;
+        
+        serde_json::from_value(value)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
     
@@ -265,18 +697,18 @@ impl Content {
     pub fn info(&self) -> String {
         format!(
             "Content ID: {}\n\
-             Type: {:?}\n\
+             Type: {}\n\
              Title: {}\n\
              Description: {}\n\
              Size: {} bytes\n\
              Created: {}\n\
              Tags: {}",
             self.id,
-            self.content_type,
+            self.type_name(),
             self.title,
             self.description,
             self.size,
-            chrono::DateTime::from_timestamp(self.created_at, 0)
+            DateTime::from_timestamp(self.created_at, 0)
                 .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
                 .unwrap_or_else(|| "Unknown".to_string()),
             if self.tags.is_empty() { "None".to_string() } else { self.tags.join(", ") }
@@ -285,16 +717,393 @@ impl Content {
     
     /// Get short summary
     pub fn summary(&self) -> String {
-        let preview = if self.data.len() > 50 {
-            format!("{}...", &self.data[..50])
+        let preview = if self.data.chars().count() > 50 {
+            format!("{}...", self.data.chars().take(50).collect::<String>())
         } else {
             self.data.clone()
         };
         
-        format!("[{:?}] {} - {}", self.content_type, self.title, preview)
+        format!("[{}] {} - {}", self.type_name(), self.title, preview)
     }
 }
 
+impl Content {
+    /// `encrypt_for` with the content key, ephemeral keys and nonces drawn from `rng`
+    ///
+    /// Meant for reproducible test vectors. Production code must use
+    /// `encrypt_for`, which draws from the OS-seeded thread RNG.
+    pub fn encrypt_for_with_rng<R: RngCore + CryptoRng>(&self, recipients: Vec<String>, rng: &mut R) -> PyResult<Content> {
+        if self.encrypted {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Content is already encrypted"));
+        }
+        if recipients.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("At least one recipient is required"));
+        }
+        
+        let mut content_key = [0u8; 32];
+        rng.fill(&mut content_key);
+        
+        let mut recipient_keys = Vec::with_capacity(recipients.len());
+        for recipient in recipients {
+            let recipient_public = X25519PublicKey::from(
+                verifying_key_from_public_key(&recipient)?.to_montgomery().to_bytes()
+            );
+            let ephemeral_secret = StaticSecret::random_from_rng(&mut *rng);
+            let ephemeral_public = X25519PublicKey::from(&ephemeral_secret);
+            
+            let shared = ephemeral_secret.diffie_hellman(&recipient_public);
+            let wrap_key = derive_wrap_key(&shared, &ephemeral_public, &recipient_public)?;
+            let wrapped_key = encrypt_with_rng(&wrap_key, &content_key, rng)?;
+            
+            recipient_keys.push(RecipientKey {
+                recipient,
+                ephemeral_public_key: hex::encode(ephemeral_public.as_bytes()),
+                wrapped_key: hex::encode(wrapped_key),
+            });
+        }
+        
+        let data = hex::encode(encrypt_with_rng(&content_key, self.data.as_bytes(), rng)?);
+        
+        let mut encrypted = self.clone();
+        encrypted.id = Self::generate_id(&data, self.created_at);
+        encrypted.size = data.len() as u64;
+        encrypted.data = data;
+        encrypted.encrypted = true;
+        encrypted.recipient_keys = recipient_keys;
+        Ok(encrypted)
+    }
+    
+    /// Canonical bytes signed by `UserIdentity::sign_content`
+    ///
+    /// Keys are sorted, tags are sorted and deduplicated, and the derived
+    /// `id` and `size` fields are left out, so equal content always yields
+    /// equal bytes.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut tags = self.tags.clone();
+        tags.sort();
+        tags.dedup();
+        
+        let mut fields = BTreeMap::new();
+        if !self.attachments.is_empty() {
+            fields.insert("attachments", serde_json::json!(self.attachments));
+        }
+        fields.insert("content_type", serde_json::json!(self.content_type));
+        fields.insert("created_at", serde_json::json!(self.created_at));
+        fields.insert("data", serde_json::json!(self.data));
+        fields.insert("description", serde_json::json!(self.description));
+        fields.insert("encrypted", serde_json::json!(self.encrypted));
+        if let Some(expires_at) = self.expires_at {
+            fields.insert("expires_at", serde_json::json!(expires_at));
+        }
+        fields.insert("filename", serde_json::json!(self.filename));
+        fields.insert("mime_type", serde_json::json!(self.mime_type));
+        if let Some(supersedes) = &self.supersedes {
+            fields.insert("supersedes", serde_json::json!(supersedes));
+        }
+        fields.insert("recipient_keys", serde_json::json!(self.recipient_keys));
+        fields.insert("tags", serde_json::json!(tags));
+        if let Some(thumbnail) = &self.thumbnail {
+            fields.insert("thumbnail", serde_json::json!(thumbnail));
+        }
+        fields.insert("title", serde_json::json!(self.title));
+        
+        for field in REDACTABLE_FIELDS {
+            if let Some(commitment) = self.field_commitment(field) {
+                let mut signed = serde_json::json!({ "commitment": commitment });
+                // A redacted field must stay blank. Text written into it later
+                // is signed alongside, so the author's signature stops matching.
+                if self.redacted.contains_key(field) {
+                    if let Some(value) = self.field_value(field).filter(|v| !v.is_null() && v != "") {
+                        signed["value"] = value;
+                    }
+                }
+                fields.insert(field, signed);
+            }
+        }
+        canonical_json(&fields)
+    }
+    
+    /// Commitment signed in place of a redactable field, if it has one
+    fn field_commitment(&self, field: &str) -> Option<String> {
+        if let Some(commitment) = self.redacted.get(field) {
+            return Some(commitment.clone());
+        }
+        let salt = self.redaction_salts.get(field)?;
+        let value = self.field_value(field)?;
+        let mut hasher = blake3::Hasher::new_derive_key(REDACTION_CONTEXT);
+        hasher.update(salt.as_bytes());
+        hasher.update(&canonical_json(&value));
+        Some(hasher.finalize().to_hex().to_string())
+    }
+    
+    /// Current value of a redactable field
+    fn field_value(&self, field: &str) -> Option<serde_json::Value> {
+        match field {
+            "description" => Some(serde_json::json!(self.description)),
+            "filename" => Some(serde_json::json!(self.filename)),
+            "thumbnail" => Some(serde_json::json!(self.thumbnail)),
+            "title" => Some(serde_json::json!(self.title)),
+            _ => None,
+        }
+    }
+}
+
+/// Several content parts, such as a caption and its image, signed as one post
+///
+/// The combined CID covers every part in order, and the author signs that
+/// CID, so no part can be dropped, swapped or reordered without breaking
+/// `verify`.
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiContent {
+    /// CID over the signing bytes of all parts
+    #[pyo3(get)]
+    pub cid: String,
+    
+    /// The parts, in order
+    #[pyo3(get)]
+    pub parts: Vec<Content>,
+    
+    /// Public key of the signing author
+    #[pyo3(get)]
+    pub author: String,
+    
+    /// Author's signature over the combined CID (hex)
+    #[pyo3(get)]
+    pub signature: String,
+}
+
+#[pymethods]
+impl MultiContent {
+    /// Group `parts` and sign them with `identity`
+    #[new]
+    pub fn new(parts: Vec<Content>, identity: &UserIdentity) -> PyResult<Self> {
+        if parts.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("A post needs at least one part"));
+        }
+        let cid = Self::combined_cid(&parts);
+        let signature = identity.sign_payload(&domain_separated(MULTI_CONTENT_DOMAIN, cid.as_bytes()))?;
+        Ok(MultiContent {
+            cid,
+            parts,
+            author: identity.public_key.clone(),
+            signature: hex::encode(signature),
+        })
+    }
+    
+    /// Check the CID still matches the parts and the author signed it
+    pub fn verify(&self) -> bool {
+        if self.parts.is_empty() || self.cid != Self::combined_cid(&self.parts) {
+            return false;
+        }
+        let (Ok(verifying_key), Ok(signature)) = (verifying_key_from_public_key(&self.author), hex::decode(&self.signature)) else {
+            return false;
+        };
+        let payload = domain_separated(MULTI_CONTENT_DOMAIN, self.cid.as_bytes());
+        verify_signature(verifying_key.to_bytes().to_vec(), payload, signature).unwrap_or(false)
+    }
+    
+    /// Export as JSON
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(self)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+}
+
+impl MultiContent {
+    /// BLAKE3 over each part's length-prefixed signing bytes
+    fn combined_cid(parts: &[Content]) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for part in parts {
+            let bytes = part.signing_bytes();
+            hasher.update(&(bytes.len() as u64).to_be_bytes());
+            hasher.update(&bytes);
+        }
+        cid_from_digest(&hasher.finalize())
+    }
+}
+
+/// Assembles file content from successive chunks
+///
+/// Bytes are base64-encoded and BLAKE3-hashed as they arrive, so only the
+/// encoded data is held, never a second raw copy of the file.
+#[pyclass]
+#[derive(Default)]
+pub struct ContentBuilder {
+    /// Base64 of every complete 3-byte group received so far
+    data: String,
+    /// Up to two trailing bytes waiting for the rest of their group
+    pending: Vec<u8>,
+    hasher: blake3::Hasher,
+    size: u64,
+}
+
+#[pymethods]
+impl ContentBuilder {
+    /// Start an empty upload
+    #[new]
+    pub fn new() -> Self {
+        ContentBuilder::default()
+    }
+    
+    /// Append the next chunk of the file
+    pub fn push_chunk(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+        self.size += chunk.len() as u64;
+        
+        let mut chunk = chunk;
+        if !self.pending.is_empty() {
+            let take = (3 - self.pending.len()).min(chunk.len());
+            self.pending.extend_from_slice(&chunk[..take]);
+            chunk = &chunk[take..];
+            if self.pending.len() < 3 {
+                return;
+            }
+            BASE64.encode_string(&self.pending, &mut self.data);
+            self.pending.clear();
+        }
+        
+        let aligned = chunk.len() - chunk.len() % 3;
+        BASE64.encode_string(&chunk[..aligned], &mut self.data);
+        self.pending.extend_from_slice(&chunk[aligned..]);
+    }
+    
+    /// Bytes received so far
+    #[getter]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+    
+    /// CID of the bytes received so far, as `content_id` would compute it
+    pub fn cid(&self) -> String {
+        cid_from_digest(&self.hasher.finalize())
+    }
+    
+    /// Build the file content and reset the builder for another upload
+    #[pyo3(signature = (title, description, filename, mime_type=None))]
+    pub fn finish(
+        &mut self,
+        title: String,
+        description: String,
+        filename: String,
+        mime_type: Option<String>,
+    ) -> Content {
+        let mut builder = std::mem::take(self);
+        BASE64.encode_string(&builder.pending, &mut builder.data);
+        let data = builder.data;
+        
+        let mime_type = mime_type.or_else(|| detect_mime(&data));
+        let mut content = Content::new(
+            ContentType::File,
+            String::new(),
+            title,
+            description,
+            Some(filename),
+            mime_type,
+            None,
+        );
+        content.id = Content::generate_id(&data, content.created_at);
+        content.size = builder.size;
+        content.data = data;
+        content
+    }
+}
+
+/// Guess a MIME type from the magic bytes at the start of base64 data
+///
+/// Recognises PNG, JPEG, GIF, MP4 and WebM. Constructors fall back to the
+/// filename extension when this returns None.
+#[pyfunction]
+pub fn detect_mime(base64_data: &str) -> Option<String> {
+    // 24 base64 characters decode to the first 18 bytes
+    let prefix = base64_data.get(..24).unwrap_or(base64_data);
+    let header = BASE64.decode(prefix).ok()?;
+    
+    let mime = if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if header.starts_with(&[0xff, 0xd8, 0xff]) {
+        "image/jpeg"
+    } else if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if header.get(4..8) == Some(b"ftyp".as_slice()) {
+        "video/mp4"
+    } else if header.starts_with(&[0x1a, 0x45, 0xdf, 0xa3]) {
+        "video/webm"
+    } else {
+        return None;
+    };
+    Some(mime.to_string())
+}
+
+/// Rewrite base64 into the padded standard alphabet
+///
+/// Strips whitespace from line-wrapped input, maps the URL-safe alphabet
+/// onto the standard one and restores missing padding. Invalid characters
+/// are left in place for the decoder to reject.
+#[pyfunction]
+pub fn normalize_base64(s: &str) -> String {
+    let mut normalized: String = s.chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    
+    let unpadded_len = normalized.trim_end_matches('=').len();
+    normalized.truncate(unpadded_len);
+    while !normalized.len().is_multiple_of(4) {
+        normalized.push('=');
+    }
+    normalized
+}
+
+/// Format a Unix timestamp as an RFC 3339 UTC string
+pub fn to_rfc3339(timestamp: i64) -> PyResult<String> {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Timestamp out of range: {}", timestamp)
+        ))
+}
+
+/// Parse an RFC 3339 / ISO-8601 string with offset into a Unix timestamp
+#[pyfunction]
+pub fn parse_rfc3339(value: &str) -> PyResult<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.timestamp())
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Byte length of content data, decoding base64 for binary types
+fn decoded_len(content_type: &ContentType, data: &str) -> Result<usize, base64::DecodeError> {
+    match content_type {
+        ContentType::Text | ContentType::Custom(_) => Ok(data.len()),
+        _ => BASE64.decode(data).map(|bytes| bytes.len()),
+    }
+}
+
+/// Derive the key-wrapping key from an X25519 shared secret
+///
+/// Both public keys are bound into the derivation so a wrap cannot be
+/// replayed against a different recipient.
+fn derive_wrap_key(
+    shared: &SharedSecret,
+    ephemeral_public: &X25519PublicKey,
+    recipient_public: &X25519PublicKey,
+) -> PyResult<[u8; 32]> {
+    if !shared.was_contributory() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid recipient public key"));
+    }
+    
+    let mut key_material = Vec::with_capacity(96);
+    key_material.extend_from_slice(shared.as_bytes());
+    key_material.extend_from_slice(ephemeral_public.as_bytes());
+    key_material.extend_from_slice(recipient_public.as_bytes());
+    Ok(blake3::derive_key(KEY_WRAP_CONTEXT, &key_material))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +1135,81 @@ mod tests {
         assert_eq!(content.mime_type, Some("image/jpeg".to_string()));
     }
 
+    #[test]
+    fn test_mime_sniffed_from_magic_bytes() {
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let content = Content::picture(
+            "Pixel".to_string(),
+            "Not really a JPEG".to_string(),
+            png.to_string(),
+            "pixel.jpg".to_string(),
+            None,
+        );
+        assert_eq!(content.mime_type, Some("image/png".to_string()));
+        
+        assert_eq!(detect_mime(&BASE64.encode(b"GIF89a\x01\x00")), Some("image/gif".to_string()));
+        assert_eq!(detect_mime(&BASE64.encode([0xff, 0xd8, 0xff, 0xe0])), Some("image/jpeg".to_string()));
+        assert_eq!(detect_mime(&BASE64.encode(b"\x00\x00\x00\x18ftypmp42")), Some("video/mp4".to_string()));
+        assert_eq!(detect_mime(&BASE64.encode([0x1a, 0x45, 0xdf, 0xa3, 0x01])), Some("video/webm".to_string()));
+        assert_eq!(detect_mime(&BASE64.encode(b"plain text")), None);
+        assert_eq!(detect_mime("not base64!"), None);
+    }
+
+    #[test]
+    fn test_picture_size_is_decoded_size() {
+        // 1x1 PNG, 70 bytes decoded
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let content = Content::picture(
+            "Pixel".to_string(),
+            "A single pixel".to_string(),
+            png.to_string(),
+            "pixel.png".to_string(),
+            None,
+        );
+        
+        assert_eq!(content.size, 70);
+        assert_eq!(content.decoded_size().unwrap(), 70);
+        
+        let text = Content::text("Title".to_string(), "Desc".to_string(), "héllo".to_string());
+        assert_eq!(text.size, 6);
+        assert_eq!(text.decoded_size().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_thumbnail_round_trips() {
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let content = Content::picture(
+            "Pixel".to_string(),
+            "A single pixel".to_string(),
+            png.to_string(),
+            "pixel.png".to_string(),
+            None,
+        );
+        assert!(!content.to_json().unwrap().contains("thumbnail"));
+        
+        let with_thumbnail = content.with_thumbnail(png.to_string()).unwrap();
+        let restored = Content::from_json(&with_thumbnail.to_json().unwrap()).unwrap();
+        assert_eq!(restored.thumbnail.as_deref(), Some(png));
+        
+        let oversized = BASE64.encode(vec![0u8; MAX_THUMBNAIL_BYTES + 1]);
+        assert!(content.with_thumbnail(oversized).is_err());
+        
+        let text = Content::text("Title".to_string(), "Desc".to_string(), "Hello".to_string());
+        assert!(text.with_thumbnail(png.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_from_json_rejects_future_schema_version() {
+        let content = Content::text("Title".to_string(), "Desc".to_string(), "Hello".to_string());
+        let mut value: serde_json::Value = serde_json::from_str(&content.to_json().unwrap()).unwrap();
+        
+        value["schema_version"] = serde_json::json!(SCHEMA_VERSION + 1);
+        assert!(Content::from_json(&value.to_string()).is_err());
+        
+        value.as_object_mut().unwrap().remove("schema_version");
+        assert_eq!(Content::from_json(&value.to_string()).unwrap().schema_version, 1);
+    }
+
     #[test]
     fn test_tags() {
         let mut content = Content::text(
@@ -343,4 +1227,379 @@ mod tests {
         content.remove_tag("rust");
         assert_eq!(content.tags.len(), 1);
     }
+
+    #[test]
+    fn test_tags_sorted_and_unique() {
+        let mut content = Content::text(
+            "Tagged Post".to_string(),
+            "Description".to_string(),
+            "Content with tags".to_string(),
+        );
+        content.add_tag("b".to_string());
+        content.add_tag("a".to_string());
+        content.add_tag("b".to_string());
+        assert_eq!(content.tags, vec!["a".to_string(), "b".to_string()]);
+        
+        let content = Content::new(
+            ContentType::Text,
+            "Hello".to_string(),
+            "Title".to_string(),
+            "Desc".to_string(),
+            None,
+            None,
+            Some(vec!["b".to_string(), "a".to_string(), "a".to_string()]),
+        );
+        assert_eq!(content.tags, vec!["a".to_string(), "b".to_string()]);
+        
+        // Unsorted tags from a serialized document are normalized on load
+        let json = content.to_json().unwrap().replace(
+            "\"tags\": [\n    \"a\",\n    \"b\"\n  ]", r#""tags": ["c", "b", "c"]"#,
+        );
+        let mut loaded = Content::from_json(&json).unwrap();
+        assert_eq!(loaded.tags, vec!["b".to_string(), "c".to_string()]);
+        loaded.add_tag("a".to_string());
+        loaded.add_tag("c".to_string());
+        assert_eq!(loaded.tags, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn test_encrypt_for_recipient() {
+        let recipient = UserIdentity::new();
+        let outsider = UserIdentity::new();
+        
+        let content = Content::text(
+            "Private Post".to_string(),
+            "Only for friends".to_string(),
+            "Secret message".to_string(),
+        );
+        let encrypted = content.encrypt_for(vec![recipient.public_key.clone()]).unwrap();
+        
+        assert!(encrypted.encrypted);
+        assert!(!encrypted.data.contains("Secret message"));
+        assert_eq!(encrypted.recipients(), vec![recipient.public_key.clone()]);
+        assert_eq!(encrypted.decrypt_with(&recipient).unwrap(), "Secret message");
+        assert!(encrypted.decrypt_with(&outsider).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_for_multiple_recipients() {
+        let alice = UserIdentity::new();
+        let bob = UserIdentity::new();
+        
+        let content = Content::text(
+            "Group Post".to_string(),
+            "Description".to_string(),
+            "Hello group".to_string(),
+        );
+        let encrypted = content
+            .encrypt_for(vec![alice.public_key.clone(), bob.public_key.clone()])
+            .unwrap();
+        
+        assert_eq!(encrypted.decrypt_with(&alice).unwrap(), "Hello group");
+        assert_eq!(encrypted.decrypt_with(&bob).unwrap(), "Hello group");
+    }
+
+    #[test]
+    fn test_canonical_bytes_pinned() {
+        let mut content = Content::text(
+            "Title".to_string(),
+            "Desc".to_string(),
+            "Hello".to_string(),
+        );
+        content.id = "abc".to_string();
+        content.created_at = 1_700_000_000;
+        content.add_tag("b".to_string());
+        content.add_tag("a".to_string());
+        
+        assert_eq!(
+            String::from_utf8(content.canonical_bytes()).unwrap(),
+            concat!(
+                r#"{"content_type":"Text","created_at":1700000000,"data":"Hello","#,
+                r#""description":"Desc","encrypted":false,"filename":null,"id":"abc","#,
+                r#""mime_type":"text/plain","recipient_keys":[],"schema_version":1,"size":5,"#,
+                r#""tags":["a","b"],"#,
+                r#""title":"Title"}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut content = Content::text(
+            "Title".to_string(),
+            "Desc".to_string(),
+            "Hello".to_string(),
+        );
+        assert!(!content.is_expired(i64::MAX));
+        
+        content.expires_at = Some(1_000);
+        assert!(!content.is_expired(999));
+        assert!(content.is_expired(1_000));
+        
+        let restored = Content::from_json(&content.to_json().unwrap()).unwrap();
+        assert_eq!(restored.expires_at, Some(1_000));
+    }
+
+    #[test]
+    fn test_expiry_defaults_to_none_for_older_content() {
+        let mut content = Content::text(
+            "Title".to_string(),
+            "Desc".to_string(),
+            "Hello".to_string(),
+        );
+        let json = content.to_json().unwrap();
+        assert!(!json.contains("expires_at"));
+        assert_eq!(Content::from_json(&json).unwrap().expires_at, None);
+        
+        let unsigned = content.signing_bytes();
+        content.expires_at = Some(1_000);
+        assert_ne!(content.signing_bytes(), unsigned);
+    }
+
+    #[test]
+    fn test_created_at_rfc3339() {
+        let mut content = Content::text(
+            "Title".to_string(),
+            "Desc".to_string(),
+            "Hello".to_string(),
+        );
+        content.created_at = 1_700_000_000;
+        assert_eq!(content.created_at_rfc3339().unwrap(), "2023-11-14T22:13:20Z");
+        
+        assert_eq!(parse_rfc3339("2023-11-14T22:13:20Z").unwrap(), 1_700_000_000);
+        assert_eq!(parse_rfc3339("2023-11-15T00:13:20+02:00").unwrap(), 1_700_000_000);
+        assert!(parse_rfc3339("14/11/2023").is_err());
+        
+        content.created_at = i64::MAX;
+        assert!(content.created_at_rfc3339().is_err());
+    }
+
+    #[test]
+    fn test_swapped_attachment_breaks_signature() {
+        use crate::crypto::content_id;
+        use crate::identity::verify_content_signature;
+        
+        let identity = UserIdentity::new();
+        let mut content = Content::text(
+            "Album".to_string(),
+            "Desc".to_string(),
+            "See attached".to_string(),
+        );
+        let photo = content_id(b"photo bytes".to_vec());
+        content.add_attachment(photo.clone()).unwrap();
+        assert!(content.add_attachment(photo).is_err());
+        assert!(content.add_attachment("not-a-cid".to_string()).is_err());
+        
+        let signature = identity.sign_content(&content).unwrap();
+        assert!(verify_content_signature(identity.verifying_key_bytes.clone(), &content, signature.clone()).unwrap());
+        
+        content.attachments[0] = content_id(b"other bytes".to_vec());
+        assert!(!verify_content_signature(identity.verifying_key_bytes.clone(), &content, signature).unwrap());
+    }
+
+    #[test]
+    fn test_summary_truncates_on_char_boundary() {
+        // 49 ASCII bytes then a 4-byte emoji straddling byte 50
+        let data = format!("{}🦀🦀 tail", "a".repeat(49));
+        let content = Content::text("Title".to_string(), "Desc".to_string(), data);
+        
+        assert_eq!(
+            content.summary(),
+            format!("[Text] Title - {}🦀...", "a".repeat(49))
+        );
+        
+        let short = Content::text("Title".to_string(), "Desc".to_string(), "🦀".repeat(50));
+        assert_eq!(short.summary(), format!("[Text] Title - {}", "🦀".repeat(50)));
+    }
+
+    #[test]
+    fn test_wrapped_and_url_safe_base64_decode_alike() {
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        
+        let image: Vec<u8> = b"\x89PNG\r\n\x1a\n".iter().copied().chain(0..=255).collect();
+        let standard = BASE64.encode(&image);
+        let wrapped = standard.as_bytes()
+            .chunks(76)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect::<Vec<_>>()
+            .join("\r\n");
+        let url_safe = URL_SAFE_NO_PAD.encode(&image);
+        assert!(url_safe.contains('-') || url_safe.contains('_'));
+        
+        for encoded in [wrapped, url_safe] {
+            let content = Content::picture(
+                "Pic".to_string(), String::new(), encoded, "pic.bin".to_string(), None,
+            );
+            assert_eq!(content.data, standard);
+            assert_eq!(content.mime_type.as_deref(), Some("image/png"));
+            assert_eq!(BASE64.decode(&content.data).unwrap(), image);
+        }
+    }
+
+    #[test]
+    fn test_flipped_data_byte_fails_integrity() {
+        let mut content = Content::text("Post".to_string(), String::new(), "Hello".to_string());
+        assert!(content.verify_integrity());
+        
+        let mut bytes = content.data.clone().into_bytes();
+        bytes[0] ^= 0x02;
+        content.data = String::from_utf8(bytes).unwrap();
+        assert!(!content.verify_integrity());
+    }
+
+    #[test]
+    fn test_custom_content_type_round_trips() {
+        let poll = ContentType::from_str("poll").unwrap();
+        assert_eq!(poll, ContentType::Custom("poll".to_string()));
+        assert_eq!(ContentType::from_str("TEXT").unwrap(), ContentType::Text);
+        assert!(ContentType::from_str("").is_err());
+        assert_eq!(poll.to_string(), "poll");
+        assert_eq!(poll.kind(), ContentKind::Custom);
+        
+        let json = serde_json::to_string(&poll).unwrap();
+        assert_eq!(json, r#"{"Custom":"poll"}"#);
+        assert_eq!(serde_json::from_str::<ContentType>(&json).unwrap(), poll);
+        
+        let content = Content::custom(
+            "poll".to_string(), r#"{"options":["a","b"]}"#.to_string(), "Lunch?".to_string(), String::new(), None,
+        ).unwrap();
+        assert_eq!(content.content_type, poll);
+        assert_eq!(content.type_name(), "poll");
+        assert_eq!(content.size, 21);
+        assert!(content.summary().starts_with("[poll]"));
+        
+        let json = content.to_json().unwrap();
+        let restored = Content::from_json(&json).unwrap();
+        assert_eq!(restored.content_type, poll);
+        assert_eq!(restored.custom_type().as_deref(), Some("poll"));
+        
+        // The name is signed, so it can't be swapped after signing
+        let mut renamed = restored.clone();
+        renamed.content_type = ContentType::Custom("vote".to_string());
+        assert_ne!(renamed.signing_bytes(), restored.signing_bytes());
+    }
+
+    #[test]
+    fn test_custom_type_cannot_shadow_builtin() {
+        for name in ["", "Text", "image"] {
+            assert!(Content::custom(name.to_string(), "x".to_string(), String::new(), String::new(), None).is_err());
+        }
+        
+        let content = Content::custom("poll".to_string(), "x".to_string(), String::new(), String::new(), None).unwrap();
+        let json = content.to_json().unwrap().replace(r#""Custom": "poll""#, r#""Custom": "Text""#);
+        assert!(Content::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_chunked_build_matches_one_shot() {
+        let file: Vec<u8> = b"\x89PNG\r\n\x1a\n".iter().copied().chain((0..1000).map(|i| i as u8)).collect();
+        let mut builder = ContentBuilder::new();
+        for chunk in [&file[..100], &file[100..101], &file[101..]] {
+            builder.push_chunk(chunk);
+        }
+        assert_eq!(builder.cid(), crate::crypto::content_id(file.clone()));
+        
+        let built = builder.finish("Upload".to_string(), String::new(), "pic.png".to_string(), None);
+        let one_shot = Content::file("Upload".to_string(), String::new(), BASE64.encode(&file), "pic.png".to_string(), None);
+        
+        assert_eq!(built.data, one_shot.data);
+        assert_eq!(built.size, one_shot.size);
+        assert_eq!(built.mime_type, one_shot.mime_type);
+        assert_eq!(built.id, Content::generate_id(&one_shot.data, built.created_at));
+        assert!(built.verify_integrity());
+        assert_eq!(builder.size(), 0);
+    }
+
+    #[test]
+    fn test_redacted_description_still_verifies() {
+        use crate::identity::verify_content_signature;
+        
+        let identity = UserIdentity::new();
+        let mut content = Content::text("Title".to_string(), "My home address".to_string(), "Hello".to_string());
+        assert!(content.redact("description").is_err());
+        
+        content.make_redactable();
+        let signature = identity.sign_content(&content).unwrap();
+        let salt = content.redaction_salts["description"].clone();
+        content.redact("description").unwrap();
+        
+        assert!(content.description.is_empty());
+        assert!(verify_content_signature(identity.verifying_key_bytes.clone(), &content, signature.clone()).unwrap());
+        let json = content.to_json().unwrap();
+        assert!(!json.contains("My home address"));
+        assert!(!json.contains(&salt));
+        assert!(content.redact("description").is_err());
+        assert!(content.redact("data").is_err());
+        
+        // Unredacted fields are still bound by the signature
+        let restored = Content::from_json(&json).unwrap();
+        assert!(verify_content_signature(identity.verifying_key_bytes.clone(), &restored, signature.clone()).unwrap());
+        let mut tampered = restored.clone();
+        tampered.title = "Other".to_string();
+        assert!(!verify_content_signature(identity.verifying_key_bytes.clone(), &tampered, signature).unwrap());
+    }
+
+    #[test]
+    fn test_filled_redacted_field_fails_verification() {
+        use crate::identity::verify_content_signature;
+        
+        let identity = UserIdentity::new();
+        let mut content = Content::text("Title".to_string(), "Private".to_string(), "Hello".to_string());
+        content.filename = Some("notes.txt".to_string());
+        content.make_redactable();
+        let signature = identity.sign_content(&content).unwrap();
+        content.redact("description").unwrap();
+        content.redact("filename").unwrap();
+        assert!(verify_content_signature(identity.verifying_key_bytes.clone(), &content, signature.clone()).unwrap());
+        
+        let mut forged = content.clone();
+        forged.description = "Send funds to this address".to_string();
+        assert!(!verify_content_signature(identity.verifying_key_bytes.clone(), &forged, signature.clone()).unwrap());
+        
+        let mut forged = content;
+        forged.filename = Some("malware.exe".to_string());
+        assert!(!verify_content_signature(identity.verifying_key_bytes.clone(), &forged, signature).unwrap());
+    }
+
+    #[test]
+    fn test_equality_follows_id() {
+        let content = Content::text("Title".to_string(), String::new(), "Hello".to_string());
+        let mut retitled = content.clone();
+        retitled.title = "Other title".to_string();
+        
+        assert!(content.__eq__(&retitled));
+        assert_eq!(content.__hash__(), retitled.__hash__());
+        
+        let mut tampered = content.clone();
+        tampered.data = "Tampered".to_string();
+        assert!(!content.__eq__(&tampered));
+        assert_ne!(content.__hash__(), tampered.__hash__());
+    }
+
+    #[test]
+    fn test_multi_content_binds_parts_together() {
+        let identity = UserIdentity::new();
+        let caption = Content::text("Sunset".to_string(), String::new(), "From the pier".to_string());
+        let image = Content::picture(
+            "Sunset".to_string(), String::new(), "iVBORw0KGgo=".to_string(),
+            "sunset.png".to_string(), Some("image/png".to_string()),
+        );
+        
+        let post = MultiContent::new(vec![caption.clone(), image.clone()], &identity).unwrap();
+        assert!(post.verify());
+        
+        let mut without_image = post.clone();
+        without_image.parts.pop();
+        assert!(!without_image.verify());
+        
+        // Recomputing the CID doesn't help without the author's key
+        without_image.cid = MultiContent::combined_cid(&without_image.parts);
+        assert!(!without_image.verify());
+        
+        let mut reordered = post.clone();
+        reordered.parts = vec![image, caption];
+        assert!(!reordered.verify());
+        
+        assert!(MultiContent::new(Vec::new(), &identity).is_err());
+    }
 }
@@ -2,8 +2,15 @@
 //! Provides hashing and encryption functions
 
 use pyo3::prelude::*;
-use sha2::{Sha256, Digest};
+use sha2::{Sha256, Sha512, Digest};
+use sha3::Sha3_256;
 use blake3;
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit, aead::Aead};
+use chacha20poly1305::aead::generic_array::GenericArray;
+use rand::{CryptoRng, Rng, RngCore};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Hash data using SHA-256
 #[pyfunction]
@@ -20,16 +27,171 @@ pub fn hash_blake3(data: Vec<u8>) -> String {
     hash.to_hex().to_string()
 }
 
+/// Hash data using SHA-512
+#[pyfunction]
+pub fn hash_sha512(data: Vec<u8>) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(&data);
+    hex::encode(hasher.finalize())
+}
+
+/// Hash data using SHA3-256 (FIPS 202, not legacy Keccak-256)
+#[pyfunction]
+pub fn hash_sha3_256(data: Vec<u8>) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(&data);
+    hex::encode(hasher.finalize())
+}
+
 /// Hash string data using SHA-256
 #[pyfunction]
 pub fn hash_string(data: &str) -> String {
     hash_data(data.as_bytes().to_vec())
 }
 
+/// Domain tag for content signatures
+///
+/// Every signed payload starts with a domain tag and a NUL byte, so a
+/// signature made for one purpose never verifies for another:
+///
+/// - `rootless:content:v1` — `UserIdentity::sign_content`
+/// - `rootless:message:v1` — `UserIdentity::sign_message`
+/// - `rootless:challenge:v1` — `UserIdentity::answer_challenge`
+/// - `rootless:multi-content:v1` — `MultiContent::new`
+/// - `rootless:block:v1` — `Block::sign`
+/// - `rootless:block-coauthor:v1` — `Block::add_signature`
+/// - `rootless:attestation:v1` — `Block::add_attestation`
+/// - `rootless:checkpoint:v1` — `Blockchain::create_checkpoint`
+///
+/// The raw `UserIdentity::sign` and `sign_hex` refuse payloads starting
+/// with `DOMAIN_PREFIX`, so they can't be used to forge any of these.
+pub const CONTENT_DOMAIN: &str = "rootless:content:v1";
+
+/// Domain tag for direct message signatures
+pub const MESSAGE_DOMAIN: &str = "rootless:message:v1";
+
+/// Domain tag for relay login challenge answers
+pub const CHALLENGE_DOMAIN: &str = "rootless:challenge:v1";
+
+/// Domain tag for signatures over a group of content parts
+pub const MULTI_CONTENT_DOMAIN: &str = "rootless:multi-content:v1";
+
+/// Domain tag for a block author's signature over the block hash
+pub const BLOCK_DOMAIN: &str = "rootless:block:v1";
+
+/// Domain tag for co-author signatures over the block hash
+pub const BLOCK_COAUTHOR_DOMAIN: &str = "rootless:block-coauthor:v1";
+
+/// Domain tag for notary attestations of a block hash
+pub const ATTESTATION_DOMAIN: &str = "rootless:attestation:v1";
+
+/// Domain tag for an authority's checkpoint of a block height and hash
+pub const CHECKPOINT_DOMAIN: &str = "rootless:checkpoint:v1";
+
+/// Prefix shared by every domain tag
+pub const DOMAIN_PREFIX: &str = "rootless:";
+
+/// Prefix `payload` with a domain tag and a NUL separator
+pub fn domain_separated(domain: &str, payload: &[u8]) -> Vec<u8> {
+    [domain.as_bytes(), b"\0", payload].concat()
+}
+
+/// Multibase-style prefix shared with `rootlessnet-tui` content CIDs
+const CID_PREFIX: &str = "bafk";
+
+/// Number of BLAKE3 digest bytes kept in a CID
+const CID_DIGEST_LEN: usize = 16;
+
 /// Generate a content-addressed ID from data
+///
+/// Same scheme as `rootlessnet-tui` `Content::new`: `bafk` followed by the
+/// base58btc encoding of the first 16 bytes of the BLAKE3 digest.
 #[pyfunction]
 pub fn content_id(data: Vec<u8>) -> String {
-    format!("cid:{}", hash_blake3(data))
+    cid_from_digest(&blake3::hash(&data))
+}
+
+/// CID for an already computed BLAKE3 digest, e.g. from a streaming hasher
+pub fn cid_from_digest(digest: &blake3::Hash) -> String {
+    format!("{}{}", CID_PREFIX, bs58::encode(&digest.as_bytes()[..CID_DIGEST_LEN]).into_string())
+}
+
+/// Recover the truncated BLAKE3 digest from a CID made by `content_id`
+#[pyfunction]
+pub fn parse_cid(cid: &str) -> PyResult<Vec<u8>> {
+    let encoded = cid.strip_prefix(CID_PREFIX)
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("CID must start with bafk"))?;
+    
+    let digest = bs58::decode(encoded).into_vec()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    
+    if digest.len() != CID_DIGEST_LEN {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid CID digest length"));
+    }
+    Ok(digest)
+}
+
+/// Serialize a value as canonical JSON (sorted keys, no whitespace)
+///
+/// Used wherever structured data is hashed or signed, so the bytes do not
+/// depend on field declaration order or serializer formatting.
+pub fn canonical_json<T: Serialize>(value: &T) -> Vec<u8> {
+    serde_json::to_vec(&canonical_value(value)).unwrap_or_default()
+}
+
+/// Convert a value to JSON with the keys of every object sorted
+pub fn canonical_value<T: Serialize>(value: &T) -> Value {
+    sort_keys(serde_json::to_value(value).unwrap_or(Value::Null))
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> = map.into_iter()
+                .map(|(k, v)| (k, sort_keys(v)))
+                .collect();
+            Value::Object(sorted.into_iter().collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// Encrypt data with ChaCha20-Poly1305, prepending the random nonce
+pub fn encrypt_with_key(key: &[u8; 32], plaintext: &[u8]) -> PyResult<Vec<u8>> {
+    encrypt_with_rng(key, plaintext, &mut rand::thread_rng())
+}
+
+/// `encrypt_with_key` with the nonce drawn from `rng`
+///
+/// For reproducible test vectors; a repeated nonce under one key breaks
+/// the cipher, so production code must use `encrypt_with_key`.
+pub fn encrypt_with_rng<R: RngCore + CryptoRng>(key: &[u8; 32], plaintext: &[u8], rng: &mut R) -> PyResult<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(key));
+    
+    let mut nonce_bytes = [0u8; 12];
+    rng.fill(&mut nonce_bytes);
+    let nonce = GenericArray::from_slice(&nonce_bytes);
+    
+    let encrypted = cipher.encrypt(nonce, plaintext)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    
+    let mut result = nonce_bytes.to_vec();
+    result.extend(encrypted);
+    Ok(result)
+}
+
+/// Decrypt data produced by `encrypt_with_key`
+pub fn decrypt_with_key(key: &[u8; 32], data: &[u8]) -> PyResult<Vec<u8>> {
+    if data.len() < 12 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid encrypted data"));
+    }
+    
+    let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(key));
+    let (nonce_bytes, ciphertext) = data.split_at(12);
+    
+    cipher.decrypt(GenericArray::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Decryption failed"))
 }
 
 #[cfg(test)]
@@ -50,6 +212,32 @@ mod tests {
         assert_eq!(hash.len(), 64); // BLAKE3 produces 32 bytes = 64 hex chars
     }
 
+    #[test]
+    fn test_hash_sha512_known_answers() {
+        // FIPS 180-2 test vectors
+        assert_eq!(
+            hash_sha512(b"abc".to_vec()),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            hash_sha512(Vec::new()),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+    }
+
+    #[test]
+    fn test_hash_sha3_256_known_answers() {
+        // FIPS 202 test vectors
+        assert_eq!(
+            hash_sha3_256(b"abc".to_vec()),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_eq!(
+            hash_sha3_256(Vec::new()),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+    }
+
     #[test]
     fn test_hash_string() {
         let hash1 = hash_string("test");
@@ -57,10 +245,33 @@ mod tests {
         assert_eq!(hash1, hash2); // Same input = same output
     }
 
+    #[test]
+    fn test_canonical_json_sorts_nested_keys() {
+        let value = serde_json::json!({"b": 1, "a": {"d": [{"z": 0, "y": 1}], "c": null}});
+        assert_eq!(
+            canonical_json(&value),
+            br#"{"a":{"c":null,"d":[{"y":1,"z":0}]},"b":1}"#.to_vec()
+        );
+    }
+
     #[test]
     fn test_content_id() {
         let data = b"Some content";
         let cid = content_id(data.to_vec());
-        assert!(cid.starts_with("cid:"));
+        assert!(cid.starts_with("bafk"));
+        assert_eq!(cid, content_id(data.to_vec()));
+    }
+
+    #[test]
+    fn test_content_id_round_trips_through_parse_cid() {
+        let data = b"Some content";
+        let cid = content_id(data.to_vec());
+        
+        let digest = parse_cid(&cid).unwrap();
+        assert_eq!(digest, blake3::hash(data).as_bytes()[..16].to_vec());
+        
+        assert!(parse_cid("cid:abcdef").is_err());
+        assert!(parse_cid("bafk0OIl").is_err());
+        assert!(parse_cid("bafk2").is_err());
     }
 }
@@ -2,18 +2,60 @@
 //! Bitcoin-like identity system with public/private keys
 
 use pyo3::prelude::*;
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::digest::{consts::U64, FixedOutput, HashMarker, Output, OutputSizeUser, Update};
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
 
+use crate::content::Content;
+use crate::crypto::{domain_separated, CHALLENGE_DOMAIN, CONTENT_DOMAIN, DOMAIN_PREFIX, MESSAGE_DOMAIN};
 use crate::wordlist::WORDLIST;
 
 /// Characters allowed in public keys
 const PUBLIC_KEY_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789$-#";
 
+/// Default number of words in a mnemonic private key
+const DEFAULT_MNEMONIC_WORDS: usize = 100;
+
+/// Minimum mnemonic entropy accepted for new identities
+const MIN_MNEMONIC_ENTROPY_BITS: f64 = 128.0;
+
+/// Passwords scored 0 regardless of their character mix
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "1234567", "12345678", "123456789", "1234567890", "000000", "111111",
+    "password", "password1", "qwerty", "qwerty123", "abc123", "letmein", "iloveyou",
+    "admin", "welcome", "monkey", "dragon", "football", "baseball", "sunshine",
+];
+
+/// Marks backups that carry a password check tag
+const BACKUP_MAGIC: &[u8; 4] = b"RNB1";
+
+/// Constant authenticated by the backup password check tag
+const BACKUP_CHECK_CONSTANT: &[u8] = b"RootlessNet backup password check v1";
+
+/// Ed25519ph context string
+///
+/// RFC 8032 binds the context into every prehashed signature, so a signature
+/// made here can never be replayed as a plain Ed25519 signature or as an
+/// Ed25519ph signature for another protocol.
+const PREHASH_CONTEXT: &[u8] = b"RootlessNet Ed25519ph v1";
+
+/// BLAKE3 context deriving the padding/mnemonic RNG seed in `from_seed`
+const SEED_RNG_CONTEXT: &str = "RootlessNet identity seed rng v1";
+
+/// BLAKE3 context deriving the Ed25519 seed from a mnemonic
+const MNEMONIC_SEED_CONTEXT: &str = "RootlessNet mnemonic seed v1";
+
 /// User Identity with public and private keys
+///
+/// Secret fields are only serialized by `to_full_json`, unless the
+/// `serialize-secrets` feature is enabled.
 #[pyclass]
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct UserIdentity {
     /// Public key - visible to everyone, ~100 characters with alphanumeric + symbols
     #[pyo3(get)]
@@ -21,9 +63,11 @@ pub struct UserIdentity {
     
     /// Private key - 100 random words from 4000-word list (mnemonic)
     #[pyo3(get)]
+    #[cfg_attr(not(feature = "serialize-secrets"), serde(skip_serializing))]
     pub private_key: String,
     
     /// Ed25519 signing key bytes (for actual cryptographic operations)
+    #[cfg_attr(not(feature = "serialize-secrets"), serde(skip_serializing))]
     pub signing_key_bytes: Vec<u8>,
     
     /// Ed25519 verifying key bytes
@@ -36,50 +80,221 @@ pub struct UserIdentity {
 
 #[pymethods]
 impl UserIdentity {
-    /// Create a new random identity
+    /// Create a new random identity with a mnemonic of `mnemonic_words` words
+    ///
+    /// The keys are derived from the mnemonic, so `from_indices` restores
+    /// them. Lengths giving less than 128 bits of entropy are rejected.
     #[new]
-    pub fn new() -> Self {
-        let mut rng = rand::thread_rng();
+    #[pyo3(signature = (mnemonic_words=DEFAULT_MNEMONIC_WORDS))]
+    pub fn with_mnemonic_words(mnemonic_words: usize) -> PyResult<Self> {
+        let entropy = mnemonic_entropy_bits(mnemonic_words);
+        if entropy < MIN_MNEMONIC_ENTROPY_BITS {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{} words give only {:.1} bits of entropy (minimum {})",
+                mnemonic_words, entropy, MIN_MNEMONIC_ENTROPY_BITS
+            )));
+        }
         
-        // Generate Ed25519 keypair for actual crypto operations
-        let mut seed = [0u8; 32];
-        rng.fill(&mut seed);
+        Ok(Self::random_mnemonic(mnemonic_words, &mut rand::thread_rng()))
+    }
+    
+    /// Rebuild an identity from its mnemonic as `WORDLIST` indices
+    ///
+    /// Reproduces the keys of identities whose keys come from their mnemonic,
+    /// which is every identity made by `new` or `from_indices`. Identities
+    /// from `from_seed` or `vanity` carry a mnemonic that does not hold the
+    /// key, so it rebuilds a different identity for them.
+    #[staticmethod]
+    pub fn from_indices(indices: Vec<u16>) -> PyResult<Self> {
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= WORDLIST.len()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Word index {} is out of range (0-{})",
+                index, WORDLIST.len() - 1
+            )));
+        }
+        let entropy = mnemonic_entropy_bits(indices.len());
+        if entropy < MIN_MNEMONIC_ENTROPY_BITS {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{} words give only {:.1} bits of entropy (minimum {})",
+                indices.len(), entropy, MIN_MNEMONIC_ENTROPY_BITS
+            )));
+        }
+        Ok(Self::from_mnemonic_indices(&indices))
+    }
+    
+    /// The mnemonic as indices into `WORDLIST`, for compact storage
+    ///
+    /// Fails unless the mnemonic derives this identity's signing key, as it
+    /// does not for `from_seed` and vanity identities or ones created
+    /// before keys were derived from the mnemonic: `from_indices` would
+    /// rebuild a different identity from such a backup.
+    pub fn mnemonic_indices(&self) -> PyResult<Vec<u16>> {
+        let indices = self.private_key.split_whitespace()
+            .map(|word| word_indices().get(word).copied().ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unknown mnemonic word: {}", word))
+            }))
+            .collect::<PyResult<Vec<u16>>>()?;
+        if Self::from_mnemonic_indices(&indices).signing_key_bytes != self.signing_key_bytes {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Mnemonic does not derive this identity's key; back up to_full_json instead"
+            ));
+        }
+        Ok(indices)
+    }
+    
+    /// Deterministically build an identity from a 32-byte seed
+    ///
+    /// The seed is used directly as the Ed25519 secret key, so it must come
+    /// from a high-entropy source; anyone who knows it controls the identity.
+    /// The same seed always yields the same keys, public key string and mnemonic.
+    #[staticmethod]
+    pub fn from_seed(seed: [u8; 32]) -> Self {
         let signing_key = SigningKey::from_bytes(&seed);
-        let verifying_key = signing_key.verifying_key();
-        
-        // Generate public key string (~100+ chars with alphanumeric + $-#)
-        let public_key = generate_public_key_string(&verifying_key.to_bytes(), &mut rng);
-        
-        // Generate private key as 100 random words from wordlist
-        let private_key = generate_mnemonic_key(&mut rng);
-        
-        let created_at = chrono::Utc::now().timestamp();
+        let mut rng = ChaCha20Rng::from_seed(blake3::derive_key(SEED_RNG_CONTEXT, &seed));
+        Self::from_signing_key(&signing_key, DEFAULT_MNEMONIC_WORDS, &mut rng)
+    }
+    
+    /// Short display ID: base58 of the first 8 bytes of BLAKE3(verifying key)
+    pub fn fingerprint(&self) -> String {
+        fingerprint_of(&self.verifying_key_bytes)
+    }
+    
+    /// Entropy of the mnemonic private key in bits
+    pub fn entropy_bits(&self) -> f64 {
+        mnemonic_entropy_bits(self.private_key.split_whitespace().count())
+    }
+    
+    /// Generate an identity whose public key starts with `prefix`
+    ///
+    /// Public keys begin with the lowercase hex verifying key, so each prefix
+    /// character matches with probability 1/16. Expected attempts: 1 char ~16,
+    /// 2 chars ~256, 3 chars ~4,096, 4 chars ~65,536, 5 chars ~1 million,
+    /// 6 chars ~16.8 million. Returns `None` after `max_attempts` misses.
+    #[staticmethod]
+    pub fn generate_vanity(prefix: &str, max_attempts: u64) -> PyResult<Option<Self>> {
+        validate_vanity_prefix(prefix)?;
         
-        UserIdentity {
-            public_key,
-            private_key,
-            signing_key_bytes: signing_key.to_bytes().to_vec(),
-            verifying_key_bytes: verifying_key.to_bytes().to_vec(),
-            created_at,
-        }
+        #[cfg(feature = "parallel")]
+        let identity = Self::search_vanity_parallel(prefix, max_attempts);
+        #[cfg(not(feature = "parallel"))]
+        let identity = Self::search_vanity(prefix, max_attempts);
+        Ok(identity)
+    }
+    
+    /// Recover the raw 32-byte verifying key from a shareable public key string
+    #[staticmethod]
+    pub fn verifying_key_from_public(public_key: &str) -> PyResult<Vec<u8>> {
+        Ok(verifying_key_from_public_key(public_key)?.to_bytes().to_vec())
     }
     
     /// Sign data with private key
+    ///
+    /// Data starting with a `rootless:` domain tag is refused; use the
+    /// dedicated method such as `sign_content` or `sign_message`.
     pub fn sign(&self, data: &[u8]) -> PyResult<Vec<u8>> {
-        let signing_key_bytes: [u8; 32] = self.signing_key_bytes.clone()
-            .try_into()
-            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid signing key"))?;
-        let signing_key = SigningKey::from_bytes(&signing_key_bytes);
-        let signature = signing_key.sign(data);
+        check_untagged(data)?;
+        self.sign_payload(data)
+    }
+    
+    /// Sign a SHA-512 digest computed by the caller, using Ed25519ph
+    ///
+    /// Lets large or streamed payloads be hashed once instead of read twice.
+    /// Verify with `verify_prehashed`.
+    pub fn sign_prehashed(&self, hash: [u8; 64]) -> PyResult<Vec<u8>> {
+        let signature = self.signing_key()?
+            .sign_prehashed(Sha512Prehash::from_digest(hash), Some(PREHASH_CONTEXT))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         Ok(signature.to_bytes().to_vec())
     }
     
-    /// Get identity info as JSON string
+    /// Sign arbitrary bytes, returning a hex-encoded detached signature
+    pub fn sign_detached(&self, data: Vec<u8>) -> PyResult<String> {
+        Ok(hex::encode(self.sign(&data)?))
+    }
+    
+    /// Sign the canonical form of a piece of content
+    pub fn sign_content(&self, content: &Content) -> PyResult<Vec<u8>> {
+        self.sign_payload(&domain_separated(CONTENT_DOMAIN, &content.signing_bytes()))
+    }
+    
+    /// Sign a direct message under the message domain
+    ///
+    /// Check it with `verify_message_signature`.
+    pub fn sign_message(&self, data: Vec<u8>) -> PyResult<Vec<u8>> {
+        self.sign_payload(&domain_separated(MESSAGE_DOMAIN, &data))
+    }
+    
+    /// Sign a relay's login challenge nonce
+    ///
+    /// Check the answer with `verify_challenge`.
+    pub fn answer_challenge(&self, nonce: Vec<u8>) -> PyResult<Vec<u8>> {
+        self.sign_payload(&challenge_payload(&nonce))
+    }
+    
+    /// Get identity info as JSON string (public fields only)
+    ///
+    /// Same output as `public_json`.
     pub fn to_json(&self) -> PyResult<String> {
-        serde_json::to_string_pretty(self)
+        self.public_json()
+    }
+    
+    /// Serialize public fields only (safe to share)
+    ///
+    /// Same output as `public_json`, so `PublicIdentity.from_public_json`
+    /// reads it back.
+    pub fn to_public_json(&self) -> PyResult<String> {
+        self.public_json()
+    }
+    
+    /// Compact public profile: `public_key`, `created_at` and `fingerprint`
+    ///
+    /// The safe format for sharing an identity; read it back with
+    /// `PublicIdentity.from_public_json`.
+    pub fn public_json(&self) -> PyResult<String> {
+        let profile = PublicIdentity {
+            public_key: self.public_key.clone(),
+            created_at: self.created_at,
+            fingerprint: self.fingerprint(),
+        };
+        serde_json::to_string(&profile)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+    
+    /// Serialize the full identity including the private key
+    pub fn to_full_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.full())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
     
+    /// Load an identity written by `to_full_json`, rejecting inconsistent keys
+    #[staticmethod]
+    pub fn from_json(json_str: &str) -> PyResult<Self> {
+        let identity: UserIdentity = serde_json::from_str(json_str)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        identity.validate()?;
+        Ok(identity)
+    }
+    
+    /// Check the key material is well formed and belongs together
+    ///
+    /// Both keys must be 32 bytes, `verifying_key_bytes` must be the key
+    /// derived from `signing_key_bytes`, and `public_key` must encode it.
+    pub fn validate(&self) -> PyResult<()> {
+        let signing_key = self.signing_key()?;
+        let verifying_key = strict_verifying_key(&self.verifying_key_bytes)?;
+        if signing_key.verifying_key() != verifying_key {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Verifying key does not match signing key"
+            ));
+        }
+        if verifying_key_from_public_key(&self.public_key)? != verifying_key {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Public key does not match verifying key"
+            ));
+        }
+        Ok(())
+    }
+    
     /// Get public-only identity info (safe to share)
     pub fn public_info(&self) -> String {
         format!(
@@ -92,21 +307,34 @@ impl UserIdentity {
     }
     
     /// Export identity to encrypted backup (returns JSON)
-    pub fn export_encrypted(&self, password: &str) -> PyResult<String> {
-        use sha2::{Sha256, Digest};
+    ///
+    /// The hex blob is `RNB1 || check tag || nonce || ciphertext`; the tag lets
+    /// `verify_backup_password` test a password without decrypting.
+    ///
+    /// Passwords scoring below `min_strength` (see `estimate_password_strength`)
+    /// are rejected.
+    #[pyo3(signature = (password, min_strength=0))]
+    pub fn export_encrypted(&self, password: &str, min_strength: u8) -> PyResult<String> {
         use chacha20poly1305::{ChaCha20Poly1305, KeyInit, aead::Aead};
         use chacha20poly1305::aead::generic_array::GenericArray;
         use rand::Rng;
         
+        let strength = estimate_password_strength(password);
+        if strength < min_strength {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Password strength {}/4 is below the required {}; use a longer passphrase \
+                 of several unrelated words, or mix in digits and symbols",
+                strength, min_strength
+            )));
+        }
+        
         // Derive key from password
-        let mut hasher = Sha256::new();
-        hasher.update(password.as_bytes());
-        let key_bytes = hasher.finalize();
+        let key_bytes = backup_key(password);
         
         let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&key_bytes));
         
-        // Serialize identity
-        let data = serde_json::to_string(self)
+        // Serialize identity, including secrets
+        let data = serde_json::to_string(&self.full())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         
         // Generate random nonce for security
@@ -118,30 +346,41 @@ impl UserIdentity {
         let encrypted = cipher.encrypt(nonce, data.as_bytes())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         
-        // Prepend nonce to encrypted data for storage
-        let mut result = nonce_bytes.to_vec();
+        // Prepend the password check and nonce to encrypted data for storage
+        let mut result = BACKUP_MAGIC.to_vec();
+        result.extend(backup_password_check(&key_bytes).as_bytes());
+        result.extend(nonce_bytes);
         result.extend(encrypted);
         Ok(hex::encode(result))
     }
     
     /// Import identity from encrypted backup
+    ///
+    /// Also accepts older backups without a password check tag.
     #[staticmethod]
     pub fn import_encrypted(encrypted_hex: &str, password: &str) -> PyResult<Self> {
-        use sha2::{Sha256, Digest};
         use chacha20poly1305::{ChaCha20Poly1305, KeyInit, aead::Aead};
         use chacha20poly1305::aead::generic_array::GenericArray;
         
         // Derive key from password
-        let mut hasher = Sha256::new();
-        hasher.update(password.as_bytes());
-        let key_bytes = hasher.finalize();
+        let key_bytes = backup_key(password);
         
         let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&key_bytes));
         
-        // Decode and extract nonce + ciphertext
+        // Decode, check the password tag and extract nonce + ciphertext
         let data = hex::decode(encrypted_hex)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         
+        let data = match split_backup_check(&data) {
+            Some((check, rest)) => {
+                if !backup_check_matches(&key_bytes, check) {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Wrong password"));
+                }
+                rest
+            }
+            None => &data[..],
+        };
+        
         if data.len() < 12 {
             return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid encrypted data"));
         }
@@ -155,17 +394,237 @@ impl UserIdentity {
         let json_str = String::from_utf8(decrypted)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         
-        serde_json::from_str(&json_str)
+        let identity: UserIdentity = serde_json::from_str(&json_str)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        
+        identity.validate()?;
+        Ok(identity)
+    }
+}
+
+/// Public profile of an identity, as produced by `UserIdentity::public_json`
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicIdentity {
+    #[pyo3(get)]
+    pub public_key: String,
+    #[pyo3(get)]
+    pub created_at: i64,
+    #[pyo3(get)]
+    pub fingerprint: String,
+}
+
+#[pymethods]
+impl PublicIdentity {
+    /// Parse a public profile, checking the fingerprint matches the key
+    #[staticmethod]
+    pub fn from_public_json(json: &str) -> PyResult<Self> {
+        let profile: Self = serde_json::from_str(json)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let verifying_key = verifying_key_from_public_key(&profile.public_key)?;
+        if fingerprint_of(verifying_key.as_bytes()) != profile.fingerprint {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Fingerprint does not match public key"
+            ));
+        }
+        Ok(profile)
+    }
+    
+    /// Serialize back to the `public_json` format
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(self)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
     }
 }
 
+/// Base58 of the first 8 bytes of BLAKE3(verifying key)
+fn fingerprint_of(verifying_key_bytes: &[u8]) -> String {
+    let digest = blake3::hash(verifying_key_bytes);
+    bs58::encode(&digest.as_bytes()[..8]).into_string()
+}
+
+/// Serialization view of an identity including its secret fields
+#[derive(Serialize)]
+struct FullUserIdentity<'a> {
+    public_key: &'a str,
+    private_key: &'a str,
+    signing_key_bytes: &'a [u8],
+    verifying_key_bytes: &'a [u8],
+    created_at: i64,
+}
+
+impl fmt::Debug for UserIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserIdentity")
+            .field("public_key", &self.public_key)
+            .field("private_key", &"<redacted>")
+            .field("signing_key_bytes", &"<redacted>")
+            .field("verifying_key_bytes", &hex::encode(&self.verifying_key_bytes))
+            .field("created_at", &self.created_at)
+            .finish()
+    }
+}
+
+impl UserIdentity {
+    /// Create a new random identity with the default 100-word mnemonic
+    pub fn new() -> Self {
+        Self::new_with_rng(&mut rand::thread_rng())
+    }
+    
+    /// Create a new identity drawing all randomness from `rng`
+    ///
+    /// Meant for deterministic tests with a seeded RNG. Production code must
+    /// use `new`, which draws from the OS-seeded thread RNG.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::random_mnemonic(DEFAULT_MNEMONIC_WORDS, rng)
+    }
+    
+    /// Draw a mnemonic of `word_count` words and derive the keys from it
+    fn random_mnemonic<R: RngCore + CryptoRng>(word_count: usize, rng: &mut R) -> Self {
+        let indices: Vec<u16> = (0..word_count)
+            .map(|_| rng.gen_range(0..WORDLIST.len()) as u16)
+            .collect();
+        Self::from_mnemonic_indices(&indices)
+    }
+    
+    /// Derive the keys and public key padding from a mnemonic
+    fn from_mnemonic_indices(indices: &[u16]) -> Self {
+        let private_key = indices.iter()
+            .map(|&i| WORDLIST[i as usize])
+            .collect::<Vec<_>>()
+            .join(" ");
+        let seed = blake3::derive_key(MNEMONIC_SEED_CONTEXT, private_key.as_bytes());
+        let mut rng = ChaCha20Rng::from_seed(blake3::derive_key(SEED_RNG_CONTEXT, &seed));
+        Self::from_parts(&SigningKey::from_bytes(&seed), private_key, &mut rng)
+    }
+    
+    /// Build an identity around an existing Ed25519 signing key
+    fn from_signing_key<R: Rng>(signing_key: &SigningKey, mnemonic_words: usize, rng: &mut R) -> Self {
+        // Generate public key string, then a mnemonic unrelated to the key
+        let public_key = generate_public_key_string(&signing_key.verifying_key().to_bytes(), rng);
+        let private_key = generate_mnemonic_key(mnemonic_words, rng);
+        Self::with_public_key(signing_key, public_key, private_key)
+    }
+    
+    /// Build an identity from a signing key and its mnemonic
+    fn from_parts<R: Rng>(signing_key: &SigningKey, private_key: String, rng: &mut R) -> Self {
+        let public_key = generate_public_key_string(&signing_key.verifying_key().to_bytes(), rng);
+        Self::with_public_key(signing_key, public_key, private_key)
+    }
+    
+    fn with_public_key(signing_key: &SigningKey, public_key: String, private_key: String) -> Self {
+        let verifying_key = signing_key.verifying_key();
+        let created_at = chrono::Utc::now().timestamp();
+        
+        UserIdentity {
+            public_key,
+            private_key,
+            signing_key_bytes: signing_key.to_bytes().to_vec(),
+            verifying_key_bytes: verifying_key.to_bytes().to_vec(),
+            created_at,
+        }
+    }
+    
+    /// Try random keys until one's hex form starts with `prefix`
+    #[cfg(not(feature = "parallel"))]
+    fn search_vanity(prefix: &str, max_attempts: u64) -> Option<Self> {
+        let mut rng = rand::thread_rng();
+        for _ in 0..max_attempts {
+            let signing_key = generate_signing_key(&mut rng);
+            if hex::encode(signing_key.verifying_key().to_bytes()).starts_with(prefix) {
+                return Some(Self::from_signing_key(&signing_key, DEFAULT_MNEMONIC_WORDS, &mut rng));
+            }
+        }
+        None
+    }
+    
+    /// Split vanity key search across all cores
+    ///
+    /// Attempts are divided evenly between rayon threads, which stop as soon
+    /// as any of them sets the shared "found" flag.
+    #[cfg(feature = "parallel")]
+    fn search_vanity_parallel(prefix: &str, max_attempts: u64) -> Option<Self> {
+        use rayon::prelude::*;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        
+        let threads = rayon::current_num_threads() as u64;
+        let found = AtomicBool::new(false);
+        
+        (0..threads).into_par_iter().find_map_any(|thread| {
+            let mut rng = rand::thread_rng();
+            let attempts = max_attempts / threads + u64::from(thread < max_attempts % threads);
+            for _ in 0..attempts {
+                if found.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let signing_key = generate_signing_key(&mut rng);
+                if hex::encode(signing_key.verifying_key().to_bytes()).starts_with(prefix) {
+                    found.store(true, Ordering::Relaxed);
+                    return Some(Self::from_signing_key(&signing_key, DEFAULT_MNEMONIC_WORDS, &mut rng));
+                }
+            }
+            None
+        })
+    }
+    
+    /// Borrow every field, secrets included, for serialization
+    fn full(&self) -> FullUserIdentity<'_> {
+        FullUserIdentity {
+            public_key: &self.public_key,
+            private_key: &self.private_key,
+            signing_key_bytes: &self.signing_key_bytes,
+            verifying_key_bytes: &self.verifying_key_bytes,
+            created_at: self.created_at,
+        }
+    }
+    
+    /// Reconstruct the Ed25519 signing key
+    pub(crate) fn signing_key(&self) -> PyResult<SigningKey> {
+        let signing_key_bytes: [u8; 32] = self.signing_key_bytes.clone()
+            .try_into()
+            .map_err(|bytes: Vec<u8>| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Signing key must be 32 bytes, got {}",
+                bytes.len()
+            )))?;
+        Ok(SigningKey::from_bytes(&signing_key_bytes))
+    }
+    
+    /// Sign a payload built by this crate, which may carry a domain tag
+    pub(crate) fn sign_payload(&self, payload: &[u8]) -> PyResult<Vec<u8>> {
+        Ok(self.signing_key()?.sign(payload).to_bytes().to_vec())
+    }
+}
+
 impl Default for UserIdentity {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Generate a random Ed25519 signing key
+fn generate_signing_key<R: Rng>(rng: &mut R) -> SigningKey {
+    let mut seed = [0u8; 32];
+    rng.fill(&mut seed);
+    SigningKey::from_bytes(&seed)
+}
+
+/// Check that a vanity prefix can appear at the start of a public key
+fn validate_vanity_prefix(prefix: &str) -> PyResult<()> {
+    if let Some(c) = prefix.bytes().find(|c| !PUBLIC_KEY_CHARS.contains(c)) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Invalid public key character in prefix: {:?}", c as char)
+        ));
+    }
+    
+    // The first 64 characters are always the lowercase hex verifying key
+    if prefix.len() > 64 || !prefix.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Vanity prefix must be at most 64 lowercase hex characters"
+        ));
+    }
+    Ok(())
+}
+
 /// Generate a public key string with ~100+ characters
 fn generate_public_key_string<R: Rng>(verifying_key_bytes: &[u8; 32], rng: &mut R) -> String {
     let mut result = String::with_capacity(120);
@@ -189,9 +648,9 @@ fn generate_public_key_string<R: Rng>(verifying_key_bytes: &[u8; 32], rng: &mut
     result
 }
 
-/// Generate mnemonic private key (100 random words from 4000-word list)
-fn generate_mnemonic_key<R: Rng>(rng: &mut R) -> String {
-    let words: Vec<&str> = (0..100)
+/// Generate mnemonic private key (random words from the wordlist)
+fn generate_mnemonic_key<R: Rng>(word_count: usize, rng: &mut R) -> String {
+    let words: Vec<&str> = (0..word_count)
         .map(|_| {
             let idx = rng.gen_range(0..WORDLIST.len());
             WORDLIST[idx]
@@ -201,6 +660,31 @@ fn generate_mnemonic_key<R: Rng>(rng: &mut R) -> String {
     words.join(" ")
 }
 
+/// Entropy in bits of a mnemonic with `word_count` words
+fn mnemonic_entropy_bits(word_count: usize) -> f64 {
+    word_count as f64 * (WORDLIST.len() as f64).log2()
+}
+
+/// Parse the Ed25519 verifying key embedded at the start of a public key string
+pub(crate) fn verifying_key_from_public_key(public_key: &str) -> PyResult<VerifyingKey> {
+    if !public_key.bytes().all(|b| PUBLIC_KEY_CHARS.contains(&b)) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Public key contains invalid characters"
+        ));
+    }
+    
+    let key_hex = public_key.get(..64)
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Public key too short"))?;
+    
+    let key_bytes: [u8; 32] = hex::decode(key_hex)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid verifying key length"))?;
+    
+    VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
 /// Generate a new identity (Python function)
 #[pyfunction]
 pub fn generate_identity() -> UserIdentity {
@@ -226,6 +710,312 @@ pub fn verify_signature(verifying_key_bytes: Vec<u8>, data: Vec<u8>, signature_b
     Ok(verifying_key.verify(&data, &signature).is_ok())
 }
 
+/// Sign data with a hex Ed25519 private key, returning a hex signature
+///
+/// Uses the same hex conventions as the rootlessnet-tui crate: a 32-byte
+/// private key seed in, a 64-byte signature out.
+#[pyfunction]
+pub fn sign_hex(private_key_hex: &str, data: Vec<u8>) -> PyResult<String> {
+    let seed: [u8; 32] = decode_hex_array(private_key_hex, "private key")?;
+    check_untagged(&data)?;
+    Ok(hex::encode(SigningKey::from_bytes(&seed).sign(&data).to_bytes()))
+}
+
+/// Verify a hex signature against a hex Ed25519 public key
+///
+/// Malformed keys or signatures raise ValueError; a well-formed signature
+/// that does not match returns False.
+#[pyfunction]
+pub fn verify_hex(public_key_hex: &str, data: Vec<u8>, sig_hex: &str) -> PyResult<bool> {
+    let key_bytes: [u8; 32] = decode_hex_array(public_key_hex, "public key")?;
+    let signature_bytes: [u8; 64] = decode_hex_array(sig_hex, "signature")?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    
+    Ok(verifying_key.verify(&data, &Signature::from_bytes(&signature_bytes)).is_ok())
+}
+
+/// Refuse raw data that starts with a domain tag
+///
+/// Otherwise a raw signature could stand in for a content, message, block
+/// or checkpoint signature.
+fn check_untagged(data: &[u8]) -> PyResult<()> {
+    if data.starts_with(DOMAIN_PREFIX.as_bytes()) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Refusing to sign raw data starting with {:?}; it is reserved for domain tags",
+            DOMAIN_PREFIX
+        )));
+    }
+    Ok(())
+}
+
+/// Decode hex of exactly `N` bytes, naming `what` in the error
+fn decode_hex_array<const N: usize>(value: &str, what: &str) -> PyResult<[u8; N]> {
+    let bytes = hex::decode(value)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Invalid {} hex: {}", what, e)
+        ))?;
+    let len = bytes.len();
+    bytes.try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid {} length: expected {} bytes, got {}",
+            what, N, len
+        )))
+}
+
+/// Verify an Ed25519ph signature made by `UserIdentity::sign_prehashed`
+#[pyfunction]
+pub fn verify_prehashed(verifying_key_bytes: Vec<u8>, hash: [u8; 64], signature_bytes: Vec<u8>) -> PyResult<bool> {
+    let verifying_key_array: [u8; 32] = verifying_key_bytes
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid verifying key length"))?;
+    
+    let signature_array: [u8; 64] = signature_bytes
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid signature length"))?;
+    
+    let verifying_key = VerifyingKey::from_bytes(&verifying_key_array)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    
+    let signature = Signature::from_bytes(&signature_array);
+    
+    Ok(verifying_key
+        .verify_prehashed(Sha512Prehash::from_digest(hash), Some(PREHASH_CONTEXT), &signature)
+        .is_ok())
+}
+
+/// An already-computed SHA-512 digest, in the shape ed25519-dalek expects
+///
+/// The prehashed APIs take a hasher and only call `finalize`. This one
+/// buffers whatever it is fed and hands it back as the digest, truncated
+/// or zero-padded to 64 bytes; `from_digest` feeds it exactly one digest.
+#[derive(Clone, Default)]
+struct Sha512Prehash(Vec<u8>);
+
+impl Sha512Prehash {
+    fn from_digest(hash: [u8; 64]) -> Self {
+        Sha512Prehash(hash.to_vec())
+    }
+}
+
+impl HashMarker for Sha512Prehash {}
+
+impl OutputSizeUser for Sha512Prehash {
+    type OutputSize = U64;
+}
+
+impl Update for Sha512Prehash {
+    fn update(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+}
+
+impl FixedOutput for Sha512Prehash {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        let len = self.0.len().min(out.len());
+        out[..len].copy_from_slice(&self.0[..len]);
+    }
+}
+
+/// Verify a detached hex signature using only a shareable public key string
+#[pyfunction]
+pub fn verify_detached(public_key: &str, data: Vec<u8>, signature_hex: &str) -> PyResult<bool> {
+    let verifying_key = verifying_key_from_public_key(public_key)?;
+    
+    let signature_array: [u8; 64] = hex::decode(signature_hex)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid signature length"))?;
+    
+    let signature = Signature::from_bytes(&signature_array);
+    
+    Ok(verifying_key.verify(&data, &signature).is_ok())
+}
+
+/// Verify a signature, rejecting small-order keys and non-canonical encodings
+///
+/// `verify_signature` follows the lenient RFC 8032 rules, under which a
+/// small-order public key can "verify" signatures it never made. Use this
+/// variant for keys and signatures from untrusted sources.
+#[pyfunction]
+pub fn verify_signature_strict(verifying_key_bytes: Vec<u8>, data: Vec<u8>, signature_bytes: Vec<u8>) -> PyResult<bool> {
+    let verifying_key = match strict_verifying_key(&verifying_key_bytes) {
+        Ok(key) => key,
+        Err(_) if verifying_key_bytes.len() == 32 => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    
+    let signature_array: [u8; 64] = signature_bytes
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid signature length"))?;
+    
+    let signature = Signature::from_bytes(&signature_array);
+    
+    Ok(verifying_key.verify_strict(&data, &signature).is_ok())
+}
+
+/// Parse verifying key bytes, rejecting small-order (weak) points
+fn strict_verifying_key(verifying_key_bytes: &[u8]) -> PyResult<VerifyingKey> {
+    let verifying_key_array: [u8; 32] = verifying_key_bytes
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid verifying key length"))?;
+    
+    let verifying_key = VerifyingKey::from_bytes(&verifying_key_array)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    
+    if verifying_key.is_weak() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Verifying key has small order"));
+    }
+    Ok(verifying_key)
+}
+
+/// Check a backup password without decrypting the identity
+///
+/// The tag comparison is constant-time. Older backups without a tag fall
+/// back to a trial decryption.
+#[pyfunction]
+pub fn verify_backup_password(encrypted_hex: &str, password: &str) -> bool {
+    let Ok(data) = hex::decode(encrypted_hex) else {
+        return false;
+    };
+    
+    match split_backup_check(&data) {
+        Some((check, _)) => backup_check_matches(&backup_key(password), check),
+        None => UserIdentity::import_encrypted(encrypted_hex, password).is_ok(),
+    }
+}
+
+/// Backup encryption key derived from a password
+fn backup_key(password: &str) -> [u8; 32] {
+    use sha2::{Sha256, Digest};
+    
+    Sha256::digest(password.as_bytes()).into()
+}
+
+/// Password check tag stored alongside a backup
+fn backup_password_check(key: &[u8; 32]) -> blake3::Hash {
+    blake3::keyed_hash(key, BACKUP_CHECK_CONSTANT)
+}
+
+/// Compare a stored check tag in constant time
+fn backup_check_matches(key: &[u8; 32], check: &[u8; 32]) -> bool {
+    backup_password_check(key) == blake3::Hash::from_bytes(*check)
+}
+
+/// Split a tagged backup into its check tag and `nonce || ciphertext`
+fn split_backup_check(data: &[u8]) -> Option<(&[u8; 32], &[u8])> {
+    let rest = data.strip_prefix(BACKUP_MAGIC.as_slice())?;
+    if rest.len() < 32 + 12 {
+        return None;
+    }
+    let (check, rest) = rest.split_at(32);
+    Some((check.try_into().ok()?, rest))
+}
+
+/// Rough password strength score from 0 (trivial) to 4 (strong)
+///
+/// Estimates entropy from length and character classes, discounting repeated
+/// characters and ascending/descending runs such as `aaaa` or `4321`. Common
+/// passwords score 0.
+#[pyfunction]
+pub fn estimate_password_strength(password: &str) -> u8 {
+    if password.is_empty() || COMMON_PASSWORDS.contains(&password.to_lowercase().as_str()) {
+        return 0;
+    }
+    
+    let mut pool = 0u32;
+    if password.chars().any(|c| c.is_ascii_lowercase()) { pool += 26; }
+    if password.chars().any(|c| c.is_ascii_uppercase()) { pool += 26; }
+    if password.chars().any(|c| c.is_ascii_digit()) { pool += 10; }
+    if password.chars().any(|c| c.is_ascii() && !c.is_ascii_alphanumeric()) { pool += 33; }
+    if !password.is_ascii() { pool += 100; }
+    
+    // Characters that repeat or continue a run add almost nothing
+    let chars: Vec<u32> = password.chars().map(u32::from).collect();
+    let effective_len = 1 + chars.windows(2)
+        .filter(|w| w[1] != w[0] && w[1] != w[0] + 1 && w[1] + 1 != w[0])
+        .count();
+    
+    let bits = effective_len as f64 * f64::from(pool).log2();
+    match bits {
+        b if b < 28.0 => 0,
+        b if b < 36.0 => 1,
+        b if b < 60.0 => 2,
+        b if b < 80.0 => 3,
+        _ => 4,
+    }
+}
+
+/// Position of each word in `WORDLIST`
+fn word_indices() -> &'static HashMap<&'static str, u16> {
+    static INDICES: OnceLock<HashMap<&'static str, u16>> = OnceLock::new();
+    INDICES.get_or_init(|| WORDLIST.iter().enumerate().map(|(i, &w)| (w, i as u16)).collect())
+}
+
+/// Wordlist as a set for fast membership checks
+fn wordlist_set() -> &'static HashSet<&'static str> {
+    static SET: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    SET.get_or_init(|| WORDLIST.iter().copied().collect())
+}
+
+/// Check a typed-back mnemonic, returning the positions of unknown words
+#[pyfunction]
+pub fn verify_mnemonic(words: &str) -> PyResult<Vec<usize>> {
+    let words: Vec<String> = words.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if words.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Mnemonic is empty"));
+    }
+    
+    let known = wordlist_set();
+    Ok(words.iter()
+        .enumerate()
+        .filter(|(_, word)| !known.contains(word.as_str()))
+        .map(|(i, _)| i)
+        .collect())
+}
+
+/// Suggest wordlist entries starting with `prefix` (for autocomplete)
+#[pyfunction]
+pub fn suggest_word(prefix: &str) -> Vec<String> {
+    let prefix = prefix.trim().to_lowercase();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    
+    let mut suggestions: Vec<String> = WORDLIST.iter()
+        .filter(|w| w.starts_with(&prefix))
+        .map(|w| w.to_string())
+        .collect();
+    suggestions.sort();
+    suggestions.dedup();
+    suggestions
+}
+
+/// Verify a login challenge answer from `UserIdentity::answer_challenge`
+#[pyfunction]
+pub fn verify_challenge(public_key: &str, nonce: Vec<u8>, signature_bytes: Vec<u8>) -> PyResult<bool> {
+    let verifying_key = verifying_key_from_public_key(public_key)?;
+    verify_signature(verifying_key.to_bytes().to_vec(), challenge_payload(&nonce), signature_bytes)
+}
+
+/// Domain-separated bytes signed for a login challenge
+fn challenge_payload(nonce: &[u8]) -> Vec<u8> {
+    domain_separated(CHALLENGE_DOMAIN, nonce)
+}
+
+/// Verify a signature made with `UserIdentity::sign_content`
+#[pyfunction]
+pub fn verify_content_signature(verifying_key_bytes: Vec<u8>, content: &Content, signature_bytes: Vec<u8>) -> PyResult<bool> {
+    verify_signature(verifying_key_bytes, domain_separated(CONTENT_DOMAIN, &content.signing_bytes()), signature_bytes)
+}
+
+/// Verify a signature made with `UserIdentity::sign_message`
+#[pyfunction]
+pub fn verify_message_signature(public_key: &str, data: Vec<u8>, signature_bytes: Vec<u8>) -> PyResult<bool> {
+    let verifying_key = verifying_key_from_public_key(public_key)?;
+    verify_signature(verifying_key.to_bytes().to_vec(), domain_separated(MESSAGE_DOMAIN, &data), signature_bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,16 +1042,457 @@ mod tests {
         
         assert!(is_valid);
     }
+    
+    #[test]
+    fn test_raw_sign_refuses_domain_tagged_data() {
+        let identity = UserIdentity::new();
+        let content = Content::text("Title".to_string(), String::new(), "Body".to_string());
+        let tagged = domain_separated(CONTENT_DOMAIN, &content.signing_bytes());
+        
+        assert!(identity.sign(&tagged).is_err());
+        assert!(identity.sign_detached(tagged).is_err());
+        assert!(identity.sign(b"rootless").is_ok());
+        assert!(identity.sign_content(&content).is_ok());
+    }
+    
+    #[test]
+    fn test_detached_signature_over_file() {
+        let identity = UserIdentity::new();
+        let path = std::env::temp_dir().join(format!("rootless-detached-{}.bin", std::process::id()));
+        std::fs::write(&path, b"file contents on disk").unwrap();
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        let signature = identity.sign_detached(data.clone()).unwrap();
+        
+        assert!(verify_detached(&identity.public_key, data.clone(), &signature).unwrap());
+        assert!(!verify_detached(&identity.public_key, b"other".to_vec(), &signature).unwrap());
+        assert!(!verify_detached(&UserIdentity::new().public_key, data.clone(), &signature).unwrap());
+        assert!(verify_detached(&identity.public_key, data, "zz").is_err());
+    }
+    
+    #[test]
+    fn test_verifying_key_from_public_round_trip() {
+        let identity = UserIdentity::new();
+        
+        let recovered = UserIdentity::verifying_key_from_public(&identity.public_key).unwrap();
+        assert_eq!(recovered, identity.verifying_key_bytes);
+        
+        assert!(UserIdentity::verifying_key_from_public("abcd").is_err());
+        assert!(UserIdentity::verifying_key_from_public(&"zz".repeat(50)).is_err());
+        assert!(UserIdentity::verifying_key_from_public(&format!("{} spaces", identity.public_key)).is_err());
+    }
+    
+    #[test]
+    fn test_from_seed_is_deterministic() {
+        let first = UserIdentity::from_seed([7u8; 32]);
+        let second = UserIdentity::from_seed([7u8; 32]);
+        let other = UserIdentity::from_seed([8u8; 32]);
+        
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.private_key, second.private_key);
+        assert_eq!(first.verifying_key_bytes, second.verifying_key_bytes);
+        assert_ne!(first.public_key, other.public_key);
+        assert_eq!(first.sign(b"data").unwrap(), second.sign(b"data").unwrap());
+    }
+    
+    #[test]
+    fn test_strict_verification_rejects_small_order_key() {
+        // Identity point as the key, R = identity and S = 0: the lenient
+        // equation holds for every message, the strict check does not
+        let mut small_order_key = vec![0u8; 32];
+        small_order_key[0] = 1;
+        let mut forged_signature = vec![0u8; 64];
+        forged_signature[0] = 1;
+        
+        assert!(verify_signature(small_order_key.clone(), b"any message".to_vec(), forged_signature.clone()).unwrap());
+        assert!(!verify_signature_strict(small_order_key, b"any message".to_vec(), forged_signature).unwrap());
+        
+        let identity = UserIdentity::new();
+        let signature = identity.sign(b"data").unwrap();
+        assert!(verify_signature_strict(identity.verifying_key_bytes.clone(), b"data".to_vec(), signature).unwrap());
+    }
+    
+    #[test]
+    fn test_import_rejects_small_order_key() {
+        let mut identity = UserIdentity::new();
+        identity.verifying_key_bytes = vec![0u8; 32];
+        identity.verifying_key_bytes[0] = 1;
+        
+        let exported = identity.export_encrypted("password", 0).unwrap();
+        assert!(UserIdentity::import_encrypted(&exported, "password").is_err());
+    }
+    
+    #[test]
+    fn test_password_strength() {
+        assert_eq!(estimate_password_strength("123456"), 0);
+        assert_eq!(estimate_password_strength("Password"), 0);
+        assert_eq!(estimate_password_strength("aaaaaaaaaaaa"), 0);
+        assert_eq!(estimate_password_strength("abcdefghijkl"), 0);
+        assert!(estimate_password_strength("Tr0ub4dor&3") >= 2);
+        assert_eq!(estimate_password_strength("correct horse battery staple galaxy"), 4);
+    }
+    
+    #[test]
+    fn test_export_rejects_weak_password() {
+        let identity = UserIdentity::new();
+        assert!(identity.export_encrypted("123456", 3).is_err());
+        assert!(identity.export_encrypted("123456", 0).is_ok());
+        assert!(identity.export_encrypted("correct horse battery staple galaxy", 3).is_ok());
+    }
+    
+    #[test]
+    fn test_verify_backup_password() {
+        let identity = UserIdentity::new();
+        let backup = identity.export_encrypted("correct horse", 0).unwrap();
+        
+        let start = std::time::Instant::now();
+        assert!(verify_backup_password(&backup, "correct horse"));
+        assert!(!verify_backup_password(&backup, "wrong horse"));
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        
+        assert!(!verify_backup_password("not hex", "correct horse"));
+        assert!(UserIdentity::import_encrypted(&backup, "wrong horse").is_err());
+    }
+    
+    #[test]
+    fn test_import_legacy_backup_without_check_tag() {
+        use chacha20poly1305::{ChaCha20Poly1305, KeyInit, aead::Aead};
+        use chacha20poly1305::aead::generic_array::GenericArray;
+        
+        let identity = UserIdentity::new();
+        let cipher = ChaCha20Poly1305::new(GenericArray::from_slice(&backup_key("pw")));
+        let nonce = [3u8; 12];
+        let data = serde_json::to_string(&identity.full()).unwrap();
+        let mut legacy = nonce.to_vec();
+        legacy.extend(cipher.encrypt(GenericArray::from_slice(&nonce), data.as_bytes()).unwrap());
+        let legacy = hex::encode(legacy);
+        
+        assert_eq!(UserIdentity::import_encrypted(&legacy, "pw").unwrap().public_key, identity.public_key);
+        assert!(verify_backup_password(&legacy, "pw"));
+        assert!(!verify_backup_password(&legacy, "other"));
+    }
+    
+    #[test]
+    fn test_fingerprint_is_deterministic_and_distinct() {
+        let identity = UserIdentity::from_seed([1u8; 32]);
+        assert_eq!(identity.fingerprint(), UserIdentity::from_seed([1u8; 32]).fingerprint());
+        assert!(identity.fingerprint().len() <= 11);
+        
+        let mut seen = HashSet::new();
+        for i in 0..1000u32 {
+            let mut seed = [0u8; 32];
+            seed[..4].copy_from_slice(&i.to_le_bytes());
+            assert!(seen.insert(UserIdentity::from_seed(seed).fingerprint()));
+        }
+    }
 
     #[test]
     fn test_export_import() {
         let identity = UserIdentity::new();
         let password = "test_password_123";
         
-        let encrypted = identity.export_encrypted(password).unwrap();
+        let encrypted = identity.export_encrypted(password, 0).unwrap();
         let imported = UserIdentity::import_encrypted(&encrypted, password).unwrap();
         
         assert_eq!(identity.public_key, imported.public_key);
         assert_eq!(identity.private_key, imported.private_key);
     }
+
+    #[test]
+    fn test_default_output_redacts_private_key() {
+        let identity = UserIdentity::new();
+        let first_words = identity.private_key.split_whitespace().take(3).collect::<Vec<_>>().join(" ");
+        let signing_key_hex = hex::encode(&identity.signing_key_bytes);
+        
+        let debug = format!("{:?}", identity);
+        let json = identity.to_json().unwrap();
+        let serialized = serde_json::to_string(&identity).unwrap();
+        
+        for output in [&debug, &json] {
+            assert!(!output.contains(&identity.private_key));
+            assert!(!output.contains(&first_words));
+            assert!(!output.contains(&signing_key_hex));
+            assert!(!output.contains("signing_key_bytes\":"));
+        }
+        // `serialize-secrets` opts the default serde output into the keys
+        let secrets = cfg!(feature = "serialize-secrets");
+        assert_eq!(serialized.contains(&identity.private_key), secrets);
+        assert_eq!(serialized.contains("signing_key_bytes\":"), secrets);
+        assert!(identity.to_full_json().unwrap().contains(&identity.private_key));
+    }
+
+    #[test]
+    fn test_generate_vanity_single_char() {
+        let identity = UserIdentity::generate_vanity("a", 10_000).unwrap().unwrap();
+        
+        assert!(identity.public_key.starts_with('a'));
+        assert!(identity.public_key.len() >= 100);
+        
+        let signature = identity.sign(b"vanity").unwrap();
+        assert!(verify_signature(identity.verifying_key_bytes.clone(), b"vanity".to_vec(), signature).unwrap());
+    }
+
+    #[test]
+    fn test_generate_vanity_gives_up() {
+        assert!(UserIdentity::generate_vanity("abcdef01", 10).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_generate_vanity_rejects_invalid_prefix() {
+        assert!(UserIdentity::generate_vanity("!", 10).is_err());
+        assert!(UserIdentity::generate_vanity("Z", 10).is_err());
+        assert!(UserIdentity::generate_vanity("$", 10).is_err());
+    }
+
+    #[test]
+    fn test_sign_content_ignores_tag_order() {
+        let identity = UserIdentity::new();
+        let mut first = Content::text(
+            "Title".to_string(),
+            "Description".to_string(),
+            "Body".to_string(),
+        );
+        let mut second = first.clone();
+        
+        first.add_tag("rust".to_string());
+        first.add_tag("blockchain".to_string());
+        second.add_tag("blockchain".to_string());
+        second.add_tag("rust".to_string());
+        second.size += 1;
+        
+        assert_eq!(first.signing_bytes(), second.signing_bytes());
+        
+        let signature = identity.sign_content(&first).unwrap();
+        assert_eq!(signature, identity.sign_content(&second).unwrap());
+        assert!(verify_content_signature(identity.verifying_key_bytes.clone(), &second, signature.clone()).unwrap());
+        
+        second.data = "Tampered".to_string();
+        assert!(!verify_content_signature(identity.verifying_key_bytes.clone(), &second, signature).unwrap());
+    }
+
+    #[test]
+    fn test_verify_mnemonic_flags_misspelled_word() {
+        let identity = UserIdentity::new();
+        assert!(verify_mnemonic(&identity.private_key).unwrap().is_empty());
+        
+        let mut words: Vec<&str> = identity.private_key.split_whitespace().collect();
+        words[42] = "abandonn";
+        assert_eq!(verify_mnemonic(&words.join(" ")).unwrap(), vec![42]);
+        
+        assert!(verify_mnemonic("   ").is_err());
+    }
+
+    #[test]
+    fn test_suggest_word() {
+        let suggestions = suggest_word("aban");
+        assert_eq!(suggestions, vec!["abandon".to_string()]);
+        
+        let suggestions = suggest_word("zo");
+        assert!(suggestions.contains(&"zone".to_string()));
+        assert!(suggestions.windows(2).all(|w| w[0] < w[1]));
+        
+        assert!(suggest_word("").is_empty());
+        assert!(suggest_word("qqq").is_empty());
+    }
+
+    #[test]
+    fn test_mnemonic_entropy() {
+        // log2(4000) ~= 11.966 bits per word
+        assert!((mnemonic_entropy_bits(12) - 143.59).abs() < 0.01);
+        assert!((mnemonic_entropy_bits(100) - 1196.58).abs() < 0.01);
+        
+        let identity = UserIdentity::with_mnemonic_words(12).unwrap();
+        assert_eq!(identity.private_key.split_whitespace().count(), 12);
+        assert!((identity.entropy_bits() - mnemonic_entropy_bits(12)).abs() < 1e-9);
+        assert!(identity.entropy_bits() >= 128.0);
+        
+        assert!((UserIdentity::new().entropy_bits() - mnemonic_entropy_bits(100)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_short_mnemonic_rejected() {
+        assert!(UserIdentity::with_mnemonic_words(10).is_err());
+        assert!(UserIdentity::with_mnemonic_words(0).is_err());
+    }
+    
+    #[test]
+    fn test_sign_prehashed_large_input() {
+        use sha2::{Digest, Sha512};
+        
+        let identity = UserIdentity::new();
+        let chunk = [0x5au8; 64 * 1024];
+        let mut hasher = Sha512::new();
+        for _ in 0..128 {
+            Digest::update(&mut hasher, chunk);
+        }
+        let hash: [u8; 64] = hasher.clone().finalize().into();
+        
+        let signature = identity.sign_prehashed(hash).unwrap();
+        assert!(verify_prehashed(identity.verifying_key_bytes.clone(), hash, signature.clone()).unwrap());
+        
+        // Interoperates with dalek's own streaming-digest verifier
+        let verifying_key = identity.signing_key().unwrap().verifying_key();
+        let signature = Signature::from_bytes(&signature.try_into().unwrap());
+        assert!(verifying_key.verify_prehashed(hasher, Some(PREHASH_CONTEXT), &signature).is_ok());
+        
+        // Context separation: not a plain signature over the digest, and the
+        // digest of different data does not verify
+        assert!(verifying_key.verify(&hash, &signature).is_err());
+        let mut other = hash;
+        other[0] ^= 1;
+        assert!(!verify_prehashed(identity.verifying_key_bytes.clone(), other, signature.to_bytes().to_vec()).unwrap());
+        
+        // The digest wrapper buffers input rather than panicking on it
+        let mut fed = Sha512Prehash::default();
+        Digest::update(&mut fed, &hash[..10]);
+        Digest::update(&mut fed, &hash[10..]);
+        assert_eq!(Digest::finalize(fed)[..], hash[..]);
+    }
+    
+    #[test]
+    fn test_challenge_and_content_signatures_do_not_mix() {
+        let identity = UserIdentity::new();
+        let content = Content::text(
+            "Title".to_string(),
+            "Desc".to_string(),
+            "Hello".to_string(),
+        );
+        let nonce = b"relay-nonce-1234".to_vec();
+        
+        let answer = identity.answer_challenge(nonce.clone()).unwrap();
+        assert!(verify_challenge(&identity.public_key, nonce.clone(), answer.clone()).unwrap());
+        assert!(!verify_challenge(&identity.public_key, b"other".to_vec(), answer.clone()).unwrap());
+        assert!(!verify_challenge(&UserIdentity::new().public_key, nonce.clone(), answer.clone()).unwrap());
+        
+        // A challenge answer is not a content signature, even when the relay
+        // picks the content bytes as its nonce
+        let content_nonce = content.signing_bytes();
+        let answer = identity.answer_challenge(content_nonce.clone()).unwrap();
+        assert!(!verify_content_signature(identity.verifying_key_bytes.clone(), &content, answer).unwrap());
+        
+        // ...and a content signature is not a challenge answer
+        let content_signature = identity.sign_content(&content).unwrap();
+        assert!(!verify_challenge(&identity.public_key, content_nonce, content_signature).unwrap());
+    }
+    
+    #[test]
+    fn test_seeded_rng_gives_reproducible_identity() {
+        let first = UserIdentity::new_with_rng(&mut ChaCha20Rng::seed_from_u64(42));
+        let second = UserIdentity::new_with_rng(&mut ChaCha20Rng::seed_from_u64(42));
+        let other = UserIdentity::new_with_rng(&mut ChaCha20Rng::seed_from_u64(43));
+        
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.private_key, second.private_key);
+        assert_eq!(first.signing_key_bytes, second.signing_key_bytes);
+        assert_ne!(first.signing_key_bytes, other.signing_key_bytes);
+    }
+
+    #[test]
+    fn test_sign_hex_verifies_with_verify_hex() {
+        let identity = UserIdentity::new();
+        let private_hex = hex::encode(&identity.signing_key_bytes);
+        let public_hex = hex::encode(&identity.verifying_key_bytes);
+        
+        let signature = sign_hex(&private_hex, b"hello".to_vec()).unwrap();
+        assert!(verify_hex(&public_hex, b"hello".to_vec(), &signature).unwrap());
+        assert!(!verify_hex(&public_hex, b"other".to_vec(), &signature).unwrap());
+        assert_eq!(signature, hex::encode(identity.sign(b"hello").unwrap()));
+        
+        assert!(sign_hex(&private_hex[..62], b"hello".to_vec()).is_err());
+        assert!(sign_hex("zz", b"hello".to_vec()).is_err());
+        assert!(sign_hex(&private_hex, domain_separated(CONTENT_DOMAIN, b"hello")).is_err());
+        assert!(verify_hex(&public_hex, b"hello".to_vec(), &signature[..126]).is_err());
+    }
+
+    #[test]
+    fn test_content_signature_fails_under_message_domain() {
+        let identity = UserIdentity::new();
+        let content = Content::text("Title".to_string(), "Desc".to_string(), "Hello".to_string());
+        let signature = identity.sign_content(&content).unwrap();
+        
+        assert!(verify_content_signature(identity.verifying_key_bytes.clone(), &content, signature.clone()).unwrap());
+        assert!(!verify_message_signature(&identity.public_key, content.signing_bytes(), signature.clone()).unwrap());
+        assert!(!verify_signature(identity.verifying_key_bytes.clone(), content.signing_bytes(), signature).unwrap());
+        
+        let message_signature = identity.sign_message(b"hi".to_vec()).unwrap();
+        assert!(verify_message_signature(&identity.public_key, b"hi".to_vec(), message_signature.clone()).unwrap());
+        assert!(!verify_challenge(&identity.public_key, b"hi".to_vec(), message_signature).unwrap());
+    }
+
+    #[test]
+    fn test_public_json_round_trip_without_secrets() {
+        let identity = UserIdentity::new();
+        let json = identity.public_json().unwrap();
+        
+        let words: Vec<&str> = identity.private_key.split_whitespace().collect();
+        for window in words.windows(2) {
+            assert!(!json.contains(&window.join(" ")));
+        }
+        assert!(!json.contains(&hex::encode(&identity.signing_key_bytes)));
+        assert!(!json.contains(&bs58::encode(&identity.signing_key_bytes).into_string()));
+        
+        let profile = PublicIdentity::from_public_json(&json).unwrap();
+        assert_eq!(profile.public_key, identity.public_key);
+        assert_eq!(profile.created_at, identity.created_at);
+        assert_eq!(profile.fingerprint, identity.fingerprint());
+        assert_eq!(profile.to_json().unwrap(), json);
+        
+        let forged = json.replace(&identity.fingerprint(), &UserIdentity::new().fingerprint());
+        assert!(PublicIdentity::from_public_json(&forged).is_err());
+        
+        // Every public serialization is the same profile
+        assert_eq!(identity.to_public_json().unwrap(), json);
+        assert_eq!(identity.to_json().unwrap(), json);
+        assert_eq!(PublicIdentity::from_public_json(&identity.to_public_json().unwrap()).unwrap(), profile);
+    }
+
+    #[test]
+    fn test_from_indices_reproduces_identity() {
+        let identity = UserIdentity::new();
+        let indices = identity.mnemonic_indices().unwrap();
+        assert_eq!(indices.len(), DEFAULT_MNEMONIC_WORDS);
+        
+        let restored = UserIdentity::from_indices(indices).unwrap();
+        assert_eq!(restored.public_key, identity.public_key);
+        assert_eq!(restored.private_key, identity.private_key);
+        assert_eq!(restored.signing_key_bytes, identity.signing_key_bytes);
+        
+        let short = UserIdentity::with_mnemonic_words(12).unwrap();
+        assert_eq!(UserIdentity::from_indices(short.mnemonic_indices().unwrap()).unwrap().public_key, short.public_key);
+        
+        assert!(UserIdentity::from_indices(vec![WORDLIST.len() as u16; 12]).is_err());
+        assert!(UserIdentity::from_indices(vec![0; 3]).is_err());
+        
+        // A seeded identity's mnemonic is unrelated to its key, so it has no index backup
+        let seeded = UserIdentity::from_seed([5u8; 32]);
+        let err = seeded.mnemonic_indices().unwrap_err();
+        assert!(err.to_string().contains("to_full_json"));
+    }
+
+    #[test]
+    fn test_validate_rejects_tampered_identity() {
+        let identity = UserIdentity::new();
+        assert!(identity.validate().is_ok());
+        assert!(UserIdentity::from_json(&identity.to_full_json().unwrap()).is_ok());
+        
+        let mut swapped = identity.clone();
+        swapped.signing_key_bytes = UserIdentity::new().signing_key_bytes;
+        assert!(swapped.validate().is_err());
+        assert!(UserIdentity::from_json(&swapped.to_full_json().unwrap()).is_err());
+        
+        let mut truncated = identity.clone();
+        truncated.signing_key_bytes.pop();
+        assert!(truncated.validate().is_err());
+        assert!(truncated.sign(b"data").is_err());
+        
+        let mut relabelled = identity.clone();
+        relabelled.public_key = UserIdentity::new().public_key;
+        assert!(relabelled.validate().is_err());
+        
+        let backup = identity.export_encrypted("correct horse battery", 0).unwrap();
+        assert!(UserIdentity::import_encrypted(&backup, "correct horse battery").is_ok());
+        let backup = swapped.export_encrypted("correct horse battery", 0).unwrap();
+        assert!(UserIdentity::import_encrypted(&backup, "correct horse battery").is_err());
+    }
 }
@@ -4,7 +4,11 @@
 pub mod identity;
 pub mod blockchain;
 pub mod content;
+pub mod contacts;
 pub mod crypto;
+pub mod schema;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
 pub mod wordlist;
 
 use pyo3::prelude::*;
@@ -13,13 +17,49 @@ use pyo3::prelude::*;
 #[pymodule]
 fn rootless_core(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<identity::UserIdentity>()?;
+    m.add_class::<identity::PublicIdentity>()?;
     m.add_class::<blockchain::Block>()?;
+    m.add_class::<blockchain::BlockHeader>()?;
     m.add_class::<blockchain::Blockchain>()?;
+    m.add_class::<blockchain::SharedBlockchain>()?;
+    m.add_class::<blockchain::MmapBlockchain>()?;
+    m.add_class::<blockchain::MiningStatus>()?;
+    m.add_class::<blockchain::HashAlgo>()?;
+    m.add_class::<blockchain::MiningCancelToken>()?;
+    m.add_class::<blockchain::ChainStats>()?;
+    m.add_class::<blockchain::Attestation>()?;
+    m.add_class::<blockchain::Checkpoint>()?;
     m.add_class::<content::Content>()?;
-    m.add_class::<content::ContentType>()?;
+    m.add_class::<content::ContentBuilder>()?;
+    m.add_class::<content::MultiContent>()?;
+    m.add_class::<contacts::ContactBook>()?;
+    m.add_class::<content::ContentKind>()?;
+    m.add_class::<contacts::ContactConflict>()?;
     m.add_function(wrap_pyfunction!(identity::generate_identity, m)?)?;
     m.add_function(wrap_pyfunction!(identity::verify_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_signature_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::sign_hex, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_hex, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::estimate_password_strength, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_backup_password, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_detached, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_prehashed, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_challenge, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_content_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_message_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::verify_mnemonic, m)?)?;
+    m.add_function(wrap_pyfunction!(identity::suggest_word, m)?)?;
     m.add_function(wrap_pyfunction!(crypto::hash_data, m)?)?;
+    m.add_function(wrap_pyfunction!(blockchain::verify_pow, m)?)?;
+    m.add_function(wrap_pyfunction!(content::detect_mime, m)?)?;
+    m.add_function(wrap_pyfunction!(content::normalize_base64, m)?)?;
+    m.add_function(wrap_pyfunction!(content::parse_rfc3339, m)?)?;
+    m.add_function(wrap_pyfunction!(crypto::hash_sha512, m)?)?;
+    m.add_function(wrap_pyfunction!(crypto::hash_sha3_256, m)?)?;
+    m.add_function(wrap_pyfunction!(crypto::content_id, m)?)?;
+    m.add_function(wrap_pyfunction!(crypto::parse_cid, m)?)?;
+    m.add_function(wrap_pyfunction!(wordlist::wordlist_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(wordlist::wordlist_len, m)?)?;
     Ok(())
 }
 
@@ -50,7 +90,7 @@ mod tests {
             None,
         );
         
-        chain.add_block(content, "test_author".to_string());
+        chain.add_block(content, "test_author".to_string()).unwrap();
         assert_eq!(chain.chain.len(), 2);
     }
 }
@@ -0,0 +1,86 @@
+//! Schema Module
+//! Version markers and migrations for serialized data
+
+use pyo3::prelude::*;
+use serde_json::Value;
+
+/// Schema version written by this build
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Version assumed for data serialized before the field existed
+pub fn default_schema_version() -> u32 {
+    1
+}
+
+/// Bring a serialized object up to `SCHEMA_VERSION`
+///
+/// A missing version is treated as 1. Newer versions are rejected rather
+/// than partially parsed.
+pub fn upgrade(value: &mut Value, kind: &str) -> PyResult<()> {
+    let object = value.as_object_mut()
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("{} must be a JSON object", kind)
+        ))?;
+    
+    let mut version = match object.get("schema_version") {
+        None => default_schema_version(),
+        Some(v) => v.as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("{} has an invalid schema_version", kind)
+            ))?,
+    };
+    
+    if version < 1 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("{} has an invalid schema_version", kind)
+        ));
+    }
+    if version > SCHEMA_VERSION {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} uses schema version {}, but this build only understands up to {}",
+            kind, version, SCHEMA_VERSION
+        )));
+    }
+    
+    while version < SCHEMA_VERSION {
+        match version {
+            1 => migrate_v1_to_v2(value),
+            _ => unreachable!("no migration from schema version {}", version),
+        }
+        version += 1;
+    }
+    
+    value["schema_version"] = Value::from(SCHEMA_VERSION);
+    Ok(())
+}
+
+/// Template for the first migration; rewrite fields here when version 2 lands
+fn migrate_v1_to_v2(_value: &mut Value) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_version_defaults_to_current() {
+        let mut value = serde_json::json!({"title": "old"});
+        upgrade(&mut value, "Content").unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_future_version_is_rejected() {
+        let mut value = serde_json::json!({"schema_version": SCHEMA_VERSION + 1});
+        assert!(upgrade(&mut value, "Content").is_err());
+        
+        let mut value = serde_json::json!({"schema_version": "one"});
+        assert!(upgrade(&mut value, "Content").is_err());
+    }
+
+    #[test]
+    fn test_version_zero_is_rejected() {
+        let mut value = serde_json::json!({"schema_version": 0});
+        assert!(upgrade(&mut value, "Content").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Test Vectors Module
+//! Reproducible golden vectors for checking other implementations
+//!
+//! Everything is derived from `VECTOR_SEED` and pinned to `VECTOR_TIMESTAMP`,
+//! so `generate` always returns the same JSON. The committed copy lives in
+//! `test-vectors/v1.json`; after an intended wire format change, regenerate
+//! it with `ROOTLESS_UPDATE_VECTORS=1 cargo test test_vectors`.
+
+use pyo3::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde_json::{json, Value};
+
+use crate::blockchain::{Block, Blockchain};
+use crate::content::Content;
+use crate::crypto::canonical_value;
+use crate::identity::UserIdentity;
+
+/// Seed every vector is derived from
+pub const VECTOR_SEED: [u8; 32] = *b"RootlessNet test vectors seed v1";
+
+/// Creation time written into every identity, content and block (2023-11-14)
+pub const VECTOR_TIMESTAMP: i64 = 1_700_000_000;
+
+/// Difficulty the vector chain is mined at
+const VECTOR_DIFFICULTY: usize = 1;
+
+/// Build the vectors from `seed`
+///
+/// Holds an author identity, a signed text post, that post encrypted for a
+/// second identity, and a three-block chain signed by the author.
+pub fn generate(seed: [u8; 32]) -> PyResult<Value> {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let author_seed: [u8; 32] = rng.gen();
+    let recipient_seed: [u8; 32] = rng.gen();
+    let author = pinned_identity(author_seed);
+    let recipient = pinned_identity(recipient_seed);
+
+    let post = pinned_content(Content::text(
+        "Hello".to_string(),
+        "First post".to_string(),
+        "Hello, RootlessNet!".to_string(),
+    ));
+    let signature = author.sign_content(&post)?;
+    let encrypted = post.encrypt_for_with_rng(vec![recipient.public_key.clone()], &mut rng)?;
+
+    Ok(json!({
+        "version": 1,
+        "seed": hex::encode(seed),
+        "identity": identity_vector(&author, author_seed),
+        "content": {
+            "content": canonical_value(&post),
+            "signing_bytes": hex::encode(post.signing_bytes()),
+            "signature": hex::encode(signature),
+        },
+        "encrypted_message": {
+            "recipient": identity_vector(&recipient, recipient_seed),
+            "plaintext": post.data,
+            "content": canonical_value(&encrypted),
+        },
+        "chain": canonical_value(&vector_chain(&author)?),
+    }))
+}
+
+/// Vectors for `VECTOR_SEED` as pretty-printed JSON with a trailing newline
+pub fn to_json() -> PyResult<String> {
+    let vectors = generate(VECTOR_SEED)?;
+    serde_json::to_string_pretty(&vectors)
+        .map(|json| json + "\n")
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+}
+
+/// Public fields of an identity and the seed that recreates it
+fn identity_vector(identity: &UserIdentity, seed: [u8; 32]) -> Value {
+    json!({
+        "seed": hex::encode(seed),
+        "public_key": identity.public_key,
+        "verifying_key": hex::encode(&identity.verifying_key_bytes),
+        "fingerprint": identity.fingerprint(),
+        "created_at": identity.created_at,
+    })
+}
+
+fn pinned_identity(seed: [u8; 32]) -> UserIdentity {
+    let mut identity = UserIdentity::from_seed(seed);
+    identity.created_at = VECTOR_TIMESTAMP;
+    identity
+}
+
+fn pinned_content(mut content: Content) -> Content {
+    content.created_at = VECTOR_TIMESTAMP;
+    content.id = Content::generate_id(&content.data, VECTOR_TIMESTAMP);
+    content
+}
+
+/// Genesis plus two posts by `author`, each block one second after the last
+fn vector_chain(author: &UserIdentity) -> PyResult<Blockchain> {
+    let mut chain = Blockchain::new();
+    chain.difficulty = VECTOR_DIFFICULTY;
+
+    let genesis = &mut chain.chain[0];
+    genesis.content = pinned_content(genesis.content.clone());
+    genesis.timestamp = VECTOR_TIMESTAMP;
+    genesis.hash = genesis.calculate_hash();
+    chain.genesis_hash = genesis.hash.clone();
+
+    for (i, text) in ["First block", "Second block"].into_iter().enumerate() {
+        let previous = chain.chain.last().unwrap();
+        let content = pinned_content(Content::text(
+            format!("Post {}", i + 1),
+            String::new(),
+            text.to_string(),
+        ));
+        let mut block = Block::new(previous.index + 1, content, author.public_key.clone(), previous.hash.clone());
+        block.timestamp = VECTOR_TIMESTAMP + i as i64 + 1;
+        block.hash = block.calculate_hash();
+        // Sequential search so the nonce doesn't depend on thread scheduling
+        block.mine_with(VECTOR_DIFFICULTY, Default::default(), 0, |_| Ok(()))?;
+        block.sign(author)?;
+        chain.chain.push(block);
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMITTED: &str = include_str!("../test-vectors/v1.json");
+
+    #[test]
+    fn test_vectors_match_committed_file() {
+        let generated = to_json().unwrap();
+        if std::env::var_os("ROOTLESS_UPDATE_VECTORS").is_some() {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test-vectors/v1.json");
+            std::fs::write(path, &generated).unwrap();
+            return;
+        }
+        assert_eq!(generated, COMMITTED, "wire format changed; see the test_vectors module docs");
+    }
+
+    #[test]
+    fn test_vectors_are_self_consistent() {
+        let vectors: Value = serde_json::from_str(COMMITTED).unwrap();
+        let recipient_seed: [u8; 32] = hex::decode(vectors["encrypted_message"]["recipient"]["seed"].as_str().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let recipient = UserIdentity::from_seed(recipient_seed);
+        let encrypted: Content = serde_json::from_value(vectors["encrypted_message"]["content"].clone()).unwrap();
+        assert_eq!(encrypted.decrypt_with(&recipient).unwrap(), "Hello, RootlessNet!");
+
+        let chain = Blockchain::from_json(&vectors["chain"].to_string()).unwrap();
+        assert_eq!(chain.len(), 3);
+        assert!(chain.is_valid());
+    }
+}
@@ -1,10 +1,16 @@
 //! Wordlist for mnemonic key generation
 //! Contains 4000 unique words for private key mnemonic phrases
 
+use pyo3::prelude::*;
+use std::collections::HashSet;
+
+/// Documented number of words in `WORDLIST`
+pub const WORDLIST_LEN: usize = 4000;
+
 /// 4000-word list for mnemonic generation
 /// Based on common English words, avoiding ambiguous/similar words
 pub const WORDLIST: &[&str] = &[
-    // A words (400)
+    // abandon to craft (400)
     "abandon", "ability", "able", "about", "above", "absent", "absorb", "abstract", "absurd", "abuse",
     "access", "accident", "account", "accuse", "achieve", "acid", "acoustic", "acquire", "across", "act",
     "action", "actor", "actress", "actual", "adapt", "add", "addict", "address", "adjust", "admit",
@@ -45,38 +51,37 @@ pub const WORDLIST: &[&str] = &[
     "common", "company", "concert", "conduct", "confirm", "congress", "connect", "consider", "control", "convince",
     "cook", "cool", "copper", "copy", "coral", "core", "corn", "correct", "cost", "cotton",
     "couch", "country", "couple", "course", "cousin", "cover", "coyote", "crack", "cradle", "craft",
-    // B words (400)
-    "banana", "banish", "banter", "baptize", "barefoot", "barley", "barrage", "barrier", "bartender", "basement",
-    "basin", "basket", "battalion", "beacon", "beaker", "bearing", "beating", "beauty", "beaver", "beckon",
-    "bedroom", "beetle", "begging", "behemoth", "belated", "beloved", "beneath", "benefit", "benevolent", "beret",
-    "berries", "besides", "bestow", "betrayal", "beverage", "bewilder", "beyond", "bicycle", "bidding", "billion",
-    "binding", "biscuit", "bizarre", "blacksmith", "blanket", "blaring", "bleacher", "bleeding", "blessing", "blinding",
-    "blizzard", "blockade", "bloodshed", "blooming", "blossom", "blotting", "blueberry", "blueprint", "blunder", "boarding",
-    "boasting", "bobcat", "bodily", "boiling", "boldness", "bolster", "bombard", "bonfire", "bookmark", "booming",
-    "boosting", "bordered", "borrowing", "botanical", "bothering", "boulder", "boundary", "bountiful", "boutique", "bowling",
-    "bracelet", "bragging", "braking", "branches", "branding", "bravery", "breaking", "breeding", "brewing", "bribery",
-    "bridging", "briefcase", "brigade", "brilliant", "bringing", "broadcast", "brochure", "brooding", "brownie", "browsing",
-    "bruising", "brushing", "brutality", "bubbling", "buckling", "budding", "buffalo", "buffering", "building", "bulletin",
-    "bumping", "bungalow", "bungling", "buoyant", "burglary", "burial", "burning", "bursting", "bushel", "bustling",
-    "butcher", "butterfly", "buttress", "buzzing", "bypassing", "byzantine", "cabinet", "cackle", "cadence", "calamity",
-    "calculate", "calendar", "caliber", "calling", "calmness", "campaign", "camping", "canceling", "candidate", "canister",
-    "capability", "capacity", "capsule", "captive", "capture", "caravan", "cardinal", "carefree", "caretaker", "carnival",
-    "carousel", "carrying", "carving", "cascade", "cashier", "casting", "catalyst", "catching", "cathedral", "cavalry",
-    "cavernous", "celebrate", "celebrity", "celestial", "cemetery", "censorship", "centered", "centimeter", "ceremony", "certainty",
-    "certified", "cessation", "chafing", "chaining", "chairman", "challenge", "chambered", "champion", "chancery", "chandelier",
+    // banish to complex (289)
+    "banish", "banter", "baptize", "barefoot", "barley", "barrage", "barrier", "bartender", "basement", "basin",
+    "battalion", "beacon", "beaker", "bearing", "beating", "beaver", "beckon", "bedroom", "beetle", "begging",
+    "behemoth", "belated", "beloved", "beneath", "benevolent", "beret", "berries", "besides", "bestow", "betrayal",
+    "beverage", "bewilder", "bidding", "billion", "binding", "biscuit", "bizarre", "blacksmith", "blaring", "bleacher",
+    "bleeding", "blessing", "blinding", "blizzard", "blockade", "bloodshed", "blooming", "blotting", "blueberry", "blueprint",
+    "blunder", "boarding", "boasting", "bobcat", "bodily", "boiling", "boldness", "bolster", "bombard", "bonfire",
+    "bookmark", "booming", "boosting", "bordered", "borrowing", "botanical", "bothering", "boulder", "boundary", "bountiful",
+    "boutique", "bowling", "bracelet", "bragging", "braking", "branches", "branding", "bravery", "breaking", "breeding",
+    "brewing", "bribery", "bridging", "briefcase", "brigade", "brilliant", "bringing", "broadcast", "brochure", "brooding",
+    "brownie", "browsing", "bruising", "brushing", "brutality", "bubbling", "buckling", "budding", "buffering", "building",
+    "bulletin", "bumping", "bungalow", "bungling", "buoyant", "burglary", "burial", "burning", "bursting", "bushel",
+    "bustling", "butcher", "butterfly", "buttress", "buzzing", "bypassing", "byzantine", "cabinet", "cackle", "cadence",
+    "calamity", "calculate", "calendar", "caliber", "calling", "calmness", "campaign", "camping", "canceling", "candidate",
+    "canister", "capability", "capacity", "capsule", "captive", "capture", "caravan", "cardinal", "carefree", "caretaker",
+    "carnival", "carousel", "carrying", "carving", "cascade", "cashier", "casting", "catalyst", "catching", "cathedral",
+    "cavalry", "cavernous", "celebrate", "celebrity", "celestial", "cemetery", "censorship", "centered", "centimeter", "ceremony",
+    "certainty", "certified", "cessation", "chafing", "chaining", "chairman", "challenge", "chambered", "chancery", "chandelier",
     "changing", "channeled", "chanting", "chaotic", "character", "charging", "charitable", "charming", "chartered", "chasing",
     "chattering", "checking", "cheerful", "chemical", "cherished", "chestnut", "chewing", "chieftain", "childcare", "chilling",
     "chimpanzee", "chirping", "chocolate", "choicest", "chopping", "choreograph", "christian", "chronicle", "chrysalis", "chuckling",
-    "churning", "cinnamon", "circling", "circuitry", "circular", "citation", "claiming", "clamoring", "clapping", "clarified",
-    "clashing", "classical", "clattering", "cleaner", "cleaning", "clearance", "clearing", "clemency", "clenching", "clerical",
-    "clicking", "climactic", "climbing", "clinging", "clinical", "clipping", "cloaking", "clockwise", "clogging", "cloister",
-    "closeness", "clothing", "cloudless", "clouding", "clowning", "clubbing", "clumping", "clustering", "clutching", "coaching",
-    "coalition", "coasting", "coating", "cobbler", "cobweb", "cocktail", "codename", "coercion", "cogent", "cognition",
-    "cohesion", "coincide", "colander", "coldness", "collapse", "collar", "colleague", "collecting", "collision", "colonial",
-    "colonize", "coloring", "colossal", "combating", "combined", "comeback", "comedian", "cometary", "comforting", "commander",
-    "commence", "commend", "commerce", "commission", "commit", "committee", "commodity", "communal", "communion", "community",
-    "commuting", "companion", "comparing", "compass", "compelled", "competing", "compiled", "complain", "complete", "complex",
-    // C words (400)
+    "churning", "circling", "circuitry", "circular", "citation", "claiming", "clamoring", "clapping", "clarified", "clashing",
+    "classical", "clattering", "cleaner", "cleaning", "clearance", "clearing", "clemency", "clenching", "clerical", "clicking",
+    "climactic", "climbing", "clinging", "clinical", "clipping", "cloaking", "clockwise", "clogging", "cloister", "closeness",
+    "clothing", "cloudless", "clouding", "clowning", "clubbing", "clumping", "clustering", "clutching", "coaching", "coalition",
+    "coasting", "coating", "cobbler", "cobweb", "cocktail", "codename", "coercion", "cogent", "cognition", "cohesion",
+    "coincide", "colander", "coldness", "collapse", "collar", "colleague", "collecting", "collision", "colonial", "colonize",
+    "coloring", "colossal", "combating", "combined", "comeback", "comedian", "cometary", "comforting", "commander", "commence",
+    "commend", "commerce", "commission", "commit", "committee", "commodity", "communal", "communion", "community", "commuting",
+    "companion", "comparing", "compass", "compelled", "competing", "compiled", "complain", "complete", "complex",
+    // crane to elderly (300)
     "crane", "crater", "crawl", "crazy", "cream", "create", "creative", "creature", "credit", "creek",
     "creep", "crescent", "crew", "cricket", "crime", "criminal", "crisp", "critical", "crocodile", "crop",
     "cross", "crouch", "crowd", "crucial", "crude", "cruise", "crumble", "crunch", "crush", "crystal",
@@ -107,45 +112,36 @@ pub const WORDLIST: &[&str] = &[
     "easily", "east", "eastern", "easy", "eat", "eating", "echo", "eclipse", "ecology", "economic",
     "economy", "edge", "edit", "edition", "editor", "educate", "education", "effect", "effective", "effort",
     "egg", "ego", "eight", "eighteen", "either", "elaborate", "elastic", "elbow", "elder", "elderly",
-    // D words (400)
-    "dolphin", "domain", "domestic", "dominant", "dominate", "donate", "donation", "donkey", "donor", "doom",
-    "door", "doorbell", "doorstep", "doorway", "dormant", "dosage", "double", "doubling", "doubtful", "doubting",
-    "doughnut", "download", "downtown", "downward", "dozing", "drabness", "draconian", "drafting", "drainage", "draining",
-    "dramatic", "draping", "drastic", "drawing", "dreadful", "dreaming", "drenched", "dressing", "dribbling", "drifting",
-    "drilling", "drinking", "dripping", "driving", "drizzle", "drooping", "dropping", "drought", "drowning", "drumbeat",
-    "drumming", "drunken", "dryness", "dualistic", "dubbing", "dubious", "duckling", "ductile", "dullness", "dumping",
-    "dungeon", "duration", "durable", "dustpan", "dutiful", "dwelling", "dwindling", "dynamic", "dynamite", "dynasty",
-    "dyslexia", "eager", "eagerness", "eagle", "eardrum", "earliest", "earmark", "earnest", "earnings", "earpiece",
-    "earthen", "earthly", "earthworm", "easement", "eastern", "eastward", "ebony", "eccentric", "echoing", "eclipsed",
-    "ecological", "economic", "economist", "ecosystem", "ecstatic", "edgewise", "edging", "edible", "edifice", "editing",
-    "editorial", "educated", "educator", "eerie", "effective", "efficacy", "efficient", "effigy", "effluent", "effortless",
-    "eggplant", "eggshell", "egotism", "eighteen", "eighteenth", "eighty", "ejecting", "elaborate", "elapsed", "elastic",
-    "elated", "elbow", "elder", "elderly", "eldest", "elected", "election", "elective", "electoral", "electric",
-    "electron", "elegance", "elegant", "element", "elephant", "elevated", "elevator", "eleven", "eleventh", "eligible",
-    "eliminate", "elite", "elixir", "elongate", "eloquent", "elsewhere", "elucidate", "elusive", "emanate", "embargo",
-    "embark", "embarrass", "embassy", "embedded", "embellish", "ember", "emblem", "embodied", "embrace", "emerge",
-    "emergency", "emerging", "emigrant", "emigrate", "eminence", "eminent", "emission", "emitter", "emotion", "emotional",
-    "empathy", "emperor", "emphasis", "emphatic", "empire", "empirical", "employ", "employee", "employer", "empower",
-    "empress", "emptiness", "empty", "emulate", "enable", "enact", "enchant", "encircle", "enclose", "encode",
-    "encompass", "encounter", "encourage", "encrypt", "endeavor", "endemic", "endless", "endorse", "endpoint", "endure",
-    "enemy", "energetic", "energy", "enforce", "engage", "engine", "engineer", "enhance", "enigma", "enjoy",
-    "enlarge", "enlighten", "enlist", "enormous", "enough", "enrage", "enrich", "enroll", "ensemble", "enslave",
-    "ensure", "entail", "enter", "enterprise", "entertain", "enthusiasm", "enthusiast", "entire", "entirely", "entitle",
-    "entity", "entrance", "entrant", "entrench", "entrust", "entry", "envelope", "enviable", "envision", "enzyme",
-    "ephemeral", "epidemic", "episode", "epitome", "equal", "equality", "equally", "equation", "equator", "equip",
-    "equipment", "equipped", "equity", "equivalent", "eradicate", "erase", "erection", "eroding", "erosion", "errand",
-    "errant", "erratic", "error", "eruption", "escalate", "escape", "escort", "esoteric", "especially", "espionage",
-    "espresso", "essay", "essence", "essential", "establish", "estate", "esteem", "estimate", "eternal", "eternity",
-    "ethical", "ethics", "ethnic", "etiquette", "euphoria", "evacuate", "evaluate", "evasion", "evasive", "evening",
-    "evenly", "event", "eventual", "eventually", "evergreen", "everlasting", "everyday", "everyone", "everything", "everywhere",
-    // E-F words (400)
-    "electric", "elephant", "elevate", "elevator", "eleven", "elite", "else", "elusive", "email", "embark",
-    "embrace", "emerge", "emergency", "emotion", "emperor", "emphasis", "empire", "employ", "employee", "employer",
-    "empower", "empty", "enable", "enact", "encounter", "encourage", "end", "endless", "endorse", "endure",
-    "enemy", "energy", "engage", "engine", "enhance", "enjoy", "enormous", "enough", "ensure", "enter",
-    "entire", "entrance", "entry", "envelope", "environment", "episode", "equal", "equip", "equity", "era",
-    "erode", "erosion", "error", "escape", "essay", "essence", "essential", "establish", "estate", "estimate",
-    "eternal", "ethics", "ethnic", "evaluate", "evade", "evening", "event", "eventual", "ever", "every",
+    // dominate to everywhere (268)
+    "dominate", "donation", "doorbell", "doorstep", "dormant", "dosage", "doubling", "doubtful", "doubting", "doughnut",
+    "downward", "dozing", "drabness", "draconian", "drafting", "drainage", "draining", "draping", "dreadful", "dreaming",
+    "drenched", "dribbling", "drifting", "drilling", "drinking", "dripping", "drizzle", "drooping", "dropping", "drowning",
+    "drumbeat", "drumming", "drunken", "dryness", "dualistic", "dubbing", "dubious", "duckling", "ductile", "dullness",
+    "dumping", "dungeon", "duration", "durable", "dustpan", "dutiful", "dwindling", "dynamite", "dynasty", "dyslexia",
+    "eagerness", "eardrum", "earliest", "earmark", "earnest", "earnings", "earpiece", "earthen", "earthly", "earthworm",
+    "easement", "eastward", "ebony", "eccentric", "echoing", "eclipsed", "ecological", "economist", "ecosystem", "ecstatic",
+    "edgewise", "edging", "edible", "edifice", "editing", "editorial", "educated", "educator", "eerie", "efficacy",
+    "efficient", "effigy", "effluent", "effortless", "eggplant", "eggshell", "egotism", "eighteenth", "eighty", "ejecting",
+    "elapsed", "elated", "eldest", "elected", "election", "elective", "electoral", "electric", "electron", "elegance",
+    "elegant", "element", "elephant", "elevated", "elevator", "eleven", "eleventh", "eligible", "eliminate", "elite",
+    "elixir", "elongate", "eloquent", "elsewhere", "elucidate", "elusive", "emanate", "embargo", "embark", "embarrass",
+    "embassy", "embedded", "embellish", "ember", "emblem", "embodied", "embrace", "emerge", "emergency", "emerging",
+    "emigrant", "emigrate", "eminence", "eminent", "emission", "emitter", "emotion", "emotional", "empathy", "emperor",
+    "emphasis", "emphatic", "empire", "empirical", "employ", "employee", "employer", "empower", "empress", "emptiness",
+    "empty", "emulate", "enable", "enact", "enchant", "encircle", "enclose", "encode", "encompass", "encounter",
+    "encourage", "encrypt", "endeavor", "endemic", "endless", "endorse", "endpoint", "endure", "enemy", "energetic",
+    "energy", "enforce", "engage", "engine", "engineer", "enhance", "enigma", "enjoy", "enlarge", "enlighten",
+    "enlist", "enormous", "enough", "enrage", "enrich", "enroll", "ensemble", "enslave", "ensure", "entail",
+    "enter", "enterprise", "entertain", "enthusiasm", "enthusiast", "entire", "entirely", "entitle", "entity", "entrance",
+    "entrant", "entrench", "entrust", "entry", "envelope", "enviable", "envision", "enzyme", "ephemeral", "epidemic",
+    "episode", "epitome", "equal", "equality", "equally", "equation", "equator", "equip", "equipment", "equipped",
+    "equity", "equivalent", "eradicate", "erase", "erection", "eroding", "erosion", "errand", "errant", "erratic",
+    "error", "eruption", "escalate", "escape", "escort", "esoteric", "especially", "espionage", "espresso", "essay",
+    "essence", "essential", "establish", "estate", "esteem", "estimate", "eternal", "eternity", "ethical", "ethics",
+    "ethnic", "etiquette", "euphoria", "evacuate", "evaluate", "evasion", "evasive", "evening", "evenly", "event",
+    "eventual", "eventually", "evergreen", "everlasting", "everyday", "everyone", "everything", "everywhere",
+    // elevate to frontier (240)
+    "elevate", "else", "email", "end", "environment", "era", "erode", "evade", "ever", "every",
     "evidence", "evil", "evolve", "exact", "exaggerate", "examine", "example", "exceed", "excellent", "except",
     "exchange", "excite", "exclude", "excuse", "execute", "exercise", "exhaust", "exhibit", "exile", "exist",
     "exit", "exotic", "expand", "expect", "expense", "expensive", "experience", "experiment", "expert", "expire",
@@ -169,7 +165,7 @@ pub const WORDLIST: &[&str] = &[
     "four", "fourteen", "fourth", "fox", "fraction", "fragile", "fragment", "frame", "framework", "france",
     "frank", "frankly", "fraud", "free", "freedom", "freely", "freeze", "french", "frequency", "frequent",
     "fresh", "friction", "friday", "friend", "friendly", "friendship", "frog", "from", "front", "frontier",
-    // G-H words (400)
+    // galaxy to idea (300)
     "galaxy", "gallery", "gallon", "game", "gaming", "gang", "gap", "garage", "garbage", "garden",
     "garlic", "garment", "gas", "gasoline", "gasp", "gate", "gather", "gauge", "gave", "gaze",
     "gear", "gem", "gender", "gene", "general", "generate", "generation", "generous", "genetic", "genius",
@@ -200,7 +196,7 @@ pub const WORDLIST: &[&str] = &[
     "house", "household", "housing", "how", "however", "hub", "huge", "human", "humanity", "humble",
     "humid", "humor", "hundred", "hunger", "hungry", "hunt", "hunter", "hunting", "hurdle", "hurricane",
     "hurry", "hurt", "husband", "hybrid", "hydrogen", "hypothesis", "ice", "iceberg", "icon", "idea",
-    // I-J words (400)
+    // ideal to kid (300)
     "ideal", "identical", "identify", "identity", "ideology", "idle", "idly", "idol", "ignite", "ignorance",
     "ignorant", "ignore", "ill", "illegal", "illness", "illuminate", "illusion", "illustrate", "illustration", "image",
     "imagery", "imaginary", "imagination", "imagine", "imbalance", "imitate", "immature", "immediate", "immediately", "immense",
@@ -231,7 +227,7 @@ pub const WORDLIST: &[&str] = &[
     "joke", "jolly", "journal", "journalism", "journalist", "journey", "joy", "joyful", "judge", "judgment",
     "juice", "july", "jump", "junction", "june", "jungle", "junior", "jury", "just", "justice",
     "justify", "keen", "keep", "keeper", "kernel", "kettle", "key", "keyboard", "kick", "kid",
-    // K-L words (400)
+    // kidney to master (300)
     "kidney", "kill", "killer", "killing", "kind", "kindergarten", "kindness", "king", "kingdom", "kiss",
     "kit", "kitchen", "kite", "knee", "knife", "knight", "knit", "knock", "knot", "know",
     "knowing", "knowledge", "known", "label", "labor", "laboratory", "laborer", "lace", "lack", "ladder",
@@ -262,7 +258,7 @@ pub const WORDLIST: &[&str] = &[
     "manor", "manual", "manufacture", "manufacturer", "manuscript", "many", "map", "maple", "marathon", "marble",
     "march", "margin", "marginal", "marine", "mark", "marker", "market", "marketing", "marketplace", "marriage",
     "married", "marry", "marsh", "martial", "marvel", "marvelous", "mask", "mass", "massive", "master",
-    // M-N words (400)
+    // masterpiece to nobody (300)
     "masterpiece", "match", "matching", "mate", "material", "math", "mathematics", "matrix", "matter", "mattress",
     "mature", "maturity", "maximize", "maximum", "may", "maybe", "mayor", "maze", "meadow", "meal",
     "mean", "meaning", "meaningful", "means", "meantime", "meanwhile", "measure", "measurement", "meat", "mechanic",
@@ -293,7 +289,7 @@ pub const WORDLIST: &[&str] = &[
     "nephew", "nerve", "nervous", "nest", "net", "network", "neutral", "never", "nevertheless", "new",
     "newly", "news", "newsletter", "newspaper", "next", "nice", "nicely", "niche", "nickel", "nickname",
     "niece", "night", "nightmare", "nine", "nineteen", "ninety", "ninth", "nitrogen", "noble", "nobody",
-    // O-P words (400)
+    // nod to penny (300)
     "nod", "noise", "noisy", "nominal", "nominate", "nomination", "nominee", "none", "nonetheless", "nonsense",
     "noon", "nor", "norm", "normal", "normally", "north", "northeast", "northern", "northwest", "nose",
     "not", "notable", "notably", "note", "notebook", "nothing", "notice", "notify", "notion", "notorious",
@@ -324,38 +320,14 @@ pub const WORDLIST: &[&str] = &[
     "pattern", "pause", "pave", "pavement", "paw", "pay", "payment", "peace", "peaceful", "peach",
     "peak", "peanut", "pearl", "peasant", "peculiar", "pedal", "pedestrian", "pediatric", "peek", "peel",
     "peer", "peg", "pen", "penalty", "pencil", "pending", "penetrate", "penguin", "peninsula", "penny",
-    // Q-R words (400)
+    // pension to pickle (55)
     "pension", "people", "pepper", "per", "perceive", "percent", "percentage", "perception", "perfect", "perfectly",
     "perform", "performance", "performer", "perfume", "perhaps", "peril", "period", "periodic", "peripheral", "permanent",
     "permission", "permit", "persist", "persistent", "person", "personal", "personality", "personally", "personnel", "perspective",
     "persuade", "persuasion", "pest", "pet", "petition", "petroleum", "petty", "phantom", "phase", "phenomenon",
     "philosopher", "philosophy", "phone", "photo", "photograph", "photographer", "photography", "phrase", "physical", "physically",
-    "physician", "physics", "piano", "pick", "pickle", "pickup", "picnic", "picture", "pie", "piece",
-    "pier", "pig", "pigeon", "pile", "pilgrim", "pill", "pillar", "pillow", "pilot", "pin",
-    "pinch", "pine", "pink", "pioneer", "pipe", "pipeline", "pirate", "pistol", "pit", "pitch",
-    "pitcher", "pity", "pizza", "place", "placement", "plague", "plain", "plaintiff", "plan", "plane",
-    "planet", "plank", "planner", "planning", "plant", "plantation", "plasma", "plaster", "plastic", "plate",
-    "platform", "platinum", "play", "player", "playground", "plea", "plead", "pleasant", "please", "pleased",
-    "pleasure", "pledge", "plenty", "plot", "plow", "pluck", "plug", "plum", "plumber", "plunge",
-    "plus", "pocket", "pod", "poem", "poet", "poetry", "point", "pointed", "pointer", "poison",
-    "polar", "pole", "police", "policeman", "policy", "polish", "polite", "political", "politically", "politician",
-    "politics", "poll", "pollen", "pollution", "polo", "pond", "ponder", "pony", "pool", "poor",
-    "poorly", "pop", "pope", "popular", "popularity", "populate", "population", "porch", "pork", "port",
-    "portable", "portal", "portion", "portrait", "portray", "pose", "position", "positive", "positively", "possess",
-    "possession", "possibility", "possible", "possibly", "post", "postal", "poster", "postpone", "pot", "potato",
-    "potent", "potential", "potentially", "pottery", "poultry", "pound", "pour", "poverty", "powder", "power",
-    "powerful", "practical", "practically", "practice", "practitioner", "praise", "pray", "prayer", "preach", "preacher",
-    "precede", "precedent", "precious", "precise", "precisely", "precision", "predict", "prediction", "predominantly", "prefer",
-    "preference", "pregnant", "prejudice", "preliminary", "premier", "premiere", "premise", "premium", "preparation", "prepare",
-    "prepared", "prescribe", "prescription", "presence", "present", "presentation", "preserve", "president", "presidential", "press",
-    "pressure", "presumably", "presume", "pretend", "pretty", "prevail", "prevent", "prevention", "preview", "previous",
-    "previously", "prey", "price", "pride", "priest", "primarily", "primary", "prime", "primitive", "prince",
-    "princess", "principal", "principle", "print", "printer", "printing", "prior", "priority", "prison", "prisoner",
-    "privacy", "private", "privately", "privilege", "prize", "probability", "probable", "probably", "probe", "problem",
-    "procedure", "proceed", "proceeding", "process", "processing", "processor", "proclaim", "produce", "producer", "product",
-    "production", "productive", "productivity", "profession", "professional", "professor", "profile", "profit", "profitable", "profound",
-    "program", "programmer", "programming", "progress", "progressive", "prohibit", "project", "projection", "prominent", "promise",
-    // S words (400)
+    "physician", "physics", "piano", "pick", "pickle",
+    // sail to slightly (300)
     "sail", "sailor", "saint", "sake", "salad", "salary", "sale", "salmon", "salon", "salt",
     "salvation", "same", "sample", "sanction", "sanctuary", "sand", "sandwich", "sandy", "sane", "satisfaction",
     "satisfactory", "satisfy", "saturday", "sauce", "sausage", "savage", "save", "saving", "saw", "say",
@@ -386,7 +358,7 @@ pub const WORDLIST: &[&str] = &[
     "skeleton", "skeptic", "skeptical", "sketch", "ski", "skill", "skilled", "skin", "skip", "skirt",
     "skull", "sky", "slab", "slack", "slam", "slap", "slash", "slate", "slaughter", "slave",
     "slavery", "sleek", "sleep", "sleeping", "sleeve", "slice", "slick", "slide", "slight", "slightly",
-    // T-U words (400)
+    // table to truck (300)
     "table", "tablet", "tabloid", "tactic", "tactical", "tag", "tail", "tailor", "take", "takeover",
     "tale", "talent", "talented", "talk", "talking", "tall", "tame", "tan", "tank", "tap",
     "tape", "target", "tariff", "task", "taste", "tasty", "tattoo", "tax", "taxation", "taxi",
@@ -417,7 +389,7 @@ pub const WORDLIST: &[&str] = &[
     "treat", "treatment", "treaty", "tree", "tremendous", "trend", "trendy", "trial", "triangle", "tribal",
     "tribe", "tribute", "trick", "tricky", "trigger", "trillion", "trim", "trio", "trip", "triple",
     "triumph", "trivial", "trolley", "troop", "trophy", "tropical", "trouble", "troubled", "trousers", "truck",
-    // V-W words (400)
+    // vacation to yearly (300)
     "vacation", "vaccine", "vacuum", "vague", "vaguely", "vain", "valid", "validate", "validity", "valley",
     "valuable", "valuation", "value", "valve", "vampire", "van", "vanish", "vanity", "vapor", "variable",
     "variance", "variant", "variation", "varied", "variety", "various", "vary", "vase", "vast", "vastly",
@@ -448,25 +420,43 @@ pub const WORDLIST: &[&str] = &[
     "worrying", "worse", "worsen", "worship", "worst", "worth", "worthwhile", "worthy", "would", "wound",
     "wounded", "wrap", "wrapper", "wrath", "wreck", "wreckage", "wrestle", "wrestling", "wrist", "write",
     "writer", "writing", "written", "wrong", "wrongly", "yard", "yarn", "yeah", "year", "yearly",
-    // X-Y-Z words (200)
+    // yell to zoology (48)
     "yell", "yellow", "yes", "yesterday", "yet", "yield", "yoga", "yogurt", "yonder", "young",
     "youngster", "your", "yours", "yourself", "youth", "youthful", "zebra", "zenith", "zero", "zest",
-    "zigzag", "zinc", "zodiac", "zone", "zoning", "zoo", "zoom", "zucchini", "azure", "xenon",
-    "xerox", "xylophone", "yacht", "yank", "yawn", "yearn", "yeast", "yelp", "yield", "yoke",
-    "yolk", "yonder", "young", "younger", "youngest", "yours", "yourself", "yourselves", "youth", "youthful",
-    "zealot", "zealous", "zenith", "zephyr", "zero", "zest", "zigzag", "zinc", "zipper", "zodiac",
-    "zombie", "zone", "zoning", "zoo", "zoology", "zoom", "zucchini", "ability", "absolute", "absorb",
-    "academic", "academy", "accent", "accept", "access", "accident", "account", "accuracy", "accurate", "achieve",
-    "acquire", "action", "active", "activity", "actor", "actress", "actual", "adapt", "addition", "address",
-    "adequate", "adjust", "administration", "admire", "admit", "adopt", "adult", "advance", "advantage", "adventure",
-    "advertise", "advice", "advise", "affair", "affect", "afford", "afraid", "afternoon", "afterwards", "against",
-    "agency", "agenda", "agent", "aggressive", "agree", "agreement", "ahead", "aircraft", "airline", "airport",
-    "alarm", "album", "alcohol", "alert", "alien", "align", "alike", "alive", "alliance", "allocate",
-    "allow", "allowance", "almost", "alone", "alongside", "already", "alter", "alternative", "although", "altogether",
-    "aluminum", "always", "amateur", "amazing", "ambition", "ambitious", "ambulance", "amend", "amendment", "among",
-    "amount", "analyse", "analysis", "analyst", "analyze", "ancestor", "ancient", "angel", "angle", "angry",
-    "animal", "ankle", "anniversary", "announce", "annual", "another", "answer", "antenna", "anticipate", "anxiety",
-    "anxious", "anybody", "anymore", "anyone", "anything", "anyway", "anywhere", "apart", "apartment", "apology",
-    "apparatus", "apparent", "appeal", "appear", "appearance", "appetite", "applaud", "apple", "application", "apply",
-    "appoint", "appointment", "appreciate", "approach", "appropriate", "approval", "approve", "approximate", "april", "arbitrary",
+    "zigzag", "zinc", "zodiac", "zone", "zoning", "zoo", "zoom", "zucchini", "xenon", "xerox",
+    "xylophone", "yacht", "yank", "yawn", "yearn", "yeast", "yelp", "yoke", "yolk", "younger",
+    "youngest", "yourselves", "zealot", "zealous", "zephyr", "zipper", "zombie", "zoology",
 ];
+
+// Mnemonic entropy assumes the documented length; fail the build if it drifts
+const _: () = assert!(WORDLIST.len() == WORDLIST_LEN);
+
+/// Total and unique entry counts of `WORDLIST`
+#[pyfunction]
+pub fn wordlist_stats() -> (usize, usize) {
+    let unique: HashSet<&str> = WORDLIST.iter().copied().collect();
+    (WORDLIST.len(), unique.len())
+}
+
+/// Number of words in the mnemonic wordlist
+#[pyfunction]
+pub fn wordlist_len() -> usize {
+    WORDLIST.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wordlist_has_no_duplicates() {
+        let (total, unique) = wordlist_stats();
+        assert_eq!(total, WORDLIST_LEN);
+        assert_eq!(unique, total);
+    }
+
+    #[test]
+    fn test_wordlist_words_are_lowercase() {
+        assert!(WORDLIST.iter().all(|w| !w.is_empty() && w.chars().all(|c| c.is_ascii_lowercase())));
+    }
+}